@@ -20,6 +20,20 @@ fn main() {
         .filter_map(|(file_name, data)| data.parse::<Table>().ok().map(|table| (file_name, table)))
         .collect::<HashMap<String, Table>>();
 
+    // optional corpus frequencies, lines of "word<TAB>occurrences per million words"
+    let frequencies = std::fs::read_to_string("src/res/frequency.tsv").unwrap_or_default();
+    let frequencies = frequencies
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .filter_map(|(word, frequency)| {
+            frequency
+                .trim()
+                .parse::<f64>()
+                .ok()
+                .map(|frequency| (word.trim(), frequency))
+        })
+        .collect::<HashMap<&str, f64>>();
+
     // collect all words and compile them into a Table
     let words = std::fs::read_dir("src/res/sona/words/metadata/")
         .unwrap()
@@ -36,17 +50,88 @@ fn main() {
 
             table.insert(
                 "definition".into(),
-                definition.get(&word).unwrap().to_owned().into(),
+                definition.get(&word).unwrap().to_owned(),
             );
             table.insert(
                 "commentary".into(),
                 commentary.get(&word).unwrap().to_owned(),
             );
 
+            if let Some(frequency) = frequencies.get(word.as_str()) {
+                table.insert("frequency".into(), (*frequency).into());
+            }
+
             (word, table)
         })
         .collect::<HashMap<String, Table>>();
 
+    // facts about the data for the about screen and `tt --version --verbose`
+    let git = |format: &str| {
+        std::process::Command::new("git")
+            .args([
+                "-C",
+                "src/res/sona",
+                "log",
+                "-1",
+                &format!("--format={format}"),
+            ])
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|text| !text.is_empty())
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+
+    let license = ["LICENSE", "LICENSE.md", "LICENSE.txt"]
+        .iter()
+        .filter_map(|file| std::fs::read_to_string(format!("src/res/sona/{file}")).ok())
+        .find_map(|text| {
+            text.lines()
+                .map(|line| line.trim_matches(|c: char| c == '#' || c.is_whitespace()))
+                .find(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    // the categories in order of use, any others after them
+    let order = ["core", "common", "uncommon", "obscure", "sandbox"];
+    let mut categories: Vec<(String, usize)> = Vec::new();
+    for table in words.values() {
+        let category = table
+            .get("usage_category")
+            .and_then(|value| value.as_str())
+            .unwrap_or("unknown");
+
+        match categories.iter_mut().find(|(name, _)| name == category) {
+            Some((_, count)) => *count += 1,
+            None => categories.push((category.to_string(), 1)),
+        }
+    }
+    categories.sort_by_key(|(name, _)| {
+        (
+            order
+                .iter()
+                .position(|category| name == category)
+                .unwrap_or(order.len()),
+            name.clone(),
+        )
+    });
+
+    let about = format!(
+        "pub const DATA_DATE: &str = {:?};\n\
+         pub const DATA_COMMIT: &str = {:?};\n\
+         pub const DATA_LICENSE: &str = {license:?};\n\
+         pub const CATEGORIES: &[(&str, usize)] = &{categories:?};\n",
+        git("%cs"),
+        git("%h"),
+    );
+
+    let path = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("about.rs");
+    if std::fs::write(&path, about).is_err() {
+        panic!("failed to save file {}", path.display());
+    }
+
     // convert Table to toml
     let words_toml = match toml::to_string(&words) {
         Ok(text) => text,
@@ -0,0 +1,28 @@
+use crate::locale::{Locale, Message};
+
+// DATA_DATE, DATA_COMMIT, DATA_LICENSE and CATEGORIES, written by build.rs from the linku data
+include!(concat!(env!("OUT_DIR"), "/about.rs"));
+
+/// the version, where the word data comes from, the words in each category and the licenses,
+/// for the about screen and `tt --version --verbose`
+#[must_use]
+pub fn lines(locale: Locale) -> Vec<String> {
+    let total: usize = CATEGORIES.iter().map(|(_, count)| count).sum();
+
+    let mut lines = vec![
+        format!("tt {}", env!("CARGO_PKG_VERSION")),
+        locale.fill(Message::DataSnapshot, &[&DATA_DATE, &DATA_COMMIT]),
+        String::new(),
+    ];
+
+    lines.extend(
+        CATEGORIES
+            .iter()
+            .map(|(category, count)| format!("{count:>5} {category}")),
+    );
+    lines.push(format!("{total:>5} {}", locale.get(Message::WordsInAll)));
+    lines.push(String::new());
+    lines.push(locale.fill(Message::DataLicense, &[&DATA_LICENSE]));
+
+    lines
+}
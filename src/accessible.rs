@@ -0,0 +1,268 @@
+use crate::{cli::Start, config::Config, data_path, load_pins, record_goals, session, Mode};
+use ratatui::crossterm::{
+    event::{self, Event},
+    terminal,
+};
+use std::{io::Write, time::Duration};
+use tt::{
+    code,
+    error::{Error, Result},
+    history::History,
+    keymap::{Action, Context, Keymap},
+    lesson::{Lesson, PassCriteria},
+    locale::{Locale, Message},
+    Game, GameSettings, TestCode,
+};
+
+/// what the next test is made from
+enum Source {
+    Words,
+    Numbers,
+    Lesson(Lesson),
+}
+
+impl Source {
+    const fn mode(&self) -> Mode {
+        match self {
+            Self::Words => Mode::Words,
+            Self::Numbers => Mode::Numbers,
+            Self::Lesson(_) => Mode::Lesson,
+        }
+    }
+}
+
+/// the game for screen readers: nothing is drawn, only new lines are written when something
+/// happens, the word to type next and every mistake, without borders or colours to read past
+pub fn run(
+    config: &Config,
+    profile: Option<&str>,
+    mut errors: Vec<Error>,
+    start: Start,
+) -> Result<()> {
+    let mut settings = config.game.clone();
+    settings.pins.extend(load_pins(
+        data_path(profile, "pins.toml").as_deref(),
+        &mut errors,
+    ));
+
+    for error in &errors {
+        eprintln!("{error}");
+    }
+
+    let keymap = Keymap::new(&config.keys);
+
+    let (source, first) = match start {
+        Start::Menu => (Source::Words, None),
+        Start::Code(code) => {
+            settings = code.settings(&settings);
+            let source = if code.numbers {
+                Source::Numbers
+            } else {
+                Source::Words
+            };
+            (source, Some(code))
+        }
+        Start::Lesson(lesson) => (Source::Lesson(lesson), None),
+    };
+
+    terminal::enable_raw_mode()?;
+
+    let result = practice(&settings, config, &keymap, profile, &source, first);
+
+    terminal::disable_raw_mode()?;
+
+    result
+}
+
+/// one test after another until escape
+#[allow(clippy::too_many_lines)]
+fn practice(
+    settings: &GameSettings<usize>,
+    config: &Config,
+    keymap: &Keymap,
+    profile: Option<&str>,
+    source: &Source,
+    mut first: Option<TestCode>,
+) -> Result<()> {
+    let locale = config.locale;
+    let key = |context, action| {
+        keymap
+            .key(context, action)
+            .map(|key| key.to_string())
+            .unwrap_or_default()
+    };
+    let history_path = data_path(profile, "history.toml");
+    let pass = match source {
+        Source::Lesson(lesson) if lesson.pass.is_set() => Some(lesson.pass),
+        _ => Some(settings.pass).filter(PassCriteria::is_set),
+    };
+    let time_limit = Some(Duration::from_secs(settings.time_secs)).filter(|limit| !limit.is_zero());
+
+    say(&locale.fill(
+        Message::AccessibleMode,
+        &[&key(Context::Game, Action::Pause)],
+    ))?;
+
+    loop {
+        // a code only makes the first test, the ones after are new
+        let mut rng = first
+            .take()
+            .map_or_else(|| code::rng(rand::random()), |code| code.rng());
+
+        let game = match source {
+            Source::Words => Game::new(settings, &mut rng)?,
+            Source::Numbers => Game::numbers(settings, &mut rng),
+            Source::Lesson(lesson) => Game::from_target(lesson.target(settings.len, &mut rng)?),
+        };
+        let mut game = Game {
+            rules: settings.into(),
+            ..game
+        };
+
+        let words: Vec<String> = game.target.split(' ').map(str::to_string).collect();
+        say(&locale.fill(Message::NewTestOf, &[&words.len()]))?;
+        announce(locale, &words, 0)?;
+
+        let end = loop {
+            // the clock starts with the first key, after it a key is only waited for as long
+            // as the test has left
+            if let Some(limit) = time_limit.filter(|_| !game.key_log.is_empty()) {
+                let left = limit.saturating_sub(game.elapsed());
+
+                if left.is_zero() || !event::poll(left)? {
+                    break End::Finished;
+                }
+            }
+
+            let event = event::read()?;
+            let Event::Key(key_event) = event else {
+                continue;
+            };
+
+            let action = keymap.action(Context::Game, &key_event);
+
+            match action {
+                Some(Action::Pause | Action::Quit) => break End::Quit,
+                Some(Action::Restart) => break End::Restart,
+                _ => (),
+            }
+
+            let word = game.input.matches(' ').count();
+            let strokes = game.strokes.len();
+
+            game.crossterm_event(&event, action);
+
+            match game.strokes.get(strokes) {
+                // a lone space is read as nothing
+                Some((' ', false, ..)) => say(locale.get(Message::WrongSpace))?,
+                Some((typed, false, ..)) => say(&locale.fill(Message::WrongKey, &[typed]))?,
+                _ => (),
+            }
+
+            if game.is_finished() {
+                break End::Finished;
+            }
+
+            let next = game.input.matches(' ').count();
+
+            if next != word {
+                announce(locale, &words, next)?;
+            }
+        };
+
+        match end {
+            End::Quit => break,
+            End::Restart => continue,
+            End::Finished => (),
+        }
+
+        game.pause();
+        let passed = pass.map(|pass| pass.passed(game.wpm(), game.accuracy()));
+
+        let mut history = match &history_path {
+            Some(path) => History::load(path)?,
+            None => History::default(),
+        };
+        history
+            .sessions
+            .push(session(&game, source.mode(), true, passed));
+        record_goals(&mut history, &settings.goals);
+
+        if let Some(path) = &history_path {
+            history.save(path, config.privacy)?;
+            config.hooks.after_save(path)?;
+        }
+
+        if let Some(session) = history.sessions.last() {
+            config.hooks.after_test(session)?;
+        }
+
+        say(&locale.fill(
+            Message::FinishedWith,
+            &[
+                &format!("{:.0}", game.wpm()),
+                &format!("{:.0}", game.accuracy() * 100.0),
+            ],
+        ))?;
+
+        match (passed, pass) {
+            (Some(true), _) => say(locale.get(Message::Passed))?,
+            (Some(false), Some(pass)) => {
+                say(&locale.fill(Message::NotPassed, &[&pass.wpm, &pass.accuracy]))?;
+            }
+            _ => (),
+        }
+
+        say(&locale.fill(
+            Message::AnotherTest,
+            &[
+                &key(Context::Menu, Action::Select),
+                &key(Context::Menu, Action::Back),
+            ],
+        ))?;
+
+        if !another(keymap)? {
+            break;
+        }
+    }
+
+    say(locale.get(Message::Quit))
+}
+
+/// how a test ended
+enum End {
+    Finished,
+    Restart,
+    Quit,
+}
+
+/// wait for select or back after a test, true for another one
+fn another(keymap: &Keymap) -> Result<bool> {
+    loop {
+        if let Event::Key(key_event) = event::read()? {
+            match keymap.action(Context::Menu, &key_event) {
+                Some(Action::Select) => return Ok(true),
+                Some(Action::Back | Action::Quit) => return Ok(false),
+                _ => (),
+            }
+        }
+    }
+}
+
+/// the word to type next and where it is in the test
+fn announce(locale: Locale, words: &[String], index: usize) -> Result<()> {
+    let Some(word) = words.get(index) else {
+        return Ok(());
+    };
+
+    say(&locale.fill(Message::WordOf, &[&(index + 1), &words.len(), word]))
+}
+
+/// one line for the screen reader, raw mode needs the carriage return
+fn say(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "{text}\r\n")?;
+    stdout.flush()?;
+
+    Ok(())
+}
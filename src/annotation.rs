@@ -0,0 +1,58 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use tt::history::Session;
+
+/// tags and a note being written for the last test
+#[derive(Default)]
+pub struct Annotation {
+    /// separated by spaces
+    pub tags: String,
+    pub note: String,
+    /// typing goes to the note instead of the tags
+    pub on_note: bool,
+}
+
+impl Annotation {
+    /// start from what the session already has
+    pub fn new(session: &Session) -> Self {
+        Self {
+            tags: session.tags.join(" "),
+            note: session.note.clone(),
+            on_note: false,
+        }
+    }
+
+    /// edit the field being typed in, Some(true) to save and Some(false) to cancel
+    pub fn key(&mut self, key_event: &KeyEvent) -> Option<bool> {
+        let field = if self.on_note {
+            &mut self.note
+        } else {
+            &mut self.tags
+        };
+
+        match key_event.code {
+            KeyCode::Enter => return Some(true),
+            KeyCode::Esc => return Some(false),
+            KeyCode::Tab => self.on_note = !self.on_note,
+            KeyCode::Backspace => _ = field.pop(),
+            KeyCode::Char(c) => field.push(c),
+            _ => (),
+        }
+
+        None
+    }
+
+    pub fn apply(&self, session: &mut Session) {
+        session.tags = self.tags.split_whitespace().map(str::to_string).collect();
+        session.note = self.note.trim().to_string();
+    }
+
+    /// both fields with a caret after the one being typed in
+    pub fn text(&self) -> String {
+        let (tags_caret, note_caret) = if self.on_note { ("", "_") } else { ("_", "") };
+
+        format!(
+            "tags {}{tags_caret}\nnote {}{note_caret}",
+            self.tags, self.note
+        )
+    }
+}
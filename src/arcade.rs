@@ -0,0 +1,214 @@
+use crate::{
+    error::{Error, Result},
+    game::Game,
+    history::Stroke,
+    settings::GameSettings,
+};
+use rand::Rng;
+use ratatui::crossterm::event::KeyCode;
+use std::time::Duration;
+
+/// screen heights a word falls per second at the start
+const START_SPEED: f64 = 0.06;
+
+/// seconds until words fall twice as fast as at the start
+const RAMP_SECS: f64 = 60.0;
+
+/// seconds between new words at the start, shorter as the speed ramps up
+const SPAWN_SECS: f64 = 3.0;
+
+/// words that can reach the bottom before the run is over
+pub const LIVES: usize = 3;
+
+pub struct FallingWord {
+    pub word: String,
+    /// 0 is the left edge and 1 the right
+    pub x: f64,
+    /// 0 is the top and 1 the bottom
+    pub y: f64,
+}
+
+/// words fall from the top and are caught by typing them before they land
+pub struct Arcade {
+    pub falling: Vec<FallingWord>,
+    /// typed so far towards the next word
+    pub input: String,
+    pub caught: usize,
+    pub lives: usize,
+    /// time played, the speed ramps with it
+    pub elapsed: Duration,
+    /// every word caught or landed in order, with what was typed for it
+    pub resolved: Vec<(String, String)>,
+    /// typed characters with whether they still matched a falling word
+    pub strokes: Vec<Stroke>,
+    settings: GameSettings<usize>,
+    /// words still to fall, picked like a test and taken from the end
+    queue: Vec<String>,
+    until_spawn: f64,
+}
+
+impl Arcade {
+    /// the first words already falling
+    ///
+    /// # Errors
+    /// the errors of `Game::new`, which picks the words
+    pub fn new(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Result<Self> {
+        let mut arcade = Self {
+            falling: Vec::new(),
+            input: String::new(),
+            caught: 0,
+            lives: LIVES,
+            elapsed: Duration::ZERO,
+            resolved: Vec::new(),
+            strokes: Vec::new(),
+            settings: settings.clone(),
+            queue: Vec::new(),
+            until_spawn: SPAWN_SECS,
+        };
+        arcade.spawn(rng)?;
+
+        Ok(arcade)
+    }
+
+    #[must_use]
+    pub const fn is_over(&self) -> bool {
+        self.lives == 0
+    }
+
+    /// how much faster than at the start everything is
+    fn ramp(&self) -> f64 {
+        1.0 + self.elapsed.as_secs_f64() / RAMP_SECS
+    }
+
+    /// screen heights per second
+    #[must_use]
+    pub fn speed(&self) -> f64 {
+        START_SPEED * self.ramp()
+    }
+
+    /// move the words down, land the ones at the bottom and drop new ones
+    ///
+    /// # Errors
+    /// the errors of `Game::new` when new words are picked
+    pub fn tick(&mut self, dt: Duration, rng: &mut impl Rng) -> Result<()> {
+        if self.is_over() {
+            return Ok(());
+        }
+
+        self.elapsed += dt;
+        let fall = self.speed() * dt.as_secs_f64();
+
+        for word in &mut self.falling {
+            word.y += fall;
+        }
+
+        let (landed, falling): (Vec<_>, Vec<_>) = std::mem::take(&mut self.falling)
+            .into_iter()
+            .partition(|word| word.y >= 1.0);
+        self.falling = falling;
+
+        for word in landed {
+            self.lives = self.lives.saturating_sub(1);
+            self.resolved.push((word.word, String::new()));
+        }
+
+        self.until_spawn -= dt.as_secs_f64();
+
+        if self.until_spawn <= 0.0 {
+            self.until_spawn = SPAWN_SECS / self.ramp();
+            self.spawn(rng)?;
+        }
+
+        Ok(())
+    }
+
+    fn spawn(&mut self, rng: &mut impl Rng) -> Result<()> {
+        if self.queue.is_empty() {
+            let game: Game<KeyCode> = Game::new(&self.settings, rng)?;
+            self.queue = game.target.split(' ').rev().map(str::to_string).collect();
+        }
+
+        let word = self.queue.pop().ok_or(Error::NoWords)?;
+        self.falling.push(FallingWord {
+            word,
+            x: rng.random_range(0.0..1.0),
+            y: 0.0,
+        });
+
+        Ok(())
+    }
+
+    /// a typed character, a space gives up on the input
+    pub fn key(&mut self, c: char) {
+        if self.is_over() {
+            return;
+        }
+
+        if c == ' ' {
+            self.input.clear();
+            return;
+        }
+
+        self.input.push(c);
+
+        let right = self
+            .falling
+            .iter()
+            .any(|word| word.word.starts_with(&self.input));
+        let ms = u64::try_from(self.elapsed.as_millis()).unwrap_or(u64::MAX);
+        self.strokes.push((c, right, ms, self.resolved.len()));
+
+        // the lowest of the same word is caught first
+        let caught = self
+            .falling
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| word.word == self.input)
+            .max_by(|(_, a), (_, b)| a.y.total_cmp(&b.y))
+            .map(|(index, _)| index);
+
+        if let Some(index) = caught {
+            let word = self.falling.remove(index);
+            self.resolved
+                .push((word.word, std::mem::take(&mut self.input)));
+            self.caught += 1;
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        self.input.pop();
+    }
+
+    /// characters of the caught words and a space after each, per five, per minute
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn wpm(&self) -> f64 {
+        let chars: usize = self
+            .resolved
+            .iter()
+            .filter(|(word, typed)| word == typed)
+            .map(|(word, _)| word.chars().count() + 1)
+            .sum();
+
+        let minutes = self.elapsed.as_secs_f64() / 60.0;
+
+        if minutes > 0.0 {
+            chars as f64 / 5.0 / minutes
+        } else {
+            0.0
+        }
+    }
+
+    /// the share of typed characters that matched a falling word
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        if self.strokes.is_empty() {
+            return 1.0;
+        }
+
+        let right = self.strokes.iter().filter(|stroke| stroke.1).count();
+
+        right as f64 / self.strokes.len() as f64
+    }
+}
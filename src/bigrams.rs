@@ -0,0 +1,77 @@
+use crate::history::{Session, MAX_GAP_MS};
+use std::collections::HashMap;
+
+/// times a bigram or trigram has to be typed before it can count as weak
+const MIN_SAMPLES: usize = 3;
+
+/// milliseconds added to a score for every time the last letter was wrong
+const ERROR_PENALTY_MS: f64 = 1000.0;
+
+#[derive(Default)]
+struct Samples {
+    total_ms: u64,
+    count: usize,
+    errors: usize,
+}
+
+/// the slowest and most mistyped runs of `N` letters over every session, worst first,
+/// letter pairs with 2 and trigrams with 3
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn weak<const N: usize>(sessions: &[Session], count: usize) -> Vec<[char; N]> {
+    let mut samples: HashMap<[char; N], Samples> = HashMap::new();
+
+    for session in sessions {
+        for run in session.strokes.windows(N) {
+            let Some((last, before)) = run.split_last() else {
+                continue;
+            };
+            let gaps = run
+                .windows(2)
+                .map(|pair| pair[1].2.saturating_sub(pair[0].2));
+
+            // the letters before the last have to be right for the run to be the one in the
+            // target, and a long pause is the user stopping rather than the letters being slow
+            if !run.iter().all(|(key, ..)| key.is_alphabetic())
+                || !before.iter().all(|(_, correct, ..)| *correct)
+                || gaps.clone().any(|gap| gap > MAX_GAP_MS)
+            {
+                continue;
+            }
+
+            let entry = samples
+                .entry(std::array::from_fn(|i| run[i].0))
+                .or_default();
+            entry.total_ms += gaps.sum::<u64>();
+            entry.count += 1;
+            entry.errors += usize::from(!last.1);
+        }
+    }
+
+    let mut scored: Vec<([char; N], f64)> = samples
+        .into_iter()
+        .filter(|(_, samples)| samples.count >= MIN_SAMPLES)
+        .map(|(letters, samples)| {
+            let count = samples.count as f64;
+            let score =
+                (samples.errors as f64).mul_add(ERROR_PENALTY_MS, samples.total_ms as f64) / count;
+
+            (letters, score)
+        })
+        .collect();
+
+    scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    scored.truncate(count);
+    scored.into_iter().map(|(letters, _)| letters).collect()
+}
+
+/// how many of the runs of letters a word contains
+#[must_use]
+pub fn hits<const N: usize>(word: &str, runs: &[[char; N]]) -> usize {
+    let chars: Vec<char> = word.chars().collect();
+
+    chars
+        .windows(N)
+        .filter(|window| runs.iter().any(|run| *window == run))
+        .count()
+}
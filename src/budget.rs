@@ -0,0 +1,105 @@
+use crate::{clock::Clock, game::Game};
+use ratatui::crossterm::event::KeyCode;
+
+/// keys typed before the accuracy floor is checked, so the first slip doesn't end the test
+const ACCURACY_AFTER: usize = 20;
+
+/// the mistakes a test can take before it ends as failed, 0 leaves a part out
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ErrorBudget {
+    /// wrong keys allowed, fixed ones count too
+    pub mistakes: usize,
+    /// the lowest accuracy in percent
+    pub accuracy: f64,
+}
+
+impl ErrorBudget {
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.mistakes > 0 || self.accuracy > 0.0
+    }
+
+    /// share of the budget left, 1 with no mistakes and 0 with no more allowed
+    #[allow(clippy::cast_precision_loss)]
+    pub fn left<C: Clock + Default>(&self, game: &Game<KeyCode, C>) -> f64 {
+        let mut left = 1.0_f64;
+
+        if self.mistakes > 0 {
+            left = left.min(1.0 - wrong(game) as f64 / self.mistakes as f64);
+        }
+
+        if self.accuracy > 0.0 && game.strokes.len() >= ACCURACY_AFTER {
+            left =
+                left.min(game.accuracy().mul_add(100.0, -self.accuracy) / (100.0 - self.accuracy));
+        }
+
+        left.clamp(0.0, 1.0)
+    }
+
+    /// more mistakes than allowed or the accuracy under the floor
+    pub fn is_spent<C: Clock + Default>(&self, game: &Game<KeyCode, C>) -> bool {
+        (self.mistakes > 0 && wrong(game) > self.mistakes)
+            || (self.accuracy > 0.0
+                && game.strokes.len() >= ACCURACY_AFTER
+                && game.accuracy() * 100.0 < self.accuracy)
+    }
+}
+
+fn wrong<C: Clock + Default>(game: &Game<KeyCode, C>) -> usize {
+    game.strokes.iter().filter(|stroke| !stroke.1).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use ratatui::crossterm::event::{Event, KeyEvent};
+
+    /// whether the budget is spent after each key of the text
+    fn spent(budget: ErrorBudget, target: &str, text: &str) -> Vec<bool> {
+        let mut game: Game<KeyCode, ManualClock> = Game::from_target(target.to_string());
+
+        text.chars()
+            .map(|c| {
+                game.crossterm_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))), None);
+                budget.is_spent(&game)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_mistake_past_the_budget_ends_the_test() {
+        let budget = ErrorBudget {
+            mistakes: 2,
+            accuracy: 0.0,
+        };
+
+        assert_eq!(
+            spent(budget, "mi moku", "xi xoxu"),
+            [false, false, false, false, false, true, true]
+        );
+        assert!(!spent(ErrorBudget::default(), "mi", "xx").contains(&true));
+    }
+
+    #[test]
+    fn the_accuracy_floor_waits_for_enough_keys() {
+        let budget = ErrorBudget {
+            mistakes: 0,
+            accuracy: 90.0,
+        };
+        let target = "a".repeat(40);
+
+        // the first key alone is 0%, but 1 wrong in 20 keys is 95%
+        let one = spent(budget, &target, &format!("x{}", "a".repeat(39)));
+        assert!(!one.contains(&true));
+
+        // 3 wrong is 85% at 20 keys and back to 90% at 30
+        let three = spent(budget, &target, &format!("xxx{}", "a".repeat(37)));
+        assert_eq!(
+            three.iter().position(|spent| *spent),
+            Some(ACCURACY_AFTER - 1)
+        );
+        assert!(!three[29]);
+    }
+}
@@ -0,0 +1,372 @@
+use crate::{output::Format, vocabulary::WordFilter};
+use std::path::PathBuf;
+use tt::{
+    error::{Error, Result},
+    lesson::Lesson,
+    TestCode,
+};
+
+pub const USAGE: &str =
+    "usage: tt [--profile NAME] [--set KEY=VALUE]... [--len WORDS] [--time SECONDS] [--emit-events FILE] [--live-stats FILE] [--serve-events PORT] [--code CODE]
+       tt [--profile NAME] [--set KEY=VALUE]... [--len WORDS] [--time SECONDS] [--emit-events FILE] [--live-stats FILE] [--serve-events PORT] lesson FILE
+       tt [--profile NAME] simulate --input-file FILE [--format FORMAT]
+       tt report HISTORY_FILE... [--format FORMAT]
+       tt [--profile NAME] report --week [HISTORY_FILE] [--html | --format FORMAT]
+       tt [--profile NAME] import HISTORY_FILE...
+       tt [--profile NAME] keylog [HISTORY_FILE]... [--format FORMAT]
+       tt [--profile NAME] stats [--compact | --format FORMAT]
+       tt words [--category CATEGORY] [--deprecated true|false] [--search TEXT] [--format FORMAT]
+       tt completions bash|zsh|fish
+       tt --version [--verbose]
+
+--set takes a dotted config key and a toml value, like --set game.len=30
+--len and --time are short for --set game.len and --set game.time_secs, any whole number works
+--live-stats keeps the speed, accuracy and words of the test in a file for stream overlays,
+json for a .json file and text for anything else, a named pipe gets a line each change
+--serve-events sends the game events as json to websocket clients on a local port,
+when tt is built with the websocket feature, browser pages only from localhost
+--code starts the test a code from the results screen was made from
+words.toml next to config.toml adds words to the dictionary, a table for each with a definition
+hooks.after_save in config.toml is a shell command run with the data directory after each save,
+hooks.after_test one run after each finished test with its results as json on stdin and TT_WPM,
+TT_ACCURACY, TT_SECONDS, TT_WORDS, TT_MODE, TT_DATE and TT_PASSED
+generator in config.toml is a shell command that writes the words of each test, it is given
+len, seed and the game settings as json on stdin
+--verbose adds the date of the linku word data, the words in each category and the licenses
+--format is text, json, toml or tsv
+--week reports the last seven days of one history, the profile's own without a file
+stats reads the summary saved with the history, --compact puts it on one line for status bars
+import adds the sessions of history files from other machines to the profile's, once each
+keylog prints every key of the histories, the profile's own without a file, as tsv or json rows of
+session, an id that hides the date, index, ms since the first key, gap_ms since the key before,
+key, correct, word, the index of the target word, target, that word, and typed, what it became
+
+TT_ environment variables set game settings under --set, like TT_LEN=30 or TT_PASS__WPM=20,
+TT_LAYOUT sets the keyboard layout, TT_MONOCHROME turns colours off,
+TT_ACCESSIBLE plays line by line for screen readers, TT_REDUCED_MOTION only draws changes,
+TT_CHECK_GRAMMAR marks grammar mistakes in free writing,
+TT_LOCALE=toki_pona puts the menus and messages in toki pona,
+TT_GLOSSES shows english under the words and TT_DATA_DIR and TT_CONFIG_DIR move the files
+NO_COLOR turns colours off too";
+
+/// the command line arguments
+pub struct Cli {
+    /// keep settings and history apart from the default ones
+    pub profile: Option<String>,
+    /// config from `--set`, over every config file
+    pub overrides: toml::Table,
+    pub command: Command,
+}
+
+/// what to do
+pub enum Command {
+    /// the normal terminal game
+    Play {
+        /// where to write game events as json lines
+        emit_events: Option<PathBuf>,
+        /// where to keep the stats of the test being typed
+        live_stats: Option<PathBuf>,
+        /// the local port to send game events to websocket clients on
+        serve_events: Option<u16>,
+        start: Start,
+    },
+    /// run a recorded key stream through the game and print the results
+    Simulate { input_file: PathBuf, format: Format },
+    /// print one report over the history files of several students
+    Report { files: Vec<PathBuf>, format: Format },
+    /// print a summary of the last week of one history, the profile's when there is no file
+    Week {
+        file: Option<PathBuf>,
+        format: Format,
+        html: bool,
+    },
+    /// add the sessions of other history files to the profile's
+    Import { files: Vec<PathBuf> },
+    /// print every stroke of the history files, the profile's when there are none
+    Keylog { files: Vec<PathBuf>, format: Format },
+    /// print the streak and today's tests from the summary next to the history
+    Stats { compact: bool, format: Format },
+    /// print the vocabulary, for piping into other tools
+    Words { filter: WordFilter, format: Format },
+    /// print a shell completion script
+    Completions { shell: String },
+    /// print the version, with the word data and licenses when verbose
+    Version { verbose: bool },
+}
+
+/// what the game opens on
+pub enum Start {
+    Menu,
+    /// straight into the test this code was made from
+    Code(TestCode),
+    /// straight into a lesson from a file
+    Lesson(Lesson),
+}
+
+impl Cli {
+    /// parse the arguments after the program name
+    #[allow(clippy::too_many_lines)]
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut profile = None;
+        let mut overrides = env_overrides(std::env::vars())?;
+        let mut subcommand = None;
+        let mut emit_events = None;
+        let mut live_stats = None;
+        let mut serve_events = None;
+        let mut input_file = None;
+        let mut code = None;
+        let mut files = Vec::new();
+        let mut lesson = None;
+        let mut shell = None;
+        let mut filter = WordFilter::default();
+        let mut format = None;
+        let mut week = false;
+        let mut html = false;
+        let mut version = false;
+        let mut verbose = false;
+        let mut compact = false;
+
+        while let Some(arg) = args.next() {
+            let mut value = |what: &str| {
+                args.next()
+                    .ok_or_else(|| Error::Usage(format!("{arg} needs {what}")))
+            };
+
+            match arg.as_str() {
+                "--profile" | "-p" => profile = Some(profile_name(value("a name")?)?),
+                "--set" | "-s" => set(&mut overrides, &value("KEY=VALUE")?)?,
+                "--len" => set(
+                    &mut overrides,
+                    &format!("game.len={}", whole_number(&arg, &value("a word count")?)?),
+                )?,
+                "--time" => set(
+                    &mut overrides,
+                    &format!("game.time_secs={}", whole_number(&arg, &value("seconds")?)?),
+                )?,
+                "--emit-events" => emit_events = Some(PathBuf::from(value("a file")?)),
+                "--live-stats" => live_stats = Some(PathBuf::from(value("a file")?)),
+                "--serve-events" => {
+                    serve_events = Some(value("a port")?.parse().map_err(|_| {
+                        Error::Usage("--serve-events needs a port number".to_string())
+                    })?);
+                }
+                "--input-file" | "-i" => input_file = Some(PathBuf::from(value("a file")?)),
+                "--code" | "-c" => code = Some(value("a test code")?.parse()?),
+                "--category" => filter.category = Some(value("a category")?),
+                "--deprecated" => {
+                    filter.deprecated = Some(value("true or false")?.parse().map_err(|_| {
+                        Error::Usage("--deprecated needs true or false".to_string())
+                    })?);
+                }
+                "--search" => filter.search = Some(value("some text")?),
+                "--format" => format = Some(value("a format")?.parse()?),
+                "--week" => week = true,
+                "--html" => html = true,
+                "--version" | "-V" => version = true,
+                "--verbose" | "-v" => verbose = true,
+                "--compact" => compact = true,
+                "simulate" if subcommand.is_none() => subcommand = Some("simulate"),
+                "report" if subcommand.is_none() => subcommand = Some("report"),
+                "lesson" if subcommand.is_none() => {
+                    lesson = Some(PathBuf::from(value("a lesson file")?));
+                    subcommand = Some("lesson");
+                }
+                "import" if subcommand.is_none() => subcommand = Some("import"),
+                "keylog" if subcommand.is_none() => subcommand = Some("keylog"),
+                "stats" if subcommand.is_none() => subcommand = Some("stats"),
+                "words" if subcommand.is_none() => subcommand = Some("words"),
+                "completions" if subcommand.is_none() => {
+                    shell = Some(value("a shell")?);
+                    subcommand = Some("completions");
+                }
+                _ if matches!(subcommand, Some("report" | "import" | "keylog"))
+                    && !arg.starts_with('-') =>
+                {
+                    files.push(PathBuf::from(&arg));
+                }
+                _ => return Err(Error::Usage(format!("unknown argument {arg:?}"))),
+            }
+        }
+
+        let filtered =
+            filter.category.is_some() || filter.deprecated.is_some() || filter.search.is_some();
+
+        let command = match subcommand {
+            None if version => Command::Version { verbose },
+            _ if version => {
+                return Err(Error::Usage(
+                    "--version can't be used with a subcommand".to_string(),
+                ))
+            }
+            _ if verbose => {
+                return Err(Error::Usage("--verbose is only for --version".to_string()))
+            }
+            Some("simulate") => Command::Simulate {
+                input_file: input_file
+                    .ok_or_else(|| Error::Usage("simulate needs --input-file".to_string()))?,
+                format: format.unwrap_or_default(),
+            },
+            _ if week && subcommand != Some("report") => {
+                return Err(Error::Usage("--week is only for report".to_string()))
+            }
+            _ if html && !week => {
+                return Err(Error::Usage("--html is only for report --week".to_string()))
+            }
+            Some("report") if week && html && format.is_some() => {
+                return Err(Error::Usage(
+                    "--html can't be used with --format".to_string(),
+                ))
+            }
+            Some("report") if week && files.len() > 1 => {
+                return Err(Error::Usage(
+                    "report --week reads one history file".to_string(),
+                ))
+            }
+            Some("report") if week => Command::Week {
+                file: files.pop(),
+                format: format.unwrap_or_default(),
+                html,
+            },
+            Some("report") if files.is_empty() => {
+                return Err(Error::Usage("report needs history files".to_string()))
+            }
+            Some("report") => Command::Report {
+                files,
+                format: format.unwrap_or_default(),
+            },
+            Some("import") if files.is_empty() => {
+                return Err(Error::Usage("import needs history files".to_string()))
+            }
+            Some("keylog") => Command::Keylog {
+                files,
+                format: format.unwrap_or_default(),
+            },
+            Some("words") => Command::Words {
+                filter,
+                format: format.unwrap_or_default(),
+            },
+            _ if filtered => {
+                return Err(Error::Usage(
+                    "--category, --deprecated and --search are only for words".to_string(),
+                ))
+            }
+            Some("stats") if compact && format.is_some() => {
+                return Err(Error::Usage(
+                    "--compact can't be used with --format".to_string(),
+                ))
+            }
+            Some("stats") => Command::Stats {
+                compact,
+                format: format.unwrap_or_default(),
+            },
+            _ if format.is_some() => {
+                return Err(Error::Usage(
+                    "--format is only for simulate, report, keylog, stats and words".to_string(),
+                ))
+            }
+            Some("import") => Command::Import { files },
+            _ if compact => return Err(Error::Usage("--compact is only for stats".to_string())),
+            Some("completions") => Command::Completions {
+                shell: shell.unwrap_or_default(),
+            },
+            _ if input_file.is_some() => {
+                return Err(Error::Usage(
+                    "--input-file is only for simulate".to_string(),
+                ))
+            }
+            _ => Command::Play {
+                emit_events,
+                live_stats,
+                serve_events,
+                start: match (code, lesson) {
+                    (Some(_), Some(_)) => {
+                        return Err(Error::Usage(
+                            "--code can't be used with a lesson".to_string(),
+                        ))
+                    }
+                    (Some(code), None) => Start::Code(code),
+                    (None, Some(path)) => Start::Lesson(Lesson::load(&path)?),
+                    (None, None) => Start::Menu,
+                },
+            },
+        };
+
+        Ok(Self {
+            profile,
+            overrides,
+            command,
+        })
+    }
+}
+
+/// config from `TT_` variables, `TT_LEN=30` is game.len and a double underscore is a dot
+fn env_overrides(vars: impl Iterator<Item = (String, String)>) -> Result<toml::Table> {
+    let mut table = toml::Table::new();
+
+    for (name, value) in vars {
+        let Some(key) = name.strip_prefix("TT_") else {
+            continue;
+        };
+
+        let key = key.to_lowercase().replace("__", ".");
+
+        match key.as_str() {
+            // directories, read where the paths are made
+            "data_dir" | "config_dir" => (),
+            "layout" | "monochrome" | "accessible" | "reduced_motion" | "check_grammar"
+            | "glosses" | "locale" => set(&mut table, &format!("{key}={value}"))?,
+            _ => set(&mut table, &format!("game.{key}={value}"))?,
+        }
+    }
+
+    Ok(table)
+}
+
+/// the value of a flag that takes a count, above 0
+fn whole_number(arg: &str, value: &str) -> Result<u64> {
+    value
+        .parse()
+        .ok()
+        .filter(|number| *number > 0)
+        .ok_or_else(|| Error::Usage(format!("{arg} needs a whole number above 0")))
+}
+
+/// add a "game.len=30" style setting to a table, values that aren't toml are taken as strings
+fn set(table: &mut toml::Table, setting: &str) -> Result<()> {
+    let Some((key, value)) = setting.split_once('=') else {
+        return Err(Error::Usage(format!("{setting:?} is not KEY=VALUE")));
+    };
+
+    let value = format!("value = {value}")
+        .parse::<toml::Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+    let mut keys = key.split('.').rev();
+    let last = keys.next().unwrap_or_default().to_string();
+
+    // build the nesting from the inside out
+    let layer = keys.fold(toml::Table::from_iter([(last, value)]), |inner, key| {
+        toml::Table::from_iter([(key.to_string(), toml::Value::Table(inner))])
+    });
+
+    crate::config::merge(table, layer);
+
+    Ok(())
+}
+
+/// profile names become directory names, so keep them to simple characters
+fn profile_name(name: String) -> Result<String> {
+    if !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        Ok(name)
+    } else {
+        Err(Error::Usage(format!(
+            "profile names can only have letters, numbers, - and _, not {name:?}"
+        )))
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+/// where a game gets the current time from
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// the wall clock
+#[derive(Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// a clock that only moves when told to, for replays, simulations and tests
+#[derive(Clone)]
+pub struct ManualClock {
+    start: Instant,
+    now: Cell<Instant>,
+}
+
+impl ManualClock {
+    /// the time since the clock was made
+    pub fn elapsed(&self) -> Duration {
+        self.now.get().saturating_duration_since(self.start)
+    }
+
+    /// move to a time since the clock was made, it can go backwards
+    pub fn set(&self, since_start: Duration) {
+        self.now.set(self.start + since_start);
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        let start = Instant::now();
+
+        Self {
+            start,
+            now: Cell::new(start),
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
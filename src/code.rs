@@ -0,0 +1,284 @@
+use crate::{
+    error::{Error, Result},
+    numbers::NumberSystem,
+    settings::{GameSettings, Pin},
+    words::ADDED_WORDS,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{fmt, str::FromStr};
+
+/// crockford's base32, no I, L, O or U so codes are easy to read out loud
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// characters in a code, enough for the 56 packed bits
+const CHARS: usize = 12;
+const MAX_LEN: usize = (1 << 12) - 1;
+
+/// the random numbers behind every test with this seed
+#[must_use]
+pub fn rng(seed: u32) -> StdRng {
+    StdRng::seed_from_u64(seed.into())
+}
+
+/// a short code that gives everyone who types it the same test,
+/// made of the seed and the settings that change what gets picked
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TestCode {
+    pub seed: u32,
+    pub len: usize,
+    pub proper_names: usize,
+    pub punctuation: bool,
+    pub strict_space: bool,
+    /// a number drill instead of words
+    pub numbers: bool,
+    pub number_system: NumberSystem,
+}
+
+impl TestCode {
+    /// the code for a test made from these settings, None if the settings
+    /// change the word weights or how they are picked, or don't fit in a code
+    pub fn new(seed: u32, settings: &GameSettings<usize>, numbers: bool) -> Option<Self> {
+        let defaults = GameSettings::default();
+
+        let default_weights = settings.categories == defaults.categories
+            && settings.unknown_category == defaults.unknown_category
+            && settings.weighting == defaults.weighting
+            && settings.deprecated == defaults.deprecated
+            && settings.nondeprecated == defaults.nondeprecated
+            && settings.words.is_empty()
+            && settings.pins.values().all(|pin| *pin == Pin::Normal);
+
+        // these aren't in the code, so the test would come out different for anyone else
+        let default_picking = settings.repeat_words == defaults.repeat_words
+            && settings.noise <= 0.0
+            && settings.time_secs == defaults.time_secs
+            && ADDED_WORDS.is_empty();
+
+        (default_weights
+            && default_picking
+            && settings.len <= MAX_LEN
+            && settings.proper_names <= 0xff)
+            .then_some(Self {
+                seed,
+                len: settings.len,
+                proper_names: settings.proper_names,
+                punctuation: settings.punctuation,
+                strict_space: settings.strict_space,
+                numbers,
+                number_system: settings.number_system,
+            })
+    }
+
+    /// these settings with everything that picks the words replaced by the code's,
+    /// the user's own words are pinned as never since the code was made without them
+    pub fn settings(&self, settings: &GameSettings<usize>) -> GameSettings<usize> {
+        let defaults = GameSettings::default();
+
+        GameSettings {
+            categories: defaults.categories,
+            unknown_category: defaults.unknown_category,
+            weighting: defaults.weighting,
+            deprecated: defaults.deprecated,
+            nondeprecated: defaults.nondeprecated,
+            words: defaults.words,
+            pins: ADDED_WORDS
+                .iter()
+                .map(|word| ((*word).to_string(), Pin::Never))
+                .collect(),
+            repeat_words: defaults.repeat_words,
+            noise: defaults.noise,
+            time_secs: defaults.time_secs,
+            len: self.len,
+            proper_names: self.proper_names,
+            punctuation: self.punctuation,
+            strict_space: self.strict_space,
+            number_system: self.number_system,
+            ..settings.clone()
+        }
+    }
+
+    /// the random numbers the test is made from
+    #[must_use]
+    pub fn rng(&self) -> StdRng {
+        rng(self.seed)
+    }
+
+    fn pack(&self) -> u64 {
+        u64::from(self.seed)
+            | (self.len as u64) << 32
+            | (self.proper_names as u64) << 44
+            | u64::from(self.punctuation) << 52
+            | u64::from(self.strict_space) << 53
+            | u64::from(self.numbers) << 54
+            | u64::from(self.number_system == NumberSystem::Pona) << 55
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    const fn unpack(bits: u64) -> Self {
+        Self {
+            seed: bits as u32,
+            len: (bits >> 32) as usize & MAX_LEN,
+            proper_names: (bits >> 44) as usize & 0xff,
+            punctuation: bits >> 52 & 1 == 1,
+            strict_space: bits >> 53 & 1 == 1,
+            numbers: bits >> 54 & 1 == 1,
+            number_system: if bits >> 55 & 1 == 1 {
+                NumberSystem::Pona
+            } else {
+                NumberSystem::Simple
+            },
+        }
+    }
+}
+
+/// groups of four split by dashes, like 0123-4567-89AB
+impl fmt::Display for TestCode {
+    #[allow(clippy::cast_possible_truncation)]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bits = self.pack();
+
+        for i in (0..CHARS).rev() {
+            let digit = (bits >> (i * 5)) & 0x1f;
+            write!(f, "{}", char::from(ALPHABET[digit as usize]))?;
+
+            if i > 0 && i % 4 == 0 {
+                write!(f, "-")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// dashes and case are ignored, and the letters that look like digits are read as them
+impl FromStr for TestCode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidCode(s.to_string());
+
+        let digits = s
+            .chars()
+            .filter(|c| *c != '-')
+            .map(|c| match c.to_ascii_uppercase() {
+                'O' => Some(0),
+                'I' | 'L' => Some(1),
+                c => ALPHABET.iter().position(|a| char::from(*a) == c),
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        if digits.len() != CHARS {
+            return Err(invalid());
+        }
+
+        let bits = digits
+            .into_iter()
+            .fold(0u64, |bits, digit| bits << 5 | digit as u64);
+
+        // only the lowest bit of the first character is used
+        if bits >> 56 != 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self::unpack(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code() -> TestCode {
+        TestCode {
+            seed: 0xdead_beef,
+            len: MAX_LEN,
+            proper_names: 0xff,
+            punctuation: true,
+            strict_space: false,
+            numbers: true,
+            number_system: NumberSystem::Pona,
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        let code = code();
+        assert_eq!(code.to_string().parse::<TestCode>().unwrap(), code);
+
+        let code = TestCode {
+            punctuation: false,
+            strict_space: true,
+            numbers: false,
+            number_system: NumberSystem::Simple,
+            ..code
+        };
+        assert_eq!(code.to_string().parse::<TestCode>().unwrap(), code);
+    }
+
+    #[test]
+    fn reads_aliases_case_and_dashes() {
+        let code = code();
+        let text = code.to_string();
+        assert!(text.contains('0') || text.contains('1'));
+
+        let aliased = text.replace('0', "o").replace('1', "l").replace('-', "");
+        assert_eq!(aliased.parse::<TestCode>().unwrap(), code);
+
+        let aliased = text.replace('0', "O").replace('1', "i").to_lowercase();
+        assert_eq!(aliased.parse::<TestCode>().unwrap(), code);
+    }
+
+    #[test]
+    fn rejects_the_top_bits() {
+        // the first character only has room for one bit
+        assert!("2000-0000-0000".parse::<TestCode>().is_err());
+        assert!("Z000-0000-0000".parse::<TestCode>().is_err());
+        assert!("1000-0000-0000".parse::<TestCode>().is_ok());
+    }
+
+    #[test]
+    fn rejects_other_lengths_and_letters() {
+        assert!("0000-0000-000".parse::<TestCode>().is_err());
+        assert!("0000-0000-0000-0".parse::<TestCode>().is_err());
+        assert!("0000-0000-000U".parse::<TestCode>().is_err());
+    }
+
+    #[test]
+    fn settings_outside_the_code_have_none() {
+        let settings = GameSettings::default();
+        assert!(TestCode::new(1, &settings, false).is_some());
+
+        for settings in [
+            GameSettings {
+                repeat_words: true,
+                ..GameSettings::default()
+            },
+            GameSettings {
+                noise: 0.1,
+                ..GameSettings::default()
+            },
+            GameSettings {
+                time_secs: 30,
+                ..GameSettings::default()
+            },
+        ] {
+            assert!(TestCode::new(1, &settings, false).is_none());
+        }
+    }
+
+    #[test]
+    fn settings_reset_what_the_code_leaves_out() {
+        let settings = GameSettings {
+            repeat_words: true,
+            noise: 0.5,
+            time_secs: 60,
+            ..GameSettings::default()
+        };
+        let settings = code().settings(&settings);
+
+        assert!(!settings.repeat_words);
+        assert_eq!(settings.noise.total_cmp(&0.0), std::cmp::Ordering::Equal);
+        assert_eq!(settings.time_secs, 0);
+        assert_eq!(settings.len, MAX_LEN);
+    }
+}
@@ -0,0 +1,181 @@
+use tt::error::{Error, Result};
+
+const BASH: &str = r#"_tt() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+
+    case "$prev" in
+        --profile|-p)
+            COMPREPLY=($(compgen -W "$(tt completions profiles 2>/dev/null)" -- "$cur"))
+            return ;;
+        completions)
+            COMPREPLY=($(compgen -W "bash zsh fish" -- "$cur"))
+            return ;;
+        --emit-events|--live-stats|--input-file|-i|lesson)
+            COMPREPLY=($(compgen -f -- "$cur"))
+            return ;;
+        --category)
+            COMPREPLY=($(compgen -W "core common uncommon obscure sandbox" -- "$cur"))
+            return ;;
+        --deprecated)
+            COMPREPLY=($(compgen -W "true false" -- "$cur"))
+            return ;;
+        --format)
+            COMPREPLY=($(compgen -W "text json toml tsv" -- "$cur"))
+            return ;;
+        --set|-s|--code|-c|--search|--len|--time|--serve-events)
+            return ;;
+    esac
+
+    if [[ " ${COMP_WORDS[*]} " == *" report "* ]]; then
+        COMPREPLY=($(compgen -f -W "--format --week --html" -- "$cur"))
+        return
+    fi
+
+    if [[ " ${COMP_WORDS[*]} " == *" import "* ]]; then
+        COMPREPLY=($(compgen -f -- "$cur"))
+        return
+    fi
+
+    if [[ " ${COMP_WORDS[*]} " == *" keylog "* ]]; then
+        COMPREPLY=($(compgen -f -W "--format" -- "$cur"))
+        return
+    fi
+
+    if [[ " ${COMP_WORDS[*]} " == *" stats "* ]]; then
+        COMPREPLY=($(compgen -W "--compact --format" -- "$cur"))
+        return
+    fi
+
+    if [[ " ${COMP_WORDS[*]} " == *" words "* ]]; then
+        COMPREPLY=($(compgen -W "--category --deprecated --search --format" -- "$cur"))
+        return
+    fi
+
+    COMPREPLY=($(compgen -W "--profile --set --len --time --emit-events --live-stats --serve-events --code --input-file --version --verbose simulate report import keylog stats lesson words completions" -- "$cur"))
+}
+
+complete -F _tt tt
+"#;
+
+const ZSH: &str = r#"#compdef tt
+
+_tt_profiles() {
+    local -a profiles
+    profiles=(${(f)"$(tt completions profiles 2>/dev/null)"})
+    _describe 'profile' profiles
+}
+
+local -a commands
+commands=(
+    'simulate:run a key recording through the game'
+    'report:one report over student history files'
+    'import:add the sessions of history files from other machines'
+    'keylog:every key of history files with its timing'
+    'stats:the streak and the best of today'
+    'lesson:play a lesson file'
+    'words:print the vocabulary'
+    'completions:print a shell completion script'
+)
+
+_arguments -C \
+    '(-p --profile)'{-p,--profile}'[keep settings and history apart]:profile:_tt_profiles' \
+    '*'{-s,--set}'[set a config key]:KEY=VALUE:' \
+    '--len[words in each test]:words:' \
+    '--time[seconds each test lasts]:seconds:' \
+    '--emit-events[write game events as json lines]:file:_files' \
+    '--live-stats[keep the stats of the test in a file for overlays]:file:_files' \
+    '--serve-events[send game events to websocket clients]:port:' \
+    '(-c --code)'{-c,--code}'[start the test a code was made from]:code:' \
+    '(-i --input-file)'{-i,--input-file}'[key recording for simulate]:file:_files' \
+    '--format[output of simulate, report, keylog, stats and words]:format:(text json toml tsv)' \
+    '--week[report the last seven days of one history]' \
+    '--html[the weekly report as a web page]' \
+    '(-V --version)'{-V,--version}'[print the version]' \
+    '(-v --verbose)'{-v,--verbose}'[with --version, the word data and licenses too]' \
+    '1: :->command' \
+    '*:: :->args'
+
+case $state in
+    command) _describe 'command' commands ;;
+    args)
+        case $words[1] in
+            completions) _values 'shell' bash zsh fish ;;
+            stats) _arguments '--compact[on one line for a status bar]' ;;
+            words)
+                _arguments \
+                    '--category[linku usage category]:category:(core common uncommon obscure sandbox)' \
+                    '--deprecated[only deprecated words or only others]:deprecated:(true false)' \
+                    '--search[text in the word or definition]:text:' ;;
+            lesson|report|import|keylog) _files ;;
+        esac ;;
+esac
+"#;
+
+const FISH: &str = "complete -c tt -f
+complete -c tt -s p -l profile -x -a '(tt completions profiles 2>/dev/null)' -d 'keep settings and history apart'
+complete -c tt -s s -l set -x -d 'set a config key, KEY=VALUE'
+complete -c tt -l len -x -d 'words in each test'
+complete -c tt -l time -x -d 'seconds each test lasts'
+complete -c tt -l emit-events -r -F -d 'write game events as json lines'
+complete -c tt -l live-stats -r -F -d 'keep the stats of the test in a file for overlays'
+complete -c tt -l serve-events -x -d 'send game events to websocket clients'
+complete -c tt -s c -l code -x -d 'start the test a code was made from'
+complete -c tt -s i -l input-file -r -F -d 'key recording for simulate'
+complete -c tt -l format -x -a 'text json toml tsv' -d 'output of simulate, report, keylog, stats and words'
+complete -c tt -s V -l version -d 'print the version'
+complete -c tt -s v -l verbose -d 'with --version, the word data and licenses too'
+complete -c tt -n __fish_use_subcommand -a simulate -d 'run a key recording through the game'
+complete -c tt -n __fish_use_subcommand -a report -d 'one report over student history files'
+complete -c tt -n '__fish_seen_subcommand_from report' -l week -d 'report the last seven days of one history'
+complete -c tt -n '__fish_seen_subcommand_from report' -l html -d 'the weekly report as a web page'
+complete -c tt -n __fish_use_subcommand -a import -d 'add the sessions of history files from other machines'
+complete -c tt -n __fish_use_subcommand -a keylog -d 'every key of history files with its timing'
+complete -c tt -n __fish_use_subcommand -a stats -d 'the streak and the best of today'
+complete -c tt -n '__fish_seen_subcommand_from stats' -l compact -d 'on one line for a status bar'
+complete -c tt -n __fish_use_subcommand -a lesson -d 'play a lesson file'
+complete -c tt -n __fish_use_subcommand -a words -d 'print the vocabulary'
+complete -c tt -n '__fish_seen_subcommand_from words' -l category -x -a 'core common uncommon obscure sandbox' -d 'linku usage category'
+complete -c tt -n '__fish_seen_subcommand_from words' -l deprecated -x -a 'true false' -d 'only deprecated words or only others'
+complete -c tt -n '__fish_seen_subcommand_from words' -l search -x -d 'text in the word or definition'
+complete -c tt -n __fish_use_subcommand -a completions -d 'print a shell completion script'
+complete -c tt -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'
+complete -c tt -n '__fish_seen_subcommand_from lesson report import keylog' -F
+";
+
+/// print the completion script for a shell, or the profile names the scripts complete
+pub fn run(shell: &str) -> Result<()> {
+    match shell {
+        "bash" => print!("{BASH}"),
+        "zsh" => print!("{ZSH}"),
+        "fish" => print!("{FISH}"),
+        "profiles" => {
+            for profile in profiles() {
+                println!("{profile}");
+            }
+        }
+        _ => {
+            return Err(Error::Usage(format!(
+                "no completions for {shell:?}, use bash, zsh or fish"
+            )))
+        }
+    }
+
+    Ok(())
+}
+
+/// the names of the profiles that have been used
+fn profiles() -> Vec<String> {
+    let Some(Ok(entries)) = crate::data_path(None, "profiles").map(std::fs::read_dir) else {
+        return Vec::new();
+    };
+
+    let mut profiles: Vec<String> = entries
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    profiles.sort_unstable();
+
+    profiles
+}
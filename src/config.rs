@@ -0,0 +1,273 @@
+use crate::{hooks::Hooks, sounds::SoundConfig};
+use std::{path::PathBuf, time::SystemTime};
+use tt::{
+    error::{Error, Result},
+    history::Privacy,
+    keymap::KeyConfig,
+    layout::KeyboardLayout,
+    locale::Locale,
+    migrate, storage, GameSettings,
+};
+
+/// everything read from config.toml
+///
+/// settings come in layers, each one only needs the keys it changes and later layers win:
+/// the built in defaults, the global config.toml, the profile's config.toml, `TT_` environment
+/// variables, then `--set`
+// the bools are separate switches in the config file
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default, serde::Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub game: GameSettings<usize>,
+    pub keys: KeyConfig,
+    pub layout: KeyboardLayout,
+    /// draw without colours, also on when `NO_COLOR` is set
+    pub monochrome: bool,
+    /// draw only when something changed, for motion sensitivity and slow connections
+    pub reduced_motion: bool,
+    /// an english gloss under each word of the test
+    pub glosses: bool,
+    /// mark ungrammatical toki pona in free writing
+    pub check_grammar: bool,
+    /// plain lines for screen readers instead of the full screen game
+    pub accessible: bool,
+    /// the language of menus, titles and messages, english or `toki_pona`
+    pub locale: Locale,
+    pub privacy: Privacy,
+    pub hooks: Hooks,
+    /// a command that writes the words of each test in place of picking them,
+    /// see generator.rs for what it is given
+    pub generator: String,
+    pub sound: SoundConfig,
+}
+
+impl Config {
+    /// merge config files from lowest to highest precedence with the command line on top,
+    /// a key the config can't take is left out and its error collected, the rest of its layer
+    /// is kept
+    ///
+    /// # Errors
+    /// when a layer can't be read at all or a `[privacy]` key is bad, leaving either out
+    /// could keep more of the history than was asked for
+    pub fn load_layered(
+        paths: &[PathBuf],
+        overrides: toml::Table,
+        errors: &mut Vec<Error>,
+    ) -> Result<Self> {
+        let mut table = toml::Table::new();
+
+        for path in paths {
+            let mut layer = storage::load::<toml::Table>(path)?.unwrap_or_default();
+            migrate::config(path, &mut layer)?;
+            add_layer(&mut table, layer, &path.display().to_string(), errors)?;
+        }
+
+        add_layer(&mut table, overrides, "TT_ variables and --set", errors)?;
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|source| Error::Settings { source })
+    }
+}
+
+/// merge a layer into the settings so far, key by key when the whole layer doesn't fit
+fn add_layer(
+    table: &mut toml::Table,
+    layer: toml::Table,
+    name: &str,
+    errors: &mut Vec<Error>,
+) -> Result<()> {
+    let mut merged = table.clone();
+    merge(&mut merged, layer.clone());
+
+    if toml::Value::Table(merged.clone())
+        .try_into::<Config>()
+        .is_ok()
+    {
+        *table = merged;
+        return Ok(());
+    }
+
+    for (key, value) in leaves(layer) {
+        let mut merged = table.clone();
+        merge(&mut merged, nest(&key, value));
+
+        match toml::Value::Table(merged.clone()).try_into::<Config>() {
+            Ok(_) => *table = merged,
+            Err(source) => {
+                let error = Error::Setting {
+                    layer: name.to_string(),
+                    key: key.join("."),
+                    source: Box::new(source),
+                };
+
+                if key[0] == "privacy" {
+                    return Err(error);
+                }
+
+                errors.push(error);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// every value of a table that isn't a table itself, with the keys leading to it
+fn leaves(table: toml::Table) -> Vec<(Vec<String>, toml::Value)> {
+    table
+        .into_iter()
+        .flat_map(|(key, value)| match value {
+            toml::Value::Table(table) => leaves(table)
+                .into_iter()
+                .map(|(mut keys, value)| {
+                    keys.insert(0, key.clone());
+                    (keys, value)
+                })
+                .collect(),
+            value => vec![(vec![key], value)],
+        })
+        .collect()
+}
+
+/// a table with the value under the keys
+fn nest(keys: &[String], value: toml::Value) -> toml::Table {
+    let (last, keys) = keys.split_last().expect("a leaf has a key");
+    let mut table = toml::Table::from_iter([(last.clone(), value)]);
+
+    for key in keys.iter().rev() {
+        table = toml::Table::from_iter([(key.clone(), toml::Value::Table(table))]);
+    }
+
+    table
+}
+
+/// the config files and command line settings, reloaded when a file changes
+pub struct ConfigWatch {
+    paths: Vec<PathBuf>,
+    overrides: toml::Table,
+    /// when each file was last changed, None for missing files
+    modified: Vec<Option<SystemTime>>,
+}
+
+impl ConfigWatch {
+    pub fn new(paths: Vec<PathBuf>, overrides: toml::Table) -> Self {
+        Self {
+            modified: modified_times(&paths),
+            paths,
+            overrides,
+        }
+    }
+
+    /// # Errors
+    /// the errors of `Config::load_layered`
+    pub fn load(&self, errors: &mut Vec<Error>) -> Result<Config> {
+        Config::load_layered(&self.paths, self.overrides.clone(), errors)
+    }
+
+    /// the new config if a file changed since the last call,
+    /// a config with errors is not returned so a half written file doesn't reset everything
+    pub fn changed(&mut self, errors: &mut Vec<Error>) -> Option<Config> {
+        let modified = modified_times(&self.paths);
+
+        if modified == self.modified {
+            return None;
+        }
+
+        self.modified = modified;
+
+        let mut load_errors = Vec::new();
+
+        match self.load(&mut load_errors) {
+            Ok(config) if load_errors.is_empty() => Some(config),
+            Ok(_) => {
+                errors.extend(load_errors);
+                None
+            }
+            Err(error) => {
+                errors.extend(load_errors);
+                errors.push(error);
+                None
+            }
+        }
+    }
+}
+
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// put the keys of top into base, tables are merged key by key and anything else replaced
+pub fn merge(base: &mut toml::Table, top: toml::Table) {
+    for (key, value) in top {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(top)) => merge(base, top),
+            (_, value) => _ = base.insert(key, value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tt::history::Logging;
+
+    /// the config of layers written to files, named so tests don't share a directory
+    fn load(name: &str, files: &[&str], overrides: &str) -> (Result<Config>, Vec<Error>) {
+        let dir = std::env::temp_dir().join(format!("tt-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let paths: Vec<_> = files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| {
+                let path = dir.join(format!("{i}.toml"));
+                std::fs::write(&path, file).unwrap();
+                path
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        let config = Config::load_layered(&paths, toml::from_str(overrides).unwrap(), &mut errors);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        (config, errors)
+    }
+
+    #[test]
+    fn a_bad_key_leaves_the_rest_of_its_layer() {
+        let (config, errors) = load(
+            "bad-key",
+            &[
+                "[privacy]\nlogging = \"aggregates\"",
+                "monochrome = true\n[game]\nlen = \"many\"",
+            ],
+            "glosses = true",
+        );
+        let config = config.unwrap();
+
+        assert!(config.monochrome && config.glosses);
+        assert!(config.privacy.logging == Logging::Aggregates);
+        assert!(matches!(
+            &errors[..],
+            [Error::Setting { layer, key, .. }] if layer.ends_with("1.toml") && key == "game.len"
+        ));
+    }
+
+    #[test]
+    fn a_bad_privacy_key_or_file_is_an_error() {
+        let (config, _) = load("bad-privacy", &["[privacy]\nkeep_days = -1"], "");
+        assert!(matches!(config, Err(Error::Setting { key, .. }) if key == "privacy.keep_days"));
+
+        let (config, _) = load("bad-file", &["[privacy\nlogging = \"off\""], "");
+        assert!(matches!(config, Err(Error::Parse { .. })));
+    }
+}
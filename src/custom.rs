@@ -0,0 +1,81 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use tt::GameSettings;
+
+/// the length of the last custom test, kept per profile
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct CustomTest {
+    /// words, 0 takes the length from the settings
+    pub len: usize,
+    /// seconds, 0 for a test that ends with its words
+    pub time_secs: u64,
+}
+
+impl CustomTest {
+    /// "30" is 30 words and "45s" is 45 seconds
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+
+        if let Some(secs) = text.strip_suffix('s') {
+            return secs
+                .trim()
+                .parse()
+                .ok()
+                .filter(|secs| *secs > 0)
+                .map(|time_secs| Self { len: 0, time_secs });
+        }
+
+        text.parse()
+            .ok()
+            .filter(|len| *len > 0)
+            .map(|len| Self { len, time_secs: 0 })
+    }
+
+    /// as it is typed in
+    pub fn text(self) -> String {
+        if self.time_secs > 0 {
+            format!("{}s", self.time_secs)
+        } else if self.len > 0 {
+            self.len.to_string()
+        } else {
+            String::new()
+        }
+    }
+
+    /// the settings with this length in place of theirs
+    pub fn settings(self, settings: &GameSettings<usize>) -> GameSettings<usize> {
+        GameSettings {
+            len: if self.len > 0 { self.len } else { settings.len },
+            time_secs: self.time_secs,
+            ..settings.clone()
+        }
+    }
+}
+
+/// a word count or duration being typed for a custom test
+#[derive(Default)]
+pub struct CustomEntry {
+    pub text: String,
+}
+
+impl CustomEntry {
+    pub fn new(custom: CustomTest) -> Self {
+        Self {
+            text: custom.text(),
+        }
+    }
+
+    /// Some with the test to start on enter when the text is one, Some(None) to cancel
+    #[allow(clippy::option_option)]
+    pub fn key(&mut self, key_event: &KeyEvent) -> Option<Option<CustomTest>> {
+        match key_event.code {
+            KeyCode::Enter => return CustomTest::parse(&self.text).map(Some),
+            KeyCode::Esc => return Some(None),
+            KeyCode::Backspace => _ = self.text.pop(),
+            KeyCode::Char(c) if c.is_ascii_digit() || c == 's' => self.text.push(c),
+            _ => (),
+        }
+
+        None
+    }
+}
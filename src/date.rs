@@ -0,0 +1,43 @@
+use std::time::SystemTime;
+
+pub const SECS_PER_DAY: u64 = 60 * 60 * 24;
+
+/// current unix time in seconds
+#[must_use]
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// year, month and day of a day counted from 1970-01-01, using Howard Hinnant's `civil_from_days`
+#[must_use]
+pub const fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (year, month as u32, day as u32)
+}
+
+/// unix seconds as "YYYY-MM-DD HH:MM", in UTC
+#[must_use]
+pub fn format(secs: u64) -> String {
+    let (year, month, day) =
+        civil_from_days(i64::try_from(secs / SECS_PER_DAY).unwrap_or_default());
+    let secs = secs % SECS_PER_DAY;
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs / 3600,
+        secs % 3600 / 60
+    )
+}
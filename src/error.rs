@@ -0,0 +1,90 @@
+use std::path::PathBuf;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("failed to read {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to write {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse {}: {source}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+
+    #[error("failed to parse {}: {source}", path.display())]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("failed to serialize {}: {source}", path.display())]
+    Serialize {
+        path: PathBuf,
+        source: toml::ser::Error,
+    },
+
+    /// a file from a later release, in a format this one can't read
+    #[error("{} was saved by a newer version of tt (format {version}), update tt to read it", path.display())]
+    NewerVersion { path: PathBuf, version: i64 },
+
+    /// one key of a config layer, which is a file or the command line
+    #[error("invalid {key} in {layer}: {source}")]
+    Setting {
+        layer: String,
+        key: String,
+        source: Box<toml::de::Error>,
+    },
+
+    #[error("invalid settings: {source}")]
+    Settings { source: toml::de::Error },
+
+    #[error("word data has no {field} field")]
+    MissingField { field: &'static str },
+
+    #[error("no words matched the current settings")]
+    NoWords,
+
+    #[error("not enough typing history yet, finish a few more tests first")]
+    NotEnoughHistory,
+
+    #[error(
+        "weighting = \"frequency\" needs src/res/frequency.tsv, which this build was made without"
+    )]
+    NoFrequencies,
+
+    #[error("{0:?} is not a test code")]
+    InvalidCode(String),
+
+    /// results of a subcommand that couldn't be written out
+    #[error("failed to format output: {0}")]
+    Output(String),
+
+    /// a command from the hooks config that couldn't be started
+    #[error("failed to run hook {command:?}: {source}")]
+    Hook {
+        command: String,
+        source: std::io::Error,
+    },
+
+    /// the generator command from the config failed, with what it wrote to stderr
+    #[error("word generator {command:?} failed: {message}")]
+    Generator { command: String, message: String },
+
+    /// bad command line arguments
+    #[error("{0}")]
+    Usage(String),
+
+    #[error("terminal error: {0}")]
+    Terminal(#[from] std::io::Error),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
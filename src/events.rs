@@ -0,0 +1,69 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tt::error::{Error, Result};
+
+/// something that happened in a game, written as one json object per line
+#[derive(serde::Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GameEvent<'a> {
+    Keystroke {
+        key: String,
+        ms: u64,
+    },
+    /// a typed character that didn't match the target
+    Error {
+        typed: char,
+        word: &'a str,
+        ms: u64,
+    },
+    WordCompleted {
+        word: &'a str,
+        correct: bool,
+        ms: u64,
+    },
+    TestEnd {
+        wpm: f64,
+        accuracy: f64,
+        seconds: f64,
+        finished: bool,
+    },
+}
+
+/// a file game events are written to as they happen
+pub struct EventLog {
+    path: PathBuf,
+    file: File,
+}
+
+impl EventLog {
+    /// open a file or fifo for appending, on unix a descriptor can be given as /dev/fd/N
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|source| Error::Write {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+        })
+    }
+
+    pub fn emit(&mut self, event: &GameEvent) -> Result<()> {
+        serde_json::to_writer(&mut self.file, event)
+            .map_err(std::io::Error::from)
+            .and_then(|()| self.file.write_all(b"\n"))
+            .and_then(|()| self.file.flush())
+            .map_err(|source| Error::Write {
+                path: self.path.clone(),
+                source,
+            })
+    }
+}
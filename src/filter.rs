@@ -0,0 +1,119 @@
+use tt::{
+    date::SECS_PER_DAY,
+    history::Session,
+    locale::{Locale, Message},
+};
+
+/// how far back the stats screen looks
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum DateRange {
+    #[default]
+    All,
+    Week,
+    Month,
+    Year,
+}
+
+impl DateRange {
+    pub const fn next(self) -> Self {
+        match self {
+            Self::All => Self::Week,
+            Self::Week => Self::Month,
+            Self::Month => Self::Year,
+            Self::Year => Self::All,
+        }
+    }
+
+    pub const fn label(self) -> Message {
+        match self {
+            Self::All => Message::AllTime,
+            Self::Week => Message::LastWeek,
+            Self::Month => Message::LastMonth,
+            Self::Year => Message::LastYear,
+        }
+    }
+
+    const fn days(self) -> Option<u64> {
+        match self {
+            Self::All => None,
+            Self::Week => Some(7),
+            Self::Month => Some(30),
+            Self::Year => Some(365),
+        }
+    }
+}
+
+/// which sessions the stats count, each filter left out matches everything
+#[derive(Default)]
+pub struct StatsFilter {
+    pub tag: Option<String>,
+    pub mode: Option<String>,
+    pub range: DateRange,
+}
+
+impl StatsFilter {
+    pub fn matches(&self, session: &Session, now: u64) -> bool {
+        self.tag
+            .as_ref()
+            .is_none_or(|tag| session.tags.contains(tag))
+            && self.mode.as_ref().is_none_or(|mode| *mode == session.mode)
+            && self
+                .range
+                .days()
+                .is_none_or(|days| session.date >= now.saturating_sub(days * SECS_PER_DAY))
+    }
+
+    /// the next tag used in the history, after the last one back to every tag
+    pub fn next_tag(&mut self, sessions: &[Session]) {
+        self.tag = next(
+            self.tag.as_deref(),
+            sessions.iter().flat_map(|session| &session.tags),
+        );
+    }
+
+    /// the next mode in the history, sessions saved before modes were kept have none
+    pub fn next_mode(&mut self, sessions: &[Session]) {
+        self.mode = next(
+            self.mode.as_deref(),
+            sessions
+                .iter()
+                .map(|session| &session.mode)
+                .filter(|mode| !mode.is_empty()),
+        );
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.tag.is_some() || self.mode.is_some() || self.range != DateRange::All
+    }
+
+    /// what is being counted, like "words, tagged morning, last 7 days"
+    pub fn label(&self, locale: Locale) -> String {
+        let mut parts = Vec::new();
+
+        if let Some(mode) = &self.mode {
+            parts.push(mode.replace('_', " "));
+        }
+
+        if let Some(tag) = &self.tag {
+            parts.push(locale.fill(Message::Tagged, &[tag]));
+        }
+
+        parts.push(locale.get(self.range.label()).to_string());
+
+        parts.join(", ")
+    }
+}
+
+/// the value after the current one in sorted order, None after the last
+fn next<'a>(current: Option<&str>, values: impl Iterator<Item = &'a String>) -> Option<String> {
+    let mut values: Vec<&String> = values.collect();
+    values.sort_unstable();
+    values.dedup();
+
+    current
+        .map_or_else(
+            || values.first(),
+            |current| values.iter().find(|value| value.as_str() > current),
+        )
+        .map(|value| (*value).clone())
+}
@@ -0,0 +1,59 @@
+use crate::{
+    history::Stroke,
+    layout::{Finger, Hand, KeyboardLayout, FINGERS},
+};
+
+/// how a test's strokes were spread over the fingers
+pub struct FingerStats {
+    /// strokes per finger in the order of `FINGERS`
+    pub load: [usize; FINGERS.len()],
+    /// strokes on a key of the layout
+    pub strokes: usize,
+    /// share of letter pairs typed by one finger on two different keys
+    pub same_finger: f64,
+    /// share of letter pairs typed by different hands
+    pub alternation: f64,
+}
+
+impl FingerStats {
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new(strokes: &[Stroke], layout: &KeyboardLayout) -> Self {
+        let fingers: Vec<Option<(Hand, Finger)>> =
+            strokes.iter().map(|(c, ..)| layout.finger(*c)).collect();
+
+        let mut load = [0; FINGERS.len()];
+
+        for finger in fingers.iter().flatten() {
+            if let Some(index) = FINGERS.iter().position(|f| f == finger) {
+                load[index] += 1;
+            }
+        }
+
+        let (mut pairs, mut same_finger, mut alternation) = (0, 0, 0);
+
+        // keys off the layout, like space, break the pairs
+        for (window, chars) in fingers.windows(2).zip(strokes.windows(2)) {
+            if let [Some(a), Some(b)] = window {
+                pairs += 1;
+                same_finger += usize::from(a == b && chars[0].0 != chars[1].0);
+                alternation += usize::from(a.0 != b.0);
+            }
+        }
+
+        let share = |count: usize| {
+            if pairs > 0 {
+                count as f64 / f64::from(pairs)
+            } else {
+                0.0
+            }
+        };
+
+        Self {
+            load,
+            strokes: load.iter().sum(),
+            same_finger: share(same_finger),
+            alternation: share(alternation),
+        }
+    }
+}
@@ -0,0 +1,1169 @@
+use crate::{
+    bigrams,
+    clock::{Clock, SystemClock},
+    error::{Error, Result},
+    history::{History, Stroke},
+    keymap::Action,
+    names::{self, Pack},
+    noise, punctuation,
+    settings::GameSettings,
+    snapshot::Snapshot,
+    words::{word_field, WORDS},
+};
+use rand::{distr::weighted::WeightedIndex, Rng};
+use ratatui::crossterm::event::{Event, KeyCode, KeyModifiers};
+use std::time::{Duration, Instant};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// a featured word is every this many words
+const FEATURED_EVERY: usize = 3;
+
+/// how many of the worst letter pairs the weak bigrams drill practices
+const WEAK_BIGRAMS: usize = 5;
+
+/// how many of the worst runs of three letters the weak bigrams drill practices too
+const WEAK_TRIGRAMS: usize = 3;
+
+pub enum GameSpan<T> {
+    Correct(T),
+    Wrong(T),
+    Overflow(T),
+    Skipped(T),
+    Hidden(T),
+}
+
+impl<T> GameSpan<T> {
+    pub fn map<T2, F: Fn(&T) -> T2>(&self, f: F) -> GameSpan<T2> {
+        match self {
+            Self::Correct(v) => GameSpan::Correct(f(v)),
+            Self::Wrong(v) => GameSpan::Wrong(f(v)),
+            Self::Overflow(v) => GameSpan::Overflow(f(v)),
+            Self::Skipped(v) => GameSpan::Skipped(f(v)),
+            Self::Hidden(v) => GameSpan::Hidden(f(v)),
+        }
+    }
+}
+
+/// one target word after a test, to go over what happened in it
+pub struct WordReview<'a> {
+    pub target: &'a str,
+    /// what was left of the word in the input, empty if the test ended before it
+    pub typed: &'a str,
+    /// every character typed in the word with whether it was right, fixed mistakes too
+    pub keys: Vec<(char, bool)>,
+}
+
+/// the next two graphemes of the input are the next two of the target the other way round,
+/// spaces are never part of a swap
+fn swapped<'a>(
+    target: &(impl Iterator<Item = &'a str> + Clone),
+    input: &(impl Iterator<Item = &'a str> + Clone),
+) -> bool {
+    let target: Vec<&str> = target.clone().take(2).collect();
+    let input: Vec<&str> = input.clone().take(2).collect();
+
+    matches!(
+        (target.as_slice(), input.as_slice()),
+        ([t0, t1], [i0, i1]) if t0 != t1 && t0 == i1 && t1 == i0 && *t0 != " " && *t1 != " "
+    )
+}
+
+/// weighted sampling without replacement: each item gets an exponential clock that runs slower
+/// the higher its weight, and they come out in the order the clocks ring, so each place takes
+/// one of the items left with a chance of one over its weight
+fn weighted_order<T>(items: Vec<(f64, T)>, rng: &mut impl Rng) -> Vec<T> {
+    let mut items: Vec<(f64, T)> = items
+        .into_iter()
+        .map(|(weight, item)| (weight * -(1.0 - rng.random::<f64>()).ln(), item))
+        .collect();
+
+    items.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    items.into_iter().map(|(_, item)| item).collect()
+}
+
+/// number of user perceived characters in a string
+#[must_use]
+pub fn graphemes(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// how typed keys are judged, taken from the settings when a test starts
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InputRules {
+    /// a space typed inside a word is a mistake instead of skipping the rest of the word
+    pub strict_space: bool,
+    /// a space always ends the word, the rest of it counts as wrong and a space before any
+    /// letter of a word is ignored
+    pub space_commits: bool,
+    /// two neighbouring letters typed the wrong way round are one mistake instead of two
+    pub forgiving: bool,
+    /// a word typed right moves on by itself, a space typed after it anyway is ignored
+    pub auto_space: bool,
+    /// letters past the end of a word beyond this are refused, 0 takes them all
+    pub max_overflow: usize,
+}
+
+impl<T> From<&GameSettings<T>> for InputRules {
+    fn from(settings: &GameSettings<T>) -> Self {
+        Self {
+            strict_space: settings.strict_space,
+            space_commits: settings.space_commits,
+            forgiving: settings.forgiving,
+            auto_space: settings.auto_space,
+            max_overflow: settings.max_overflow,
+        }
+    }
+}
+
+pub struct Game<K, C = SystemClock> {
+    pub words: Vec<&'static toml::map::Map<String, toml::Value>>,
+    pub key_log: Vec<(K, Instant)>,
+    /// typed characters with whether each was right and the target word it was typed in
+    pub strokes: Vec<(char, bool, Instant, usize)>,
+    pub target: String,
+    pub input: String,
+    pub spans: Vec<GameSpan<String>>,
+    /// text shown for each target word instead of its definition, empty for plain tests
+    pub prompts: Vec<String>,
+    pub combo: usize,
+    pub best_combo: usize,
+    /// total time spent paused, excluded from the timer
+    pub paused: Duration,
+    pub paused_at: Option<Instant>,
+    /// paused because nothing was typed for a while, the next key resumes
+    pub afk: bool,
+    pub rules: InputRules,
+    /// keys refused, spaces in strict space mode and letters over the overflow cap,
+    /// counted against accuracy
+    pub rejected: usize,
+    /// every timestamp comes from here
+    pub clock: C,
+}
+
+impl<C: Clock + Default> Game<KeyCode, C> {
+    /// a test picked by the settings, the same rng state always gives the same test
+    ///
+    /// # Errors
+    /// `Error::NoWords` when the settings leave no words to pick, and the errors of
+    /// `GameSettings::weight`
+    pub fn new(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Result<Self> {
+        let words = Self::pick_words(settings, rng)?;
+        Ok(Self::from_words(settings, words, rng))
+    }
+
+    /// a test picked by the settings with `word` as every few words
+    ///
+    /// # Errors
+    /// the errors of `Game::new`
+    pub fn featuring(
+        settings: &GameSettings<usize>,
+        word: &'static str,
+        rng: &mut impl Rng,
+    ) -> Result<Self> {
+        let mut words = Self::pick_words(settings, rng)?;
+
+        for slot in words.iter_mut().step_by(FEATURED_EVERY) {
+            *slot = word;
+        }
+
+        Ok(Self::from_words(settings, words, rng))
+    }
+
+    /// the words of a test in order
+    #[allow(clippy::cast_precision_loss)]
+    fn pick_words(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Result<Vec<&'static str>> {
+        // the map's order changes between runs, so go through the words in a fixed one
+        let mut words: Vec<(&String, &'static toml::Table)> = WORDS
+            .iter()
+            .filter(|(word, _)| settings.allows(word))
+            .collect();
+        words.sort_unstable_by_key(|(word, _)| *word);
+
+        // a timed test can want more words than there are, so it draws them like real text
+        if settings.repeat_words || settings.time_secs > 0 {
+            return Self::draw_words(settings, &words, rng);
+        }
+
+        let words = words
+            .into_iter()
+            .map(|(_, toml)| Ok((settings.weight(toml)? as f64, toml)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let words = weighted_order(words, rng)
+            .into_iter()
+            .take(settings.test_len())
+            .map(|toml| word_field(toml, "word"))
+            .collect::<Result<Vec<_>>>()?;
+
+        if words.is_empty() {
+            return Err(Error::NoWords);
+        }
+
+        Ok(words)
+    }
+
+    /// weighted sampling with replacement, so common words come back like in real text,
+    /// but never straight after themselves
+    #[allow(clippy::cast_precision_loss)]
+    fn draw_words(
+        settings: &GameSettings<usize>,
+        words: &[(&String, &'static toml::Table)],
+        rng: &mut impl Rng,
+    ) -> Result<Vec<&'static str>> {
+        let weights = words
+            .iter()
+            .map(|(_, toml)| Ok(1.0 / settings.weight(toml)? as f64))
+            .collect::<Result<Vec<f64>>>()?;
+        let index = WeightedIndex::new(weights).map_err(|_| Error::NoWords)?;
+
+        let mut picked: Vec<&'static str> = Vec::new();
+
+        while picked.len() < settings.test_len() {
+            let word = word_field(words[rng.sample(&index)].1, "word")?;
+
+            // a word that is the only one allowed has to repeat
+            if picked.last() != Some(&word) || words.len() == 1 {
+                picked.push(word);
+            }
+        }
+
+        if picked.is_empty() {
+            return Err(Error::NoWords);
+        }
+
+        Ok(picked)
+    }
+
+    /// a test of these words with the names and punctuation from the settings
+    fn from_words(
+        settings: &GameSettings<usize>,
+        mut words: Vec<&'static str>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        names::mix_in(&mut words, settings.proper_names, rng);
+
+        let target = if settings.punctuation {
+            punctuation::sentences(&words, rng)
+        } else {
+            words.join(" ")
+        };
+        let target = noise::garble(&target, settings, rng);
+
+        Self {
+            rules: settings.into(),
+            ..Self::from_target(target)
+        }
+    }
+
+    /// a fresh game over a fixed target, used to repeat a test
+    #[must_use]
+    pub fn from_target(target: String) -> Self {
+        let mut game = Self {
+            words: target
+                .split_whitespace()
+                .filter_map(|word| WORDS.get(punctuation::bare(word)))
+                .collect(),
+            key_log: Vec::new(),
+            strokes: Vec::new(),
+            target,
+            input: String::new(),
+            spans: Vec::new(),
+            prompts: Vec::new(),
+            combo: 0,
+            best_combo: 0,
+            paused: Duration::ZERO,
+            paused_at: None,
+            afk: false,
+            rules: InputRules::default(),
+            rejected: 0,
+            clock: C::default(),
+        };
+
+        game.calculate_spans();
+        game
+    }
+
+    /// a drill of words containing the letter pairs and trigrams the history shows are
+    /// slowest to type
+    ///
+    /// # Errors
+    /// `Error::NotEnoughHistory` when no pair has been typed often enough to judge,
+    /// and the errors of `Game::new`
+    #[allow(clippy::cast_precision_loss)]
+    pub fn weak_bigrams(
+        settings: &GameSettings<usize>,
+        history: &History,
+        rng: &mut impl Rng,
+    ) -> Result<Self> {
+        let weak = bigrams::weak::<2>(&history.sessions, WEAK_BIGRAMS);
+        let weak_trigrams = bigrams::weak::<3>(&history.sessions, WEAK_TRIGRAMS);
+
+        if weak.is_empty() && weak_trigrams.is_empty() {
+            return Err(Error::NotEnoughHistory);
+        }
+
+        // the map's order changes between runs, so go through the words in a fixed one
+        let mut candidates: Vec<(&String, &'static toml::Table)> = WORDS
+            .iter()
+            .filter(|(word, _)| settings.allows(word))
+            .collect();
+        candidates.sort_unstable_by_key(|(word, _)| *word);
+
+        // words with more of the pairs and trigrams come up sooner
+        let candidates = candidates
+            .into_iter()
+            .filter_map(|(_, toml)| {
+                let word = word_field(toml, "word");
+                let hits = word.as_ref().map_or(1, |word| {
+                    bigrams::hits(word, &weak) + bigrams::hits(word, &weak_trigrams)
+                });
+
+                (hits > 0).then(|| Ok((settings.weight(toml)? as f64 / hits as f64, word?)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if candidates.is_empty() {
+            return Err(Error::NoWords);
+        }
+
+        // too few words have the pairs for one round of them to fill the test, so go round
+        // again in a new order each time
+        let mut words: Vec<&str> = Vec::new();
+
+        while words.len() < settings.test_len() {
+            let mut round = weighted_order(candidates.clone(), rng);
+
+            // the first of a round can't be the last of the one before
+            if round.len() > 1 && words.last() == round.first() {
+                round.swap(0, 1);
+            }
+
+            words.extend(round);
+        }
+
+        words.truncate(settings.test_len());
+
+        Ok(Self::from_target(words.join(" ")))
+    }
+
+    /// a drill of numerals to be typed as toki pona number phrases
+    pub fn numbers(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Self {
+        let drill = settings.number_system.drill(settings.test_len(), rng);
+
+        let prompts = drill
+            .iter()
+            .flat_map(|(n, phrase)| phrase.split(' ').map(move |_| n.to_string()))
+            .collect();
+
+        let target = drill
+            .into_iter()
+            .map(|(_, phrase)| phrase)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        Self {
+            prompts,
+            ..Self::from_target(target)
+        }
+    }
+
+    /// a drill of names from a pack like ma Kanata, as long as the settings' tests
+    pub fn names(settings: &GameSettings<usize>, pack: Pack, rng: &mut impl Rng) -> Self {
+        Self::from_target(pack.words(settings.test_len(), rng).join(" "))
+    }
+
+    /// names on their own, to read from their cartouches
+    pub fn cartouches(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Self {
+        Self::from_target(names::bare_names(settings.test_len(), rng).join(" "))
+    }
+
+    /// rebuild a game from a snapshot, it starts out paused
+    #[must_use]
+    pub fn from_snapshot(snapshot: &Snapshot) -> Self {
+        let game = Self::from_target(snapshot.target.clone());
+        let now = game.clock.now();
+        let last = snapshot.key_log.last().map_or(0, |(_, ms)| *ms);
+        let start = now.checked_sub(Duration::from_millis(last)).unwrap_or(now);
+
+        let mut game = Self {
+            key_log: snapshot
+                .key_log
+                .iter()
+                .map(|(key, ms)| (*key, start + Duration::from_millis(*ms)))
+                .collect(),
+            strokes: snapshot
+                .strokes
+                .iter()
+                .map(|(c, correct, ms, word)| {
+                    (*c, *correct, start + Duration::from_millis(*ms), *word)
+                })
+                .collect(),
+            input: snapshot.input.clone(),
+            combo: snapshot.combo,
+            best_combo: snapshot.best_combo,
+            paused: Duration::from_millis(snapshot.paused_ms),
+            paused_at: Some(now),
+            prompts: snapshot.prompts.clone(),
+            rules: (&snapshot.settings).into(),
+            rejected: snapshot.rejected,
+            ..game
+        };
+
+        game.calculate_spans();
+        game
+    }
+
+    /// milliseconds from the first key to an instant
+    pub fn millis(&self, time: Instant) -> u64 {
+        let since = self.key_log.first().map_or(Duration::ZERO, |(_, start)| {
+            time.saturating_duration_since(*start)
+        });
+
+        u64::try_from(since.as_millis()).unwrap_or(u64::MAX)
+    }
+
+    /// strokes timed from the first key, as they are saved
+    pub fn saved_strokes(&self) -> Vec<Stroke> {
+        self.strokes
+            .iter()
+            .map(|(c, correct, time, word)| (*c, *correct, self.millis(*time), *word))
+            .collect()
+    }
+
+    /// the game so far, the mode it was started in is filled in by whoever started it
+    pub fn snapshot(&self, settings: &GameSettings<usize>) -> Snapshot {
+        Snapshot {
+            version: crate::migrate::SNAPSHOT,
+            mode: String::new(),
+            custom_len: 0,
+            custom_secs: 0,
+            pack: String::new(),
+            lesson: None,
+            target: self.target.clone(),
+            input: self.input.clone(),
+            key_log: self
+                .key_log
+                .iter()
+                .map(|(key, time)| (*key, self.millis(*time)))
+                .collect(),
+            strokes: self.saved_strokes(),
+            paused_ms: u64::try_from(self.paused.as_millis()).unwrap_or(u64::MAX),
+            prompts: self.prompts.clone(),
+            rejected: self.rejected,
+            combo: self.combo,
+            best_combo: self.best_combo,
+            settings: settings.clone(),
+        }
+    }
+
+    /// number of target graphemes the input has consumed so far
+    pub fn cursor(&self) -> usize {
+        self.spans
+            .iter()
+            .map(|span| match span {
+                GameSpan::Correct(s) | GameSpan::Wrong(s) | GameSpan::Skipped(s) => graphemes(s),
+                GameSpan::Overflow(_) | GameSpan::Hidden(_) => 0,
+            })
+            .sum()
+    }
+
+    /// each target word reached so far with whether it was typed without mistakes
+    pub fn word_results(&self) -> Vec<(&str, bool)> {
+        let words: Vec<&str> = self.target.split(' ').collect();
+        let mut correct = vec![true; words.len()];
+        let mut index = 0;
+
+        for span in &self.spans {
+            match span {
+                GameSpan::Correct(s) => index += s.chars().filter(|c| *c == ' ').count(),
+                GameSpan::Wrong(_) | GameSpan::Skipped(_) | GameSpan::Overflow(_) => {
+                    correct[index.min(words.len() - 1)] = false;
+                }
+                GameSpan::Hidden(_) => break,
+            }
+        }
+
+        words.into_iter().zip(correct).take(index + 1).collect()
+    }
+
+    /// every target word next to what was typed for it
+    pub fn review(&self) -> Vec<WordReview<'_>> {
+        let mut typed = self.input.split(' ');
+
+        self.target
+            .split(' ')
+            .enumerate()
+            .map(|(index, target)| WordReview {
+                target,
+                typed: typed.next().unwrap_or_default(),
+                keys: self
+                    .strokes
+                    .iter()
+                    .filter(|stroke| stroke.3 == index)
+                    .map(|&(c, correct, ..)| (c, correct))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// time between each keystroke and the one before it
+    pub fn intervals(&self) -> Vec<Duration> {
+        self.key_log
+            .windows(2)
+            .map(|pair| pair[1].1.saturating_duration_since(pair[0].1))
+            .collect()
+    }
+
+    /// wpm over the last few seconds at each second since the first key
+    #[allow(clippy::cast_precision_loss)]
+    pub fn rolling_wpm(&self) -> Vec<u64> {
+        const WINDOW: Duration = Duration::from_secs(5);
+
+        let Some((_, start)) = self.key_log.first() else {
+            return Vec::new();
+        };
+
+        let seconds = self.key_log.last().map_or(0, |(_, last)| {
+            last.saturating_duration_since(*start).as_secs()
+        });
+
+        (1..=seconds)
+            .map(|second| {
+                let end = *start + Duration::from_secs(second);
+                let correct = self
+                    .strokes
+                    .iter()
+                    .filter(|(_, correct, time, _)| {
+                        *correct && *time <= end && end.saturating_duration_since(*time) < WINDOW
+                    })
+                    .count();
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let wpm = (correct as f64 / 5.0 / (WINDOW.as_secs_f64() / 60.0)).round() as u64;
+                wpm
+            })
+            .collect()
+    }
+
+    /// gaps before a typed character longer than threshold, with the word they came in
+    pub fn hesitations(&self, threshold: Duration) -> Vec<(&str, Duration)> {
+        self.strokes
+            .windows(2)
+            .filter_map(|pair| {
+                let gap = pair[1].2.saturating_duration_since(pair[0].2);
+                let word = self.target.split(' ').nth(pair[1].3)?;
+
+                (gap > threshold).then_some((word, gap))
+            })
+            .collect()
+    }
+
+    /// time since the first keystroke
+    pub fn elapsed(&self) -> Duration {
+        let Some((_, start)) = self.key_log.first() else {
+            return Duration::ZERO;
+        };
+
+        self.paused_at
+            .unwrap_or_else(|| self.clock.now())
+            .saturating_duration_since(*start)
+            .saturating_sub(self.paused)
+    }
+
+    /// keys pressed per character of input, 1 means nothing was corrected
+    #[allow(clippy::cast_precision_loss)]
+    pub fn kspc(&self) -> f64 {
+        let chars = graphemes(&self.input);
+
+        if chars > 0 {
+            self.key_log.len() as f64 / chars as f64
+        } else {
+            1.0
+        }
+    }
+
+    /// share of consumed graphemes that were typed correctly
+    #[allow(clippy::cast_precision_loss)]
+    pub fn accuracy(&self) -> f64 {
+        let (correct, total) =
+            self.spans
+                .iter()
+                .fold((0, 0), |(correct, total), span| match span {
+                    GameSpan::Correct(s) => (correct + graphemes(s), total + graphemes(s)),
+                    GameSpan::Wrong(s) | GameSpan::Overflow(s) | GameSpan::Skipped(s) => {
+                        (correct, total + graphemes(s))
+                    }
+                    GameSpan::Hidden(_) => (correct, total),
+                });
+        let total = total + self.rejected;
+
+        if total > 0 {
+            correct as f64 / total as f64
+        } else {
+            1.0
+        }
+    }
+
+    /// the next target grapheme is part of a word rather than a space or the end
+    fn inside_word(&self) -> bool {
+        self.target
+            .graphemes(true)
+            .nth(self.cursor())
+            .is_some_and(|grapheme| grapheme != " ")
+    }
+
+    /// graphemes typed past the end of the current word
+    fn overflow(&self) -> usize {
+        let index = self.input.matches(' ').count();
+        let typed = self.input.rsplit(' ').next().unwrap_or_default();
+        let target = self.target.split(' ').nth(index).unwrap_or_default();
+
+        graphemes(typed).saturating_sub(graphemes(target))
+    }
+
+    /// a key that is a mistake and doesn't change the input
+    fn reject(&mut self, c: char) {
+        let word = self.input.matches(' ').count();
+        self.strokes.push((c, false, self.clock.now(), word));
+        self.rejected += 1;
+        self.combo = 0;
+    }
+
+    /// the word being typed matches its target word and another word follows it
+    fn word_done(&self) -> bool {
+        let index = self.input.matches(' ').count();
+        let typed = self.input.rsplit(' ').next().unwrap_or_default();
+        let mut words = self.target.split(' ').skip(index);
+
+        !typed.is_empty() && words.next() == Some(typed) && words.next().is_some()
+    }
+
+    /// the whole target has been typed
+    pub fn is_finished(&self) -> bool {
+        self.cursor() >= graphemes(&self.target)
+    }
+
+    pub fn pause(&mut self) {
+        // the timer only starts with the first key
+        if !self.key_log.is_empty() {
+            self.paused_at.get_or_insert_with(|| self.clock.now());
+        }
+    }
+
+    /// pause the timer from the last keystroke if nothing was typed since for timeout
+    pub fn check_afk(&mut self, timeout: Duration) {
+        if self.paused_at.is_some() || self.is_finished() {
+            return;
+        }
+
+        if let Some((_, last)) = self.key_log.last() {
+            if self.clock.now().saturating_duration_since(*last) >= timeout {
+                self.paused_at = Some(*last);
+                self.afk = true;
+            }
+        }
+    }
+
+    pub fn resume(&mut self) {
+        self.afk = false;
+
+        if let Some(paused_at) = self.paused_at.take() {
+            self.paused += self.clock.now().saturating_duration_since(paused_at);
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    pub fn wpm(&self) -> f64 {
+        let correct: usize = self
+            .spans
+            .iter()
+            .map(|span| match span {
+                GameSpan::Correct(s) => graphemes(s),
+                _ => 0,
+            })
+            .sum();
+
+        let minutes = self.elapsed().as_secs_f64() / 60.0;
+
+        if minutes > 0.0 {
+            correct as f64 / 5.0 / minutes
+        } else {
+            0.0
+        }
+    }
+
+    /// judge the rest of the test by other rules, the input typed so far too
+    pub fn set_rules(&mut self, rules: InputRules) {
+        self.rules = rules;
+        self.calculate_spans();
+    }
+
+    /// diff the input against the target a grapheme at a time
+    fn calculate_spans(&mut self) {
+        let mut spans = Vec::new();
+
+        let mut targ = self.target.graphemes(true).peekable();
+        let mut inpt = self.input.graphemes(true).peekable();
+
+        loop {
+            match (targ.peek().copied(), inpt.peek().copied()) {
+                (Some(t), Some(i)) if t == i => {
+                    spans.push(GameSpan::Correct(t));
+                    targ.next();
+                    inpt.next();
+                }
+                // the first of the pair is the mistake and the second is taken as right
+                (Some(t), Some(_)) if self.rules.forgiving && swapped(&targ, &inpt) => {
+                    spans.push(GameSpan::Wrong(t));
+                    targ.next();
+                    inpt.nth(1);
+
+                    if let Some(t) = targ.next() {
+                        spans.push(GameSpan::Correct(t));
+                    }
+                }
+                (Some(t), Some(" ")) => {
+                    spans.push(if self.rules.space_commits {
+                        GameSpan::Wrong(t)
+                    } else {
+                        GameSpan::Skipped(t)
+                    });
+                    targ.next();
+                }
+                (Some(" ") | None, Some(i)) => {
+                    spans.push(GameSpan::Overflow(i));
+                    inpt.next();
+                }
+                (Some(t), Some(_)) => {
+                    spans.push(GameSpan::Wrong(t));
+                    targ.next();
+                    inpt.next();
+                }
+                (Some(t), None) => {
+                    spans.push(GameSpan::Hidden(t));
+                    targ.next();
+                }
+                _ => break,
+            }
+        }
+
+        self.spans.clear();
+
+        // hidden spans keep the target text, how much of it shows is up to the drawing
+        for span in spans {
+            let span = span.map(|s| (*s).to_string());
+
+            let merged = match (self.spans.last_mut(), &span) {
+                (Some(GameSpan::Correct(s_span)), GameSpan::Correct(c_span))
+                | (Some(GameSpan::Wrong(s_span)), GameSpan::Wrong(c_span))
+                | (Some(GameSpan::Overflow(s_span)), GameSpan::Overflow(c_span))
+                | (Some(GameSpan::Skipped(s_span)), GameSpan::Skipped(c_span))
+                | (Some(GameSpan::Hidden(s_span)), GameSpan::Hidden(c_span)) => {
+                    s_span.push_str(c_span);
+                    true
+                }
+                _ => false,
+            };
+
+            if !merged {
+                self.spans.push(span);
+            }
+        }
+    }
+
+    /// remove the last grapheme of the input, combining marks go with their base
+    fn backspace(&mut self) {
+        if let Some((index, _)) = self.input.grapheme_indices(true).next_back() {
+            self.input.truncate(index);
+        }
+    }
+
+    /// remove the word being typed, or the previous word if the current one is empty
+    pub fn delete_word(&mut self) {
+        let input = if self.input.ends_with(' ') {
+            self.input.trim_end_matches(' ')
+        } else {
+            &self.input
+        };
+
+        let len = input.trim_end_matches(|c| c != ' ').len();
+        self.input.truncate(len);
+    }
+
+    /// give up on the current word, the rest of it is marked as skipped
+    pub fn skip_word(&mut self) {
+        self.combo = 0;
+        self.input.push(' ');
+    }
+
+    /// handle a key, `action` is what the keymap bound it to if anything
+    pub fn crossterm_event(&mut self, event: &Event, action: Option<Action>) {
+        if self.afk {
+            self.resume();
+        }
+
+        if let Event::Key(key_event) = event {
+            self.key_log.push((key_event.code, self.clock.now()));
+
+            match (action, key_event.code) {
+                (Some(Action::DeleteWord), _) => self.delete_word(),
+                (Some(Action::SkipWord), _) => self.skip_word(),
+                // unbound modified keys are not text
+                (_, KeyCode::Char(_))
+                    if key_event
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {}
+                (_, KeyCode::Char(' ')) if self.rules.strict_space && self.inside_word() => {
+                    // the caret stays where it is
+                    self.reject(' ');
+                }
+                // the word already runs over as far as it may
+                (_, KeyCode::Char(c))
+                    if c != ' '
+                        && self.rules.max_overflow > 0
+                        && self.overflow() >= self.rules.max_overflow =>
+                {
+                    self.reject(c);
+                }
+                // nothing of the word is typed yet, so there is nothing to commit
+                (_, KeyCode::Char(' '))
+                    if (self.rules.space_commits || self.rules.auto_space)
+                        && (self.input.is_empty() || self.input.ends_with(' ')) => {}
+                (_, KeyCode::Char(c)) => {
+                    // every space moves on a word, typed or skipping
+                    let word = self.input.matches(' ').count();
+                    self.input.push(c);
+                    self.calculate_spans();
+
+                    // a key can finish a grapheme that is already there, so check the diff
+                    let last = self
+                        .spans
+                        .iter()
+                        .rfind(|span| !matches!(span, GameSpan::Hidden(_)));
+
+                    let correct = matches!(last, Some(GameSpan::Correct(_)));
+                    self.strokes.push((c, correct, self.clock.now(), word));
+
+                    if correct {
+                        self.combo += 1;
+                        self.best_combo = self.best_combo.max(self.combo);
+                    } else {
+                        self.combo = 0;
+                    }
+
+                    if self.rules.auto_space && self.word_done() {
+                        self.input.push(' ');
+                    }
+                }
+                (_, KeyCode::Backspace) => self.backspace(),
+                _ => (),
+            }
+        }
+
+        self.calculate_spans();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{clock::ManualClock, settings::Pin};
+    use rand::{rngs::StdRng, SeedableRng};
+    use ratatui::crossterm::event::KeyEvent;
+
+    fn game(target: &str, rules: InputRules) -> Game<KeyCode, ManualClock> {
+        let mut game = Game::from_target(target.to_string());
+        game.set_rules(rules);
+        game
+    }
+
+    /// type the text a tenth of a second a key
+    fn type_text(game: &mut Game<KeyCode, ManualClock>, text: &str) {
+        for c in text.chars() {
+            game.crossterm_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))), None);
+            game.clock.advance(Duration::from_millis(100));
+        }
+    }
+
+    fn spans(game: &Game<KeyCode, ManualClock>) -> Vec<(&'static str, &str)> {
+        game.spans
+            .iter()
+            .map(|span| match span {
+                GameSpan::Correct(s) => ("correct", s.as_str()),
+                GameSpan::Wrong(s) => ("wrong", s.as_str()),
+                GameSpan::Overflow(s) => ("overflow", s.as_str()),
+                GameSpan::Skipped(s) => ("skipped", s.as_str()),
+                GameSpan::Hidden(s) => ("hidden", s.as_str()),
+            })
+            .collect()
+    }
+
+    /// words of the core category for drawing, leaked as the word data is static
+    fn words(names: &[&str]) -> Vec<(&'static String, &'static toml::Table)> {
+        names
+            .iter()
+            .map(|name| {
+                let table: toml::Table = toml::from_str(&format!(
+                    "word = {name:?}\nusage_category = \"core\"\ndeprecated = false"
+                ))
+                .unwrap();
+                let name: &'static String = Box::leak(Box::new((*name).to_string()));
+                (name, &*Box::leak(Box::new(table)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn drawn_words_come_back_by_weight() {
+        let settings = GameSettings {
+            len: 3000,
+            repeat_words: true,
+            pins: [("mi".to_string(), Pin::Often)].into(),
+            ..GameSettings::default()
+        };
+        let drawn = Game::<KeyCode, ManualClock>::draw_words(
+            &settings,
+            &words(&["mi", "pona", "moku"]),
+            &mut StdRng::seed_from_u64(7),
+        )
+        .unwrap();
+
+        assert_eq!(drawn.len(), 3000);
+
+        // mi is a thousand times lighter, so it is every other word as it can't follow itself
+        let count = |word| drawn.iter().filter(|drawn| **drawn == word).count();
+        assert!(count("mi") > 1400, "{}", count("mi"));
+        assert!(count("pona") > 600 && count("moku") > 600);
+    }
+
+    #[test]
+    fn a_drawn_word_never_follows_itself() {
+        let settings = GameSettings {
+            len: 200,
+            repeat_words: true,
+            ..GameSettings::default()
+        };
+
+        for seed in 0..20 {
+            for names in [&["mi", "pona", "moku"][..], &["mi", "pona"]] {
+                let drawn = Game::<KeyCode, ManualClock>::draw_words(
+                    &settings,
+                    &words(names),
+                    &mut StdRng::seed_from_u64(seed),
+                )
+                .unwrap();
+
+                assert!(drawn.windows(2).all(|pair| pair[0] != pair[1]), "{drawn:?}");
+            }
+        }
+
+        // unless it is the only word there is
+        let drawn = Game::<KeyCode, ManualClock>::draw_words(
+            &settings,
+            &words(&["mi"]),
+            &mut StdRng::seed_from_u64(0),
+        )
+        .unwrap();
+        assert_eq!(drawn, ["mi"; 200]);
+    }
+
+    #[test]
+    fn weighted_order_puts_light_words_first_more_often() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut firsts = [0_usize; 3];
+
+        for _ in 0..3000 {
+            let order = weighted_order(vec![(1.0, 0), (2.0, 1), (4.0, 2)], &mut rng);
+            firsts[order[0]] += 1;
+        }
+
+        // one over the weights is 4:2:1 of 3000
+        for (count, expected) in firsts.into_iter().zip([1714, 857, 429]) {
+            assert!(count.abs_diff(expected) < 100, "{firsts:?}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::cast_precision_loss)]
+    fn a_zero_weight_counts_as_one() {
+        let word: toml::Table = toml::from_str(
+            r#"
+            word = "pona"
+            usage_category = "core"
+            deprecated = false
+            "#,
+        )
+        .unwrap();
+        let settings = GameSettings {
+            words: [("pona".to_string(), 0)].into(),
+            ..GameSettings::default()
+        };
+
+        assert_eq!(settings.weight(&word).unwrap(), 1);
+
+        // so it takes its turn first only as often as any word of weight 1
+        let mut rng = StdRng::seed_from_u64(7);
+        let firsts = (0..1000)
+            .filter(|_| {
+                let weights = vec![(settings.weight(&word).unwrap() as f64, true), (1.0, false)];
+                weighted_order(weights, &mut rng)[0]
+            })
+            .count();
+
+        assert!(firsts.abs_diff(500) < 60, "{firsts}");
+    }
+
+    #[test]
+    fn a_mistake_ends_the_combo() {
+        let mut game = game("mi moku", InputRules::default());
+        type_text(&mut game, "mi mxku");
+
+        assert_eq!(
+            spans(&game),
+            [("correct", "mi m"), ("wrong", "o"), ("correct", "ku")]
+        );
+        assert_eq!(game.combo, 2);
+        assert_eq!(game.best_combo, 4);
+    }
+
+    #[test]
+    fn strict_space_refuses_a_space_inside_a_word() {
+        let rules = InputRules {
+            strict_space: true,
+            ..InputRules::default()
+        };
+        let mut game = game("tomo pona", rules);
+        type_text(&mut game, "to ");
+
+        assert_eq!(game.input, "to");
+        assert_eq!(game.rejected, 1);
+        assert_eq!(spans(&game), [("correct", "to"), ("hidden", "mo pona")]);
+        assert!((game.accuracy() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn swapped_letters_are_two_mistakes() {
+        let mut game = game("tomo pona", InputRules::default());
+        type_text(&mut game, "tmoo");
+
+        assert_eq!(
+            spans(&game),
+            [
+                ("correct", "t"),
+                ("wrong", "om"),
+                ("correct", "o"),
+                ("hidden", " pona")
+            ]
+        );
+        assert!((game.accuracy() - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn forgiving_takes_swapped_letters_as_one_mistake() {
+        let rules = InputRules {
+            forgiving: true,
+            ..InputRules::default()
+        };
+        let mut game = game("tomo pona", rules);
+        type_text(&mut game, "tmoo");
+
+        assert_eq!(
+            spans(&game),
+            [
+                ("correct", "t"),
+                ("wrong", "o"),
+                ("correct", "mo"),
+                ("hidden", " pona")
+            ]
+        );
+        assert!((game.accuracy() - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn a_space_skips_the_rest_of_the_word() {
+        let mut game = game("tomo pona", InputRules::default());
+        type_text(&mut game, "to ");
+
+        assert_eq!(
+            spans(&game),
+            [
+                ("correct", "to"),
+                ("skipped", "mo"),
+                ("correct", " "),
+                ("hidden", "pona")
+            ]
+        );
+    }
+
+    #[test]
+    fn space_commits_counts_the_rest_of_the_word_as_wrong() {
+        let rules = InputRules {
+            space_commits: true,
+            ..InputRules::default()
+        };
+        let mut game = game("tomo pona", rules);
+
+        // nothing of the word is typed, so the space is ignored
+        type_text(&mut game, " to ");
+
+        assert_eq!(game.input, "to ");
+        assert_eq!(
+            spans(&game),
+            [
+                ("correct", "to"),
+                ("wrong", "mo"),
+                ("correct", " "),
+                ("hidden", "pona")
+            ]
+        );
+        assert!((game.accuracy() - 0.6).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn max_overflow_refuses_letters_past_it() {
+        let rules = InputRules {
+            max_overflow: 2,
+            ..InputRules::default()
+        };
+        let mut game = game("mi sina", rules);
+        type_text(&mut game, "mixxxx");
+
+        assert_eq!(game.input, "mixx");
+        assert_eq!(game.rejected, 2);
+        assert_eq!(
+            spans(&game),
+            [("correct", "mi"), ("overflow", "xx"), ("hidden", " sina")]
+        );
+        assert!((game.accuracy() - 1.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn wpm_counts_from_the_first_key() {
+        let mut game = game("mi moku", InputRules::default());
+        type_text(&mut game, "mi moku");
+        assert!(game.is_finished());
+
+        // 7 right characters are 1.4 words, over six seconds
+        game.clock.set(Duration::from_secs(6));
+
+        assert_eq!(game.elapsed(), Duration::from_secs(6));
+        assert!((game.wpm() - 14.0).abs() < 1e-9);
+        assert!((game.accuracy() - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn pauses_are_not_counted() {
+        let mut game = game("mi moku", InputRules::default());
+        type_text(&mut game, "mi ");
+
+        game.pause();
+        game.clock.advance(Duration::from_secs(50));
+        game.resume();
+
+        type_text(&mut game, "moku");
+        game.clock.set(Duration::from_secs(56));
+
+        assert_eq!(game.elapsed(), Duration::from_secs(6));
+    }
+}
@@ -0,0 +1,117 @@
+use crate::hooks;
+use std::{
+    io::{Read, Write},
+    process::Stdio,
+    time::{Duration, Instant},
+};
+use tt::{
+    error::{Error, Result},
+    GameSettings,
+};
+
+/// a generator still running after this is killed, the game waits on it
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// how often a running generator is checked on
+const POLL: Duration = Duration::from_millis(10);
+
+/// what a generator is given on stdin, one json object
+#[derive(serde::Serialize)]
+struct Request<'a> {
+    /// words wanted, the settings' length or a custom test's
+    len: usize,
+    /// for a generator that wants to pick the same way twice
+    seed: u32,
+    settings: &'a GameSettings<usize>,
+}
+
+/// the text of a test from the generator command in the config, run by the shell
+///
+/// it is given a request as json on stdin and writes the words of the test to stdout,
+/// anything on stderr is shown when it exits with an error
+pub fn generate(command: &str, settings: &GameSettings<usize>, seed: u32) -> Result<String> {
+    let failed = |message: String| Error::Generator {
+        command: command.to_string(),
+        message,
+    };
+
+    let request = serde_json::to_string(&Request {
+        len: settings.test_len(),
+        seed,
+        settings,
+    })
+    .map_err(|error| Error::Output(error.to_string()))?;
+
+    let mut child = hooks::shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| failed(error.to_string()))?;
+
+    // written and read on threads so a generator that doesn't read the request, or writes
+    // more than a pipe holds before exiting, can't stop the clock below
+    let stdin = child.stdin.take();
+    std::thread::spawn(move || {
+        // a generator that doesn't read the request closes its stdin early, that's fine
+        if let Some(mut stdin) = stdin {
+            _ = stdin.write_all(request.as_bytes());
+        }
+    });
+    let stdout = read_all(child.stdout.take());
+    let stderr = read_all(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child
+            .try_wait()
+            .map_err(|error| failed(error.to_string()))?
+        {
+            break status;
+        }
+
+        if started.elapsed() >= TIMEOUT {
+            _ = child.kill();
+            _ = child.wait();
+
+            return Err(failed(format!(
+                "still running after {} seconds",
+                TIMEOUT.as_secs()
+            )));
+        }
+
+        std::thread::sleep(POLL);
+    };
+
+    if !status.success() {
+        return Err(failed(
+            String::from_utf8_lossy(&stderr.join().unwrap_or_default())
+                .trim()
+                .to_string(),
+        ));
+    }
+
+    let target = String::from_utf8_lossy(&stdout.join().unwrap_or_default())
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if target.is_empty() {
+        return Err(Error::NoWords);
+    }
+
+    Ok(target)
+}
+
+/// everything a pipe gives until it closes, read on a thread of its own
+fn read_all(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut data = Vec::new();
+
+        if let Some(mut pipe) = pipe {
+            _ = pipe.read_to_end(&mut data);
+        }
+
+        data
+    })
+}
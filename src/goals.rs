@@ -0,0 +1,167 @@
+use crate::{
+    date::SECS_PER_DAY,
+    history::Session,
+    locale::{Locale, Message},
+};
+
+/// how much practice a day or a week should have, 0 leaves a part out
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Goal {
+    pub minutes: f64,
+    pub tests: usize,
+    /// mean accuracy of the period's tests in percent
+    pub accuracy: f64,
+}
+
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Goals {
+    pub daily: Goal,
+    pub weekly: Goal,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Period {
+    Day,
+    /// from monday, in UTC like the dates in the history
+    Week,
+}
+
+impl Period {
+    pub const ALL: [Self; 2] = [Self::Day, Self::Week];
+
+    /// as it is recorded on the session that met the goal
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Day => "daily",
+            Self::Week => "weekly",
+        }
+    }
+
+    /// unix seconds when the period holding a time started
+    #[must_use]
+    pub const fn start(self, now: u64) -> u64 {
+        let day = now / SECS_PER_DAY;
+
+        match self {
+            Self::Day => day * SECS_PER_DAY,
+            // 1970-01-01 was a thursday
+            Self::Week => (day - (day + 3) % 7) * SECS_PER_DAY,
+        }
+    }
+}
+
+/// what was practiced so far in a period
+pub struct Progress {
+    pub minutes: f64,
+    pub tests: usize,
+    /// a fraction like in the history, 0 with no tests
+    pub accuracy: f64,
+}
+
+impl Progress {
+    /// the sessions at or after a time, unfinished ones only count for time
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn since(sessions: &[Session], start: u64) -> Self {
+        let period = sessions.iter().filter(|session| session.date >= start);
+        let finished: Vec<&Session> = period.clone().filter(|session| session.finished).collect();
+
+        Self {
+            minutes: period.map(|session| session.seconds).sum::<f64>() / 60.0,
+            tests: finished.len(),
+            accuracy: if finished.is_empty() {
+                0.0
+            } else {
+                finished.iter().map(|session| session.accuracy).sum::<f64>() / finished.len() as f64
+            },
+        }
+    }
+}
+
+impl Goal {
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.minutes > 0.0 || self.tests > 0 || self.accuracy > 0.0
+    }
+
+    /// each part that is set with how far along it is, from 0 to 1
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn parts(&self, progress: &Progress, locale: Locale) -> Vec<(String, f64)> {
+        let mut parts = Vec::new();
+
+        if self.minutes > 0.0 {
+            parts.push((
+                locale.fill(
+                    Message::MinutesOf,
+                    &[
+                        &format!("{:.0}", progress.minutes),
+                        &format!("{:.0}", self.minutes),
+                    ],
+                ),
+                progress.minutes / self.minutes,
+            ));
+        }
+
+        if self.tests > 0 {
+            parts.push((
+                locale.fill(Message::TestsOf, &[&progress.tests, &self.tests]),
+                progress.tests as f64 / self.tests as f64,
+            ));
+        }
+
+        if self.accuracy > 0.0 {
+            parts.push((
+                locale.fill(
+                    Message::AccuracyOf,
+                    &[&format!("{:.1}", progress.accuracy * 100.0), &self.accuracy],
+                ),
+                progress.accuracy * 100.0 / self.accuracy,
+            ));
+        }
+
+        parts
+            .into_iter()
+            .map(|(label, ratio)| (label, ratio.min(1.0)))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn met(&self, progress: &Progress) -> bool {
+        self.is_set()
+            && self
+                .parts(progress, Locale::default())
+                .iter()
+                .all(|(_, ratio)| *ratio >= 1.0)
+    }
+}
+
+impl Goals {
+    #[must_use]
+    pub const fn get(&self, period: Period) -> &Goal {
+        match period {
+            Period::Day => &self.daily,
+            Period::Week => &self.weekly,
+        }
+    }
+
+    /// the periods whose goal is met now and wasn't recorded as met earlier in the period
+    #[must_use]
+    pub fn completed(&self, sessions: &[Session], now: u64) -> Vec<Period> {
+        Period::ALL
+            .into_iter()
+            .filter(|period| {
+                let start = period.start(now);
+
+                self.get(*period).met(&Progress::since(sessions, start))
+                    && !sessions.iter().any(|session| {
+                        session.date >= start
+                            && session.goals_met.iter().any(|name| name == period.name())
+                    })
+            })
+            .collect()
+    }
+}
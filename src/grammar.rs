@@ -0,0 +1,134 @@
+use crate::locale::Message;
+
+/// words that mark how a sentence is put together rather than carrying meaning
+const PARTICLES: [&str; 7] = ["li", "e", "la", "pi", "en", "anu", "o"];
+
+/// subjects that take no li after them
+const BARE_SUBJECTS: [&str; 2] = ["mi", "sina"];
+
+/// an ungrammatical part of the text, as byte offsets
+pub struct Issue {
+    pub start: usize,
+    pub end: usize,
+    pub message: Message,
+}
+
+/// a word with where it is in the text
+struct Word<'a> {
+    text: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl Word<'_> {
+    fn is(&self, word: &str) -> bool {
+        self.text == word
+    }
+
+    fn is_particle(&self) -> bool {
+        PARTICLES.contains(&self.text)
+    }
+}
+
+/// the common mistakes in some toki pona: a missing li, li after mi or sina, pi that doesn't
+/// start a phrase of at least two words, and doubled particles
+#[must_use]
+pub fn check(text: &str) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for sentence in sentences(text) {
+        check_sentence(&sentence, &mut issues);
+    }
+
+    issues
+}
+
+fn check_sentence(words: &[Word], issues: &mut Vec<Issue>) {
+    let mut issue = |word: &Word, message| {
+        issues.push(Issue {
+            start: word.start,
+            end: word.end,
+            message,
+        });
+    };
+
+    for pair in words.windows(2) {
+        if pair[0].is_particle() && pair[0].text == pair[1].text {
+            issue(&pair[1], Message::SameParticleTwice);
+        }
+    }
+
+    // a la phrase only sets the context, the sentence itself comes after the last one
+    let clause = words
+        .iter()
+        .rposition(|word| word.is("la"))
+        .map_or(words, |la| &words[la + 1..]);
+
+    let Some(first) = clause.first() else {
+        return;
+    };
+
+    let has_li = clause.iter().any(|word| word.is("li"));
+    if BARE_SUBJECTS.contains(&first.text) {
+        if let Some(li) = clause.get(1).filter(|word| word.is("li")) {
+            issue(li, Message::NoLiAfterMiSina);
+        }
+    } else if !has_li && !first.is("o") {
+        // a verb with an object needs li unless the subject is mi or sina or it is a command
+        if let Some(e) = clause.iter().skip(1).find(|word| word.is("e")) {
+            issue(e, Message::MissingLi);
+        }
+    }
+
+    if first.is("e") || first.is("li") {
+        issue(first, Message::NothingBeforeParticle);
+    }
+
+    for (index, word) in words.iter().enumerate() {
+        if !word.is("pi") {
+            continue;
+        }
+
+        let after_particle = index == 0 || words[index - 1].is_particle();
+        let phrase = words[index + 1..]
+            .iter()
+            .take_while(|word| !word.is_particle())
+            .count();
+
+        if after_particle {
+            issue(word, Message::PiNeedsWordBefore);
+        } else if phrase < 2 {
+            issue(word, Message::PiNeedsTwoWords);
+        }
+    }
+}
+
+/// the words of each sentence, sentences end at . ! ? : and line breaks
+fn sentences(text: &str) -> Vec<Vec<Word<'_>>> {
+    let mut sentences = vec![Vec::new()];
+    let mut start = None;
+
+    for (index, c) in text.char_indices().chain([(text.len(), '\n')]) {
+        if c.is_alphanumeric() {
+            start.get_or_insert(index);
+            continue;
+        }
+
+        if let Some(start) = start.take() {
+            if let Some(sentence) = sentences.last_mut() {
+                sentence.push(Word {
+                    text: &text[start..index],
+                    start,
+                    end: index,
+                });
+            }
+        }
+
+        if matches!(c, '.' | '!' | '?' | ':' | '\n') {
+            sentences.push(Vec::new());
+        }
+    }
+
+    sentences.retain(|sentence| !sentence.is_empty());
+    sentences
+}
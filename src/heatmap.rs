@@ -0,0 +1,156 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType::Rounded, Paragraph},
+    Frame,
+};
+use std::collections::HashMap;
+use tt::{
+    history::{Session, MAX_GAP_MS},
+    layout::KeyboardLayout,
+    locale::{Locale, Message},
+};
+
+/// error rate drawn fully red
+const MAX_ERROR_RATE: f64 = 0.2;
+
+/// height of one keyboard, three rows and the border
+pub const HEIGHT: u16 = 3 + 2;
+
+#[derive(Default)]
+pub struct KeyStats {
+    count: usize,
+    errors: usize,
+    /// time since the previous stroke, over the strokes that were not after a pause
+    total_ms: u64,
+    timed: usize,
+}
+
+impl KeyStats {
+    #[allow(clippy::cast_precision_loss)]
+    fn error_rate(&self) -> f64 {
+        self.errors as f64 / self.count as f64
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn latency(&self) -> Option<f64> {
+        (self.timed > 0).then(|| self.total_ms as f64 / self.timed as f64)
+    }
+}
+
+/// strokes per typed key over some sessions
+pub fn key_stats<'a>(sessions: impl IntoIterator<Item = &'a Session>) -> HashMap<char, KeyStats> {
+    let mut stats: HashMap<char, KeyStats> = HashMap::new();
+
+    for session in sessions {
+        let mut previous = None;
+
+        for (c, correct, ms, _) in &session.strokes {
+            let entry = stats.entry(c.to_ascii_lowercase()).or_default();
+            entry.count += 1;
+            entry.errors += usize::from(!correct);
+
+            if let Some(gap) = previous
+                .map(|previous| ms.saturating_sub(previous))
+                .filter(|gap| *gap <= MAX_GAP_MS)
+            {
+                entry.total_ms += gap;
+                entry.timed += 1;
+            }
+
+            previous = Some(*ms);
+        }
+    }
+
+    stats
+}
+
+/// green for 0 through red for 1
+fn gradient(value: f64) -> Color {
+    let value = value.clamp(0.0, 1.0);
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Color::Rgb((255.0 * value) as u8, (255.0 * (1.0 - value)) as u8, 0)
+}
+
+/// one keyboard with each key colored by color, keys without strokes are gray
+fn keyboard<F>(
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    layout: &KeyboardLayout,
+    stats: &HashMap<char, KeyStats>,
+    color: F,
+) where
+    F: Fn(&KeyStats) -> Option<f64>,
+{
+    let lines: Vec<Line> = layout
+        .rows()
+        .iter()
+        .enumerate()
+        .map(|(indent, row)| {
+            let mut spans = vec![Span::raw(" ".repeat(indent * 2))];
+
+            for c in row.chars() {
+                let style = stats.get(&c).and_then(&color).map_or_else(
+                    || Style::new().on_dark_gray(),
+                    |value| Style::new().black().bg(gradient(value)),
+                );
+
+                spans.push(Span::styled(format!(" {c} "), style));
+                spans.push(Span::raw(" "));
+            }
+
+            Line::from(spans)
+        })
+        .collect();
+
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().border_type(Rounded).title(title)),
+        area,
+    );
+}
+
+/// keyboards colored by error rate and by how long each key takes
+pub fn render(
+    frame: &mut Frame,
+    area: Rect,
+    layout: &KeyboardLayout,
+    stats: &HashMap<char, KeyStats>,
+    locale: Locale,
+) {
+    let [errors, speed] =
+        Layout::vertical([Constraint::Length(HEIGHT), Constraint::Length(HEIGHT)]).areas(area);
+
+    keyboard(
+        frame,
+        errors,
+        &locale.title(Message::Errors),
+        layout,
+        stats,
+        |key| Some(key.error_rate() / MAX_ERROR_RATE),
+    );
+
+    // slowest key red, fastest green
+    let latencies = stats.values().filter_map(KeyStats::latency);
+    let fastest = latencies.clone().fold(f64::INFINITY, f64::min);
+    let slowest = latencies.fold(0.0, f64::max);
+
+    keyboard(
+        frame,
+        speed,
+        &locale.title(Message::Speed),
+        layout,
+        stats,
+        |key| {
+            key.latency().map(|latency| {
+                if slowest > fastest {
+                    (latency - fastest) / (slowest - fastest)
+                } else {
+                    0.0
+                }
+            })
+        },
+    );
+}
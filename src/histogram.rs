@@ -0,0 +1,73 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Bar, BarChart, BarGroup, Block, BorderType::Rounded},
+    Frame,
+};
+use std::time::Duration;
+use tt::locale::{Locale, Message};
+
+/// width of each bucket in milliseconds
+const BUCKET_MS: u128 = 100;
+
+/// buckets before the last one, which holds everything slower
+const BUCKETS: usize = 10;
+
+/// buckets from here on are slow enough to hurt the average
+const SLOW_BUCKET: usize = 5;
+
+/// height of the chart with its border
+pub const HEIGHT: u16 = 12;
+
+/// width of every bar and the gaps between them with the border
+#[allow(clippy::cast_possible_truncation)]
+pub const WIDTH: u16 = (BUCKETS as u16 + 1) * 5 + 2;
+
+/// a bar chart of the time between keystrokes with the slow tail colored
+pub fn render(frame: &mut Frame, area: Rect, intervals: &[Duration], locale: Locale) {
+    let mut counts = [0_u64; BUCKETS + 1];
+
+    for interval in intervals {
+        let bucket = usize::try_from(interval.as_millis() / BUCKET_MS).unwrap_or(BUCKETS);
+        counts[bucket.min(BUCKETS)] += 1;
+    }
+
+    let slow = counts[BUCKETS];
+
+    let bars: Vec<Bar> = counts
+        .iter()
+        .enumerate()
+        .map(|(bucket, count)| {
+            let label = if bucket == BUCKETS {
+                "1s+".to_string()
+            } else {
+                format!("{}", bucket as u128 * BUCKET_MS)
+            };
+
+            let color = match bucket {
+                BUCKETS => Color::Red,
+                b if b >= SLOW_BUCKET => Color::Yellow,
+                _ => Color::Green,
+            };
+
+            Bar::default()
+                .value(*count)
+                .label(label.into())
+                .style(Style::new().fg(color))
+        })
+        .collect();
+
+    frame.render_widget(
+        BarChart::default()
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(4)
+            .bar_gap(1)
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(locale.title(Message::MsBetweenKeys))
+                    .title_bottom(format!(" {} ", locale.fill(Message::SlowPauses, &[&slow]))),
+            ),
+        area,
+    );
+}
@@ -0,0 +1,385 @@
+use crate::{
+    date::{self, SECS_PER_DAY},
+    error::{Error, Result},
+    migrate, storage,
+    summary::{self, Summary},
+};
+use std::path::Path;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// a typed character, whether it was right, the milliseconds since the first key
+/// and the index of the target word it was typed in
+pub type Stroke = (char, bool, u64, usize);
+
+/// gaps between strokes longer than this are pauses rather than typing
+pub const MAX_GAP_MS: u64 = 2000;
+
+/// the result of one test
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Session {
+    /// random, so it gives nothing about the session away, and the same for it in every copy
+    /// of the history and every export, kept when the privacy settings take out what was typed
+    pub id: String,
+    /// unix time in seconds when the test ended
+    pub date: u64,
+    /// words in the target, kept when the target is taken out
+    pub words: usize,
+    pub target: String,
+    pub input: String,
+    pub wpm: f64,
+    pub accuracy: f64,
+    pub seconds: f64,
+    pub best_combo: usize,
+    /// false when the test was abandoned and saved part way through
+    pub finished: bool,
+    /// missing from sessions saved before strokes were recorded
+    #[serde(default)]
+    pub strokes: Vec<Stroke>,
+    /// every key pressed including corrections, 0 for sessions saved before it was counted
+    #[serde(default)]
+    pub keystrokes: usize,
+    /// whether the test met the pass criteria, None when there were none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passed: Option<bool>,
+    /// what kind of test it was, like words or lesson, empty for sessions saved before it was kept
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub mode: String,
+    /// free words to filter by later, like "morning" or "new-keyboard"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+    /// the goals this test completed, "daily" or "weekly"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals_met: Vec<String>,
+}
+
+/// an id for a new session, see `Session::id`
+#[must_use]
+pub fn new_id() -> String {
+    format!("{:032x}", rand::random::<u128>())
+}
+
+impl Session {
+    /// made from when the session ended and what was typed, so two copies of a history from
+    /// before sessions had ids, which got different random ones, still agree on it,
+    /// never exported since the date can be found from it
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        // fnv-1a, which unlike the std hasher is the same on every platform and release
+        self.date
+            .to_le_bytes()
+            .iter()
+            .chain(self.target.as_bytes())
+            .chain(self.input.as_bytes())
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+            })
+    }
+
+    /// keystrokes per character of the final input, None if it wasn't counted
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn kspc(&self) -> Option<f64> {
+        let chars = self.input.graphemes(true).count();
+        (self.keystrokes > 0 && chars > 0).then(|| self.keystrokes as f64 / chars as f64)
+    }
+}
+
+/// how much of each test the history keeps
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Logging {
+    /// every keystroke with its timing, for the charts and the keystroke export
+    #[default]
+    Full,
+    /// the text and results but not the keystrokes
+    NoKeystrokes,
+    /// only the results like speed and accuracy, not what was typed
+    Aggregates,
+}
+
+/// what the history may keep, the `[privacy]` table of the config
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Privacy {
+    pub logging: Logging,
+    /// sessions older than this many days are deleted when the history is saved,
+    /// 0 keeps them all
+    pub keep_days: u64,
+}
+
+impl Privacy {
+    #[must_use]
+    pub const fn keeps_keystrokes(self) -> bool {
+        matches!(self.logging, Logging::Full)
+    }
+
+    /// take out of the sessions what isn't allowed, sessions saved before a setting was
+    /// changed included, true if sessions were too old to keep
+    fn apply(self, sessions: &mut Vec<Session>) -> bool {
+        let count = sessions.len();
+
+        if self.keep_days > 0 {
+            let oldest = date::now().saturating_sub(self.keep_days.saturating_mul(SECS_PER_DAY));
+
+            sessions.retain(|session| session.date >= oldest);
+        }
+
+        let expired = sessions.len() < count;
+
+        for session in sessions {
+            if self.logging != Logging::Full {
+                session.strokes = Vec::new();
+            }
+
+            if self.logging == Logging::Aggregates {
+                session.target = String::new();
+                session.input = String::new();
+            }
+        }
+
+        expired
+    }
+}
+
+#[derive(Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct History {
+    /// the format it was saved in, see `migrate::HISTORY`
+    pub version: i64,
+    /// the privacy settings it was saved with, the backups are gone over again when they change
+    pub privacy: Privacy,
+    pub sessions: Vec<Session>,
+}
+
+impl History {
+    /// read history from a file, a missing file gives an empty history
+    ///
+    /// # Errors
+    /// when the file can't be read or parsed, or is from a newer version
+    pub fn load(path: &Path) -> Result<Self> {
+        let Some(mut table) = storage::load::<toml::Table>(path)? else {
+            return Ok(Self::default());
+        };
+
+        migrate::history(path, &mut table)?;
+
+        toml::Value::Table(table)
+            .try_into()
+            .map_err(|source| Error::Parse {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// write the history with what the privacy settings don't allow taken out first,
+    /// and its summary next to it
+    ///
+    /// # Errors
+    /// when a file or backup can't be written
+    pub fn save(&mut self, path: &Path, privacy: Privacy) -> Result<()> {
+        let expired = privacy.apply(&mut self.sessions);
+        let changed = self.privacy != privacy;
+        self.version = migrate::HISTORY;
+        self.privacy = privacy;
+        storage::save_with_backups(path, self)?;
+
+        // the backups are old copies of the file, which was saved under the old settings
+        if expired || changed {
+            Self::filter_backups(path, privacy)?;
+        }
+
+        storage::save(
+            &path.with_file_name(summary::FILE),
+            &Summary::new(&self.sessions, date::now()),
+        )
+    }
+
+    /// take out of each backup what the privacy settings don't allow
+    fn filter_backups(path: &Path, privacy: Privacy) -> Result<()> {
+        for number in 1..=storage::BACKUPS {
+            let backup = storage::backup(path, number);
+
+            // loading a missing one gives an empty history, which would be saved as a backup
+            if !backup.exists() {
+                continue;
+            }
+
+            let mut history = Self::load(&backup)?;
+            privacy.apply(&mut history.sessions);
+            history.version = migrate::HISTORY;
+            history.privacy = privacy;
+            storage::save(&backup, &history)?;
+        }
+
+        Ok(())
+    }
+
+    /// the last test was faster than every earlier one of the same mode and word count
+    #[must_use]
+    pub fn is_personal_best(&self) -> bool {
+        self.percentile().is_some_and(|share| share >= 1.0)
+    }
+
+    /// the share of earlier finished tests of the same mode and word count that were slower
+    /// than the last one, None when there is nothing to compare to
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percentile(&self) -> Option<f64> {
+        let (last, earlier) = self.sessions.split_last()?;
+
+        let peers: Vec<&Session> = earlier
+            .iter()
+            .filter(|session| {
+                session.finished && session.mode == last.mode && session.words == last.words
+            })
+            .collect();
+
+        if peers.is_empty() {
+            return None;
+        }
+
+        let slower = peers
+            .iter()
+            .filter(|session| session.wpm < last.wpm)
+            .count();
+
+        Some(slower as f64 / peers.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(days_ago: u64, wpm: f64) -> Session {
+        Session {
+            id: new_id(),
+            date: date::now() - days_ago * SECS_PER_DAY,
+            words: 2,
+            target: "mi moku".to_string(),
+            input: "mi moku".to_string(),
+            wpm,
+            accuracy: 1.0,
+            seconds: 3.0,
+            best_combo: 7,
+            finished: true,
+            strokes: vec![('m', true, 0, 0), ('i', true, 100, 0)],
+            keystrokes: 7,
+            passed: None,
+            mode: "words".to_string(),
+            tags: Vec::new(),
+            note: String::new(),
+            goals_met: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn sessions_past_keep_days_are_pruned() {
+        let mut sessions = vec![session(40, 10.0), session(29, 20.0), session(0, 30.0)];
+        let privacy = Privacy {
+            keep_days: 30,
+            ..Privacy::default()
+        };
+
+        assert!(privacy.apply(&mut sessions));
+        assert_eq!(
+            sessions
+                .iter()
+                .map(|session| session.wpm)
+                .collect::<Vec<_>>(),
+            [20.0, 30.0]
+        );
+
+        // nothing left to take out, and 0 keeps everything
+        assert!(!privacy.apply(&mut sessions));
+        let mut sessions = vec![session(4000, 10.0)];
+        assert!(!Privacy::default().apply(&mut sessions));
+        assert_eq!(sessions.len(), 1);
+    }
+
+    #[test]
+    fn logging_takes_out_what_it_doesnt_keep() {
+        for (logging, strokes, text) in [
+            (Logging::Full, 2, "mi moku"),
+            (Logging::NoKeystrokes, 0, "mi moku"),
+            (Logging::Aggregates, 0, ""),
+        ] {
+            let mut sessions = vec![session(0, 10.0)];
+            let privacy = Privacy {
+                logging,
+                keep_days: 0,
+            };
+            privacy.apply(&mut sessions);
+
+            let session = &sessions[0];
+            assert_eq!(session.strokes.len(), strokes);
+            assert_eq!(
+                (session.target.as_str(), session.input.as_str()),
+                (text, text)
+            );
+            // what the results and ids need stays
+            assert_eq!(
+                (session.words, session.keystrokes, session.wpm),
+                (2, 7, 10.0)
+            );
+            assert_eq!(session.id.len(), 32);
+        }
+    }
+
+    #[test]
+    fn percentile_of_the_last_test() {
+        let history = |results: &[(f64, &str, bool)]| History {
+            sessions: results
+                .iter()
+                .map(|(wpm, mode, finished)| Session {
+                    mode: (*mode).to_string(),
+                    finished: *finished,
+                    ..session(0, *wpm)
+                })
+                .collect(),
+            ..History::default()
+        };
+
+        for (results, percentile) in [
+            (&[][..], None),
+            (&[(30.0, "words", true)], None),
+            (&[(20.0, "words", true), (30.0, "words", true)], Some(1.0)),
+            (&[(40.0, "words", true), (30.0, "words", true)], Some(0.0)),
+            (
+                &[(0.0, "words", true), (f64::MAX, "words", true)],
+                Some(1.0),
+            ),
+            (
+                &[(f64::MAX, "words", true), (0.0, "words", true)],
+                Some(0.0),
+            ),
+            // a tie isn't slower
+            (&[(30.0, "words", true), (30.0, "words", true)], Some(0.0)),
+            (
+                &[
+                    (10.0, "words", true),
+                    (20.0, "words", true),
+                    (40.0, "words", true),
+                    (50.0, "words", true),
+                    (30.0, "words", true),
+                ],
+                Some(0.5),
+            ),
+            // only finished tests of the same mode count
+            (&[(20.0, "numbers", true), (30.0, "words", true)], None),
+            (&[(20.0, "words", false), (30.0, "words", true)], None),
+        ] {
+            let history = history(results);
+
+            assert_eq!(history.percentile(), percentile, "{results:?}");
+            assert_eq!(history.is_personal_best(), percentile == Some(1.0));
+        }
+
+        // nor do tests of another length
+        let mut history = history(&[(20.0, "words", true), (30.0, "words", true)]);
+        history.sessions[0].words = 3;
+        assert_eq!(history.percentile(), None);
+    }
+}
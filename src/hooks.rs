@@ -0,0 +1,141 @@
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+use tt::{
+    error::{Error, Result},
+    history::Session,
+};
+
+/// commands run when something happens, the `[hooks]` table of the config,
+/// each is run by the shell and an empty one is left out
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct Hooks {
+    /// after the history is saved, with the data directory in `TT_DATA_DIR` and as `$1`,
+    /// for syncing it with rclone, git or the like
+    pub after_save: String,
+    /// after each finished test, with its results as json on stdin and in `TT_` variables
+    /// like `TT_WPM`, for logging them somewhere else
+    pub after_test: String,
+}
+
+/// the results given to the after test hook
+#[derive(serde::Serialize)]
+struct TestResult<'a> {
+    /// unix seconds when the test ended
+    date: u64,
+    mode: &'a str,
+    words: usize,
+    wpm: f64,
+    accuracy: f64,
+    seconds: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    passed: Option<bool>,
+}
+
+impl<'a> TestResult<'a> {
+    fn new(session: &'a Session) -> Self {
+        Self {
+            date: session.date,
+            mode: &session.mode,
+            words: session.words,
+            wpm: session.wpm,
+            accuracy: session.accuracy,
+            seconds: session.seconds,
+            passed: session.passed,
+        }
+    }
+
+    fn vars(&self) -> [(&'static str, String); 6] {
+        [
+            ("TT_DATE", self.date.to_string()),
+            ("TT_MODE", self.mode.to_string()),
+            ("TT_WORDS", self.words.to_string()),
+            ("TT_WPM", self.wpm.to_string()),
+            ("TT_ACCURACY", self.accuracy.to_string()),
+            ("TT_SECONDS", self.seconds.to_string()),
+        ]
+    }
+}
+
+impl Hooks {
+    /// start the after save hook for the history at a path
+    pub fn after_save(&self, history_path: &Path) -> Result<()> {
+        let Some(dir) = history_path.parent() else {
+            return Ok(());
+        };
+
+        run(&self.after_save, None, |command| {
+            command.env("TT_DATA_DIR", dir).arg(dir);
+        })
+    }
+
+    /// start the after test hook for a session as it was saved
+    pub fn after_test(&self, session: &Session) -> Result<()> {
+        let result = TestResult::new(session);
+        let json =
+            serde_json::to_string(&result).map_err(|error| Error::Output(error.to_string()))?;
+
+        run(&self.after_test, Some(json), |command| {
+            command.envs(result.vars());
+
+            if let Some(passed) = result.passed {
+                command.env("TT_PASSED", passed.to_string());
+            }
+        })
+    }
+}
+
+/// start a command in the shell without waiting for it, its output would draw over the game
+pub fn run(hook: &str, input: Option<String>, setup: impl FnOnce(&mut Command)) -> Result<()> {
+    if hook.trim().is_empty() {
+        return Ok(());
+    }
+
+    let mut command = shell(hook);
+    command
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    setup(&mut command);
+
+    let mut child = command.spawn().map_err(|source| Error::Hook {
+        command: hook.to_string(),
+        source,
+    })?;
+
+    let stdin = child.stdin.take();
+
+    // waited on so it doesn't linger as a zombie until the game quits, a hook that doesn't
+    // read its input only stops the write
+    std::thread::spawn(move || {
+        if let (Some(mut stdin), Some(input)) = (stdin, input) {
+            _ = stdin.write_all(input.as_bytes());
+        }
+
+        child.wait()
+    });
+
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn shell(hook: &str) -> Command {
+    let mut command = Command::new("cmd");
+    command.arg("/C").arg(hook);
+    command
+}
+
+/// `sh -c` names the script by the argument after it, the ones after that are `$1` on
+#[cfg(not(windows))]
+pub fn shell(hook: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(hook).arg("tt");
+    command
+}
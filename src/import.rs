@@ -0,0 +1,124 @@
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+use tt::{
+    error::{Error, Result},
+    history::{History, Privacy, Session},
+};
+
+/// add the sessions of other history files to a profile's, like one from another machine,
+/// sessions it already has are left out so importing twice changes nothing
+pub fn run(files: &[PathBuf], history_path: &Path, privacy: Privacy) -> Result<()> {
+    let mut history = History::load(history_path)?;
+    let mut imported = 0;
+    let mut skipped = 0;
+
+    for path in files {
+        // a missing history loads as empty, which would hide a mistyped path
+        if !path.exists() {
+            return Err(Error::Read {
+                path: path.clone(),
+                source: std::io::ErrorKind::NotFound.into(),
+            });
+        }
+
+        let sessions = History::load(path)?.sessions;
+        let count = sessions.len();
+        let added = merge(&mut history, sessions);
+        imported += added;
+        skipped += count - added;
+    }
+
+    // the stats read the sessions in the order they were typed
+    history.sessions.sort_by_key(|session| session.date);
+    history.save(history_path, privacy)?;
+
+    println!("imported {imported} sessions, {skipped} were already there");
+
+    Ok(())
+}
+
+/// add the sessions the history doesn't have yet, the number added
+fn merge(history: &mut History, sessions: Vec<Session>) -> usize {
+    let mut ids: HashSet<String> = history
+        .sessions
+        .iter()
+        .map(|session| session.id.clone())
+        .collect();
+    // copies of a history from before sessions had ids gave them different ones
+    let mut contents: HashSet<u64> = history.sessions.iter().map(Session::content_hash).collect();
+    let count = history.sessions.len();
+
+    for session in sessions {
+        let new_id = ids.insert(session.id.clone());
+        let new_content = contents.insert(session.content_hash());
+
+        if new_id && new_content {
+            history.sessions.push(session);
+        }
+    }
+
+    history.sessions.len() - count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sessions(rows: &[(&str, u64, &str)]) -> Vec<Session> {
+        rows.iter()
+            .map(|(id, date, input)| {
+                toml::from_str(&format!(
+                    r#"
+                    id = "{id}"
+                    date = {date}
+                    words = 1
+                    target = "moku"
+                    input = "{input}"
+                    wpm = 30.0
+                    accuracy = 1.0
+                    seconds = 1.0
+                    best_combo = 4
+                    finished = true
+                    "#
+                ))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn sessions_already_there_by_id_or_content_are_left_out() {
+        let mut history = History {
+            sessions: sessions(&[("a", 100, "moku"), ("b", 200, "moku")]),
+            ..History::default()
+        };
+
+        let added = merge(
+            &mut history,
+            sessions(&[
+                // the same session
+                ("a", 100, "moku"),
+                // the same id, typed differently, so an edited copy
+                ("b", 300, "moku"),
+                // a new id for what was typed at the same time, from before ids
+                ("c", 100, "moku"),
+                ("d", 100, "mokx"),
+                // twice in what is imported
+                ("e", 400, "moku"),
+                ("e", 400, "moku"),
+            ]),
+        );
+
+        assert_eq!(added, 2);
+        assert_eq!(
+            history
+                .sessions
+                .iter()
+                .map(|session| session.id.as_str())
+                .collect::<Vec<_>>(),
+            ["a", "b", "d", "e"]
+        );
+    }
+}
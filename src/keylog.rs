@@ -0,0 +1,102 @@
+use crate::output::{self, Format, Record};
+use std::path::PathBuf;
+use tt::{
+    error::{Error, Result},
+    history::History,
+};
+
+/// one key of one session, the rows of `tt keylog`
+///
+/// sessions saved before strokes were recorded have no rows, and corrections aren't
+/// strokes, so a backspace shows only as the gap before the key typed after it
+#[derive(serde::Serialize)]
+struct Keystroke {
+    /// the same for every key of a session and for the session in every export,
+    /// random so it says nothing about when the session was, see `Session::id`
+    session: String,
+    /// counts from 0 in each session
+    index: usize,
+    /// milliseconds since the session's first key
+    ms: u64,
+    /// milliseconds since the key before, 0 for the first
+    gap_ms: u64,
+    key: char,
+    /// whether it matched the target where it was typed
+    correct: bool,
+    /// counts the target's words from 0
+    word: usize,
+    /// the target word the key was typed in, empty past the end of the target
+    target: String,
+    /// what the word ended up as, empty when the session ended before it
+    typed: String,
+}
+
+impl Record for Keystroke {
+    const FIELDS: &'static [&'static str] = &[
+        "session", "index", "ms", "gap_ms", "key", "correct", "word", "target", "typed",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.session.clone(),
+            self.index.to_string(),
+            self.ms.to_string(),
+            self.gap_ms.to_string(),
+            // a tab or newline would break the row
+            self.key.escape_default().to_string(),
+            self.correct.to_string(),
+            self.word.to_string(),
+            self.target.clone(),
+            self.typed.clone(),
+        ]
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Keylog {
+    keys: Vec<Keystroke>,
+}
+
+/// print every stroke of the history files with its timing and the word it was typed in
+pub fn run(files: &[PathBuf], format: Format) -> Result<()> {
+    let mut keys = Vec::new();
+
+    for path in files {
+        // a missing history loads as empty, which would hide a mistyped path
+        if !path.exists() {
+            return Err(Error::Read {
+                path: path.clone(),
+                source: std::io::ErrorKind::NotFound.into(),
+            });
+        }
+
+        for session in History::load(path)?.sessions {
+            let id = &session.id;
+            let targets: Vec<&str> = session.target.split(' ').collect();
+            let typed: Vec<&str> = session.input.split(' ').collect();
+            let mut last = None;
+
+            for (index, &(key, correct, ms, word)) in session.strokes.iter().enumerate() {
+                keys.push(Keystroke {
+                    session: id.clone(),
+                    index,
+                    ms,
+                    gap_ms: last.map_or(0, |last| ms.saturating_sub(last)),
+                    key,
+                    correct,
+                    word,
+                    target: targets.get(word).copied().unwrap_or_default().to_string(),
+                    typed: typed.get(word).copied().unwrap_or_default().to_string(),
+                });
+                last = Some(ms);
+            }
+        }
+    }
+
+    match format {
+        Format::Text | Format::Tsv => output::print_tsv(&keys),
+        Format::Json | Format::Toml => format.print_document(&Keylog { keys })?,
+    }
+
+    Ok(())
+}
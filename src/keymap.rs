@@ -0,0 +1,394 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::{collections::HashMap, str::FromStr};
+
+/// something a key can be bound to
+#[derive(Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    Quit,
+    Help,
+    Pause,
+    Begin,
+    TogglePanels,
+    ToggleGrammar,
+    ToggleGlosses,
+    SkipWord,
+    DeleteWord,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Search,
+    Select,
+    Back,
+    Restart,
+    Repeat,
+    PracticeMissed,
+    Review,
+    Annotate,
+    NextTab,
+    FilterTag,
+    FilterMode,
+    FilterDate,
+    WordOfTheDay,
+    Pin,
+    Sort,
+}
+
+impl Action {
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::Quit => "quit",
+            Self::Help => "show this help",
+            Self::Pause => "pause",
+            Self::Begin => "start the previewed test",
+            Self::TogglePanels => "show or hide the word panels",
+            Self::ToggleGrammar => "turn the grammar check on or off",
+            Self::ToggleGlosses => "show or hide english under the words",
+            Self::SkipWord => "skip the current word",
+            Self::DeleteWord => "delete the current word",
+            Self::Up => "move up",
+            Self::Down => "move down",
+            Self::PageUp => "page up",
+            Self::PageDown => "page down",
+            Self::Top => "go to the top",
+            Self::Bottom => "go to the bottom",
+            Self::Search => "search",
+            Self::Select => "select",
+            Self::Back => "back",
+            Self::Restart => "new test",
+            Self::Repeat => "repeat the same words",
+            Self::PracticeMissed => "practice missed words",
+            Self::Review => "go over every word typed",
+            Self::Annotate => "add tags and a note to the test",
+            Self::NextTab => "next tab",
+            Self::FilterTag => "count only one tag",
+            Self::FilterMode => "count only one mode",
+            Self::FilterDate => "count only recent tests",
+            Self::WordOfTheDay => "drill the word of the day",
+            Self::Pin => "pin the word as often, rare or never",
+            Self::Sort => "change the sort order",
+        }
+    }
+}
+
+/// where a binding applies, `Global` bindings apply everywhere
+#[derive(Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Context {
+    Global,
+    Game,
+    Menu,
+    Results,
+}
+
+impl Context {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Global => "everywhere",
+            Self::Game => "typing",
+            Self::Menu => "menus",
+            Self::Results => "results",
+        }
+    }
+}
+
+/// a key code with its modifiers, written like "ctrl+w" in the config
+#[derive(Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    #[must_use]
+    pub const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    #[must_use]
+    pub const fn plain(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::NONE)
+    }
+
+    #[must_use]
+    pub const fn ctrl(code: KeyCode) -> Self {
+        Self::new(code, KeyModifiers::CONTROL)
+    }
+}
+
+impl From<&KeyEvent> for Key {
+    fn from(key_event: &KeyEvent) -> Self {
+        let mut modifiers = key_event.modifiers;
+
+        // shift is already part of the character
+        if let KeyCode::Char(_) = key_event.code {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        Self::new(key_event.code, modifiers)
+    }
+}
+
+impl FromStr for Key {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+
+        // a trailing "+" is the plus key rather than a separator
+        while let Some((modifier, tail)) = rest.split_once('+').filter(|(_, tail)| !tail.is_empty())
+        {
+            modifiers |= match modifier {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(format!("unknown modifier {modifier:?} in key {s:?}")),
+            };
+            rest = tail;
+        }
+
+        let code = match rest {
+            "space" => KeyCode::Char(' '),
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "delete" => KeyCode::Delete,
+            f if f.strip_prefix('f').is_some_and(|n| n.parse::<u8>().is_ok()) => {
+                KeyCode::F(f[1..].parse().unwrap_or_default())
+            }
+            c if c.chars().count() == 1 => KeyCode::Char(c.chars().next().unwrap_or_default()),
+            _ => return Err(format!("unknown key {s:?}")),
+        };
+
+        Ok(Self::new(code, modifiers))
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl std::fmt::Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "ctrl+"),
+            (KeyModifiers::ALT, "alt+"),
+            (KeyModifiers::SHIFT, "shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                f.write_str(name)?;
+            }
+        }
+
+        match self.code {
+            KeyCode::Char(' ') => f.write_str("space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(n) => write!(f, "f{n}"),
+            KeyCode::Esc => f.write_str("esc"),
+            KeyCode::Enter => f.write_str("enter"),
+            KeyCode::Tab => f.write_str("tab"),
+            KeyCode::BackTab => f.write_str("shift+tab"),
+            KeyCode::Backspace => f.write_str("backspace"),
+            KeyCode::Up => f.write_str("up"),
+            KeyCode::Down => f.write_str("down"),
+            KeyCode::Left => f.write_str("left"),
+            KeyCode::Right => f.write_str("right"),
+            code => f.write_str(&format!("{code:?}").to_lowercase()),
+        }
+    }
+}
+
+pub struct Binding {
+    pub context: Context,
+    pub key: Key,
+    pub action: Action,
+}
+
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        use Action as A;
+        use Context as C;
+        use KeyCode as K;
+
+        let bindings = [
+            (C::Global, Key::ctrl(K::Char('c')), A::Quit),
+            (C::Global, Key::ctrl(K::Char('d')), A::Quit),
+            (C::Global, Key::plain(K::F(1)), A::Help),
+            (C::Game, Key::plain(K::Esc), A::Pause),
+            (C::Game, Key::plain(K::Enter), A::Begin),
+            (C::Game, Key::ctrl(K::Char('r')), A::Restart),
+            (C::Game, Key::plain(K::F(2)), A::TogglePanels),
+            (C::Game, Key::plain(K::F(3)), A::ToggleGrammar),
+            (C::Game, Key::plain(K::F(4)), A::ToggleGlosses),
+            (C::Game, Key::plain(K::Tab), A::SkipWord),
+            (C::Game, Key::ctrl(K::Char('w')), A::DeleteWord),
+            // most terminals send ctrl+backspace as ctrl+h
+            (C::Game, Key::ctrl(K::Char('h')), A::DeleteWord),
+            (C::Game, Key::ctrl(K::Backspace), A::DeleteWord),
+            (
+                C::Game,
+                Key::new(K::Backspace, KeyModifiers::ALT),
+                A::DeleteWord,
+            ),
+            (C::Menu, Key::plain(K::Up), A::Up),
+            (C::Menu, Key::plain(K::Char('k')), A::Up),
+            (C::Menu, Key::plain(K::Down), A::Down),
+            (C::Menu, Key::plain(K::Char('j')), A::Down),
+            (C::Menu, Key::plain(K::PageUp), A::PageUp),
+            (C::Menu, Key::ctrl(K::Char('u')), A::PageUp),
+            (C::Menu, Key::plain(K::PageDown), A::PageDown),
+            (C::Menu, Key::ctrl(K::Char('d')), A::PageDown),
+            (C::Menu, Key::plain(K::Home), A::Top),
+            (C::Menu, Key::plain(K::Char('g')), A::Top),
+            (C::Menu, Key::plain(K::End), A::Bottom),
+            (C::Menu, Key::plain(K::Char('G')), A::Bottom),
+            (C::Menu, Key::plain(K::Char('/')), A::Search),
+            (C::Menu, Key::plain(K::Enter), A::Select),
+            (C::Menu, Key::plain(K::Char('l')), A::Select),
+            (C::Menu, Key::plain(K::Right), A::Select),
+            (C::Menu, Key::plain(K::Esc), A::Back),
+            (C::Menu, Key::plain(K::Char('h')), A::Back),
+            (C::Menu, Key::plain(K::Left), A::Back),
+            (C::Menu, Key::plain(K::Char('?')), A::Help),
+            (C::Menu, Key::plain(K::Tab), A::NextTab),
+            (C::Menu, Key::plain(K::Char('t')), A::FilterTag),
+            (C::Menu, Key::plain(K::Char('m')), A::FilterMode),
+            (C::Menu, Key::plain(K::Char('d')), A::FilterDate),
+            (C::Menu, Key::plain(K::Char('w')), A::WordOfTheDay),
+            (C::Menu, Key::plain(K::Char('p')), A::Pin),
+            (C::Menu, Key::plain(K::Char('s')), A::Sort),
+            (C::Results, Key::plain(K::Enter), A::Restart),
+            (C::Results, Key::plain(K::Char('r')), A::Repeat),
+            (C::Results, Key::plain(K::Char('m')), A::PracticeMissed),
+            (C::Results, Key::plain(K::Char('v')), A::Review),
+            (C::Results, Key::plain(K::Char('t')), A::Annotate),
+            (C::Results, Key::plain(K::Esc), A::Back),
+            (C::Results, Key::plain(K::Char('?')), A::Help),
+        ];
+
+        Self {
+            bindings: bindings
+                .into_iter()
+                .map(|(context, key, action)| Binding {
+                    context,
+                    key,
+                    action,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// replacement keys for actions, by context then action
+pub type KeyConfig = HashMap<Context, HashMap<Action, Vec<Key>>>;
+
+impl Keymap {
+    /// the default bindings with the ones from the config replacing them
+    #[must_use]
+    pub fn new(config: &KeyConfig) -> Self {
+        let mut keymap = Self::default();
+
+        for (context, actions) in config {
+            for (action, keys) in actions {
+                keymap.remap(*context, *action, keys);
+            }
+        }
+
+        keymap
+    }
+
+    /// replace every key bound to an action in a context, no keys unbinds it
+    pub fn remap(&mut self, context: Context, action: Action, keys: &[Key]) {
+        // keep the action where it was so the help stays in the same order
+        let index = self
+            .bindings
+            .iter()
+            .position(|binding| binding.context == context && binding.action == action)
+            .unwrap_or(self.bindings.len());
+
+        self.bindings
+            .retain(|binding| binding.context != context || binding.action != action);
+
+        let index = index.min(self.bindings.len());
+
+        self.bindings.splice(
+            index..index,
+            keys.iter().map(|key| Binding {
+                context,
+                key: *key,
+                action,
+            }),
+        );
+    }
+
+    /// the action a key does in a context, bindings for the context win over global ones
+    #[must_use]
+    pub fn action(&self, context: Context, key_event: &KeyEvent) -> Option<Action> {
+        let key = Key::from(key_event);
+
+        [context, Context::Global].into_iter().find_map(|context| {
+            self.bindings
+                .iter()
+                .find(|binding| binding.context == context && binding.key == key)
+                .map(|binding| binding.action)
+        })
+    }
+
+    /// the first key bound to an action in a context or globally
+    #[must_use]
+    pub fn key(&self, context: Context, action: Action) -> Option<Key> {
+        self.bindings
+            .iter()
+            .find(|binding| {
+                binding.action == action
+                    && (binding.context == context || binding.context == Context::Global)
+            })
+            .map(|binding| binding.key)
+    }
+
+    /// every action available in a context with all of the keys bound to it, in table order
+    #[must_use]
+    pub fn help(&self, context: Context) -> Vec<(Action, Vec<Key>)> {
+        let mut help: Vec<(Action, Vec<Key>)> = Vec::new();
+
+        for binding in self
+            .bindings
+            .iter()
+            .filter(|binding| binding.context == context || binding.context == Context::Global)
+        {
+            match help
+                .iter_mut()
+                .find(|(action, _)| *action == binding.action)
+            {
+                Some((_, keys)) => keys.push(binding.key),
+                None => help.push((binding.action, vec![binding.key])),
+            }
+        }
+
+        help
+    }
+}
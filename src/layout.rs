@@ -0,0 +1,97 @@
+/// the physical keyboard layout, used to put characters on the right keys and fingers
+#[derive(Clone, Default, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Colemak,
+    Dvorak,
+    /// the top, home and bottom rows from left to right
+    Custom([String; 3]),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+impl Hand {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Finger {
+    Pinky,
+    Ring,
+    Middle,
+    Index,
+}
+
+impl Finger {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Pinky => "pinky",
+            Self::Ring => "ring",
+            Self::Middle => "middle",
+            Self::Index => "index",
+        }
+    }
+}
+
+/// every finger from the left pinky to the right pinky
+pub const FINGERS: [(Hand, Finger); 8] = [
+    (Hand::Left, Finger::Pinky),
+    (Hand::Left, Finger::Ring),
+    (Hand::Left, Finger::Middle),
+    (Hand::Left, Finger::Index),
+    (Hand::Right, Finger::Index),
+    (Hand::Right, Finger::Middle),
+    (Hand::Right, Finger::Ring),
+    (Hand::Right, Finger::Pinky),
+];
+
+impl KeyboardLayout {
+    /// characters of the top, home and bottom rows
+    pub fn rows(&self) -> [&str; 3] {
+        match self {
+            Self::Qwerty => ["qwertyuiop", "asdfghjkl;", "zxcvbnm,./"],
+            Self::Colemak => ["qwfpgjluy;", "arstdhneio", "zxcvbkm,./"],
+            Self::Dvorak => ["',.pyfgcrl", "aoeuidhtns", ";qjkxbmwvz"],
+            Self::Custom(rows) => [&rows[0], &rows[1], &rows[2]].map(String::as_str),
+        }
+    }
+
+    /// row and column of the key that types a character
+    #[must_use]
+    pub fn position(&self, c: char) -> Option<(usize, usize)> {
+        let c = c.to_ascii_lowercase();
+
+        self.rows()
+            .iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.chars().position(|key| key == c).map(|col| (row, col)))
+    }
+
+    /// the hand and finger that type a character with touch typing
+    #[must_use]
+    pub fn finger(&self, c: char) -> Option<(Hand, Finger)> {
+        self.position(c).map(|(_, col)| match col {
+            0 => (Hand::Left, Finger::Pinky),
+            1 => (Hand::Left, Finger::Ring),
+            2 => (Hand::Left, Finger::Middle),
+            3 | 4 => (Hand::Left, Finger::Index),
+            5 | 6 => (Hand::Right, Finger::Index),
+            7 => (Hand::Right, Finger::Middle),
+            8 => (Hand::Right, Finger::Ring),
+            _ => (Hand::Right, Finger::Pinky),
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    error::{Error, Result},
+    storage,
+};
+use rand::{seq::IndexedRandom, Rng};
+use std::path::{Path, PathBuf};
+
+/// a lesson file, so courses can be shared as toml
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+pub struct Lesson {
+    pub title: String,
+    /// shown next to the lesson before it is started
+    #[serde(default)]
+    pub notes: String,
+    /// words a test is picked from, ignored when there is a text
+    #[serde(default)]
+    pub words: Vec<String>,
+    /// typed exactly as written instead of random words
+    pub text: Option<String>,
+    /// words in a test picked from the word list, the game length when missing
+    pub len: Option<usize>,
+    #[serde(default)]
+    pub pass: PassCriteria,
+}
+
+/// the lowest results that pass a lesson, 0 for no minimum
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct PassCriteria {
+    pub wpm: f64,
+    /// in percent
+    pub accuracy: f64,
+}
+
+impl PassCriteria {
+    /// false when there is nothing to meet
+    #[must_use]
+    pub fn is_set(&self) -> bool {
+        self.wpm > 0.0 || self.accuracy > 0.0
+    }
+
+    /// accuracy is a fraction, as the game gives it
+    #[must_use]
+    pub fn passed(&self, wpm: f64, accuracy: f64) -> bool {
+        wpm >= self.wpm && accuracy * 100.0 >= self.accuracy
+    }
+}
+
+impl Lesson {
+    /// read a lesson file
+    ///
+    /// # Errors
+    /// when the file is missing or can't be read or parsed
+    pub fn load(path: &Path) -> Result<Self> {
+        storage::load(path)?.ok_or_else(|| Error::Read {
+            path: path.to_path_buf(),
+            source: std::io::ErrorKind::NotFound.into(),
+        })
+    }
+
+    /// every lesson file in a directory sorted by title, a missing directory has none
+    pub fn load_dir(dir: &Path, errors: &mut Vec<Error>) -> Vec<Self> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Vec::new();
+        };
+
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml")
+            })
+            .collect();
+        paths.sort();
+
+        let mut lessons: Vec<Self> = paths
+            .iter()
+            .filter_map(|path| Self::load(path).map_err(|error| errors.push(error)).ok())
+            .collect();
+        lessons.sort_by(|a, b| a.title.cmp(&b.title));
+
+        lessons
+    }
+
+    /// the text of a test, a new pick of words each time unless the lesson has a text
+    ///
+    /// # Errors
+    /// `Error::NoWords` when the lesson has neither a text nor words
+    pub fn target(&self, len: usize, rng: &mut impl Rng) -> Result<String> {
+        let words: Vec<&str> = self.text.as_ref().map_or_else(
+            || {
+                (0..self.len.unwrap_or(len))
+                    .filter_map(|_| self.words.choose(rng).map(String::as_str))
+                    .collect()
+            },
+            |text| text.split_whitespace().collect(),
+        );
+
+        if words.is_empty() {
+            return Err(Error::NoWords);
+        }
+
+        Ok(words.join(" "))
+    }
+}
@@ -0,0 +1,39 @@
+#![warn(clippy::all, clippy::pedantic, clippy::nursery)]
+
+pub mod about;
+pub mod arcade;
+pub mod bigrams;
+pub mod budget;
+pub mod clock;
+pub mod code;
+pub mod date;
+pub mod error;
+pub mod fingers;
+pub mod game;
+pub mod goals;
+pub mod grammar;
+pub mod history;
+pub mod keymap;
+pub mod layout;
+pub mod lesson;
+pub mod locale;
+pub mod migrate;
+pub mod names;
+pub mod noise;
+pub mod numbers;
+pub mod punctuation;
+pub mod quiz;
+pub mod settings;
+pub mod snapshot;
+pub mod storage;
+pub mod summary;
+pub mod survival;
+pub mod time_attack;
+pub mod words;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use code::TestCode;
+pub use error::{Error, Result};
+pub use game::{graphemes, Game, GameSpan, WordReview};
+pub use settings::GameSettings;
+pub use words::WORDS;
@@ -0,0 +1,58 @@
+use ratatui::crossterm::event::{KeyCode, KeyEvent};
+use tt::keymap::Action;
+
+/// how far page up and page down move
+const PAGE: usize = 10;
+
+/// selection and search state of a list screen
+#[derive(Default)]
+pub struct ListView {
+    pub selected: usize,
+    pub query: String,
+    /// keys edit the query instead of going through the keymap
+    pub searching: bool,
+}
+
+impl ListView {
+    /// whether an item matches the search query
+    pub fn matches(&self, item: &str) -> bool {
+        item.to_lowercase().contains(&self.query.to_lowercase())
+    }
+
+    /// apply a movement action to a list of len matching items, false if the action is not one
+    pub fn action(&mut self, action: Action, len: usize) -> bool {
+        let last = len.saturating_sub(1);
+
+        match action {
+            Action::Up => self.selected = self.selected.saturating_sub(1),
+            Action::Down => self.selected = (self.selected + 1).min(last),
+            Action::PageUp => self.selected = self.selected.saturating_sub(PAGE),
+            Action::PageDown => self.selected = (self.selected + PAGE).min(last),
+            Action::Top => self.selected = 0,
+            Action::Bottom => self.selected = last,
+            Action::Search => self.searching = true,
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// edit the query while searching
+    pub fn search_key(&mut self, key_event: &KeyEvent) {
+        match key_event.code {
+            KeyCode::Enter => {
+                self.searching = false;
+                return;
+            }
+            KeyCode::Esc => {
+                self.searching = false;
+                self.query.clear();
+            }
+            KeyCode::Backspace => _ = self.query.pop(),
+            KeyCode::Char(c) => self.query.push(c),
+            _ => return,
+        }
+
+        self.selected = 0;
+    }
+}
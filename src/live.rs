@@ -0,0 +1,125 @@
+use ratatui::crossterm::event::KeyCode;
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+use tt::{
+    error::{Error, Result},
+    Game,
+};
+
+/// how the game in progress is going, for a streaming overlay to show
+#[derive(PartialEq, serde::Serialize)]
+struct Stats {
+    wpm: u32,
+    /// in percent
+    accuracy: u32,
+    /// words moved past so far
+    words: usize,
+    total_words: usize,
+    seconds: u64,
+    finished: bool,
+}
+
+impl Stats {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn new(game: &Game<KeyCode>) -> Self {
+        let total_words = game.target.split(' ').count();
+
+        Self {
+            wpm: game.wpm().round() as u32,
+            accuracy: (game.accuracy() * 100.0).round() as u32,
+            words: game.input.matches(' ').count().min(total_words),
+            total_words,
+            seconds: game.elapsed().as_secs(),
+            finished: game.is_finished(),
+        }
+    }
+
+    /// a few lines for a text source
+    fn text(&self) -> String {
+        format!(
+            "{} wpm\n{}% accuracy\n{}/{} words\n{}s\n",
+            self.wpm, self.accuracy, self.words, self.total_words, self.seconds
+        )
+    }
+}
+
+/// a file rewritten with the current stats whenever they change, json for a .json file
+/// and plain text for anything else, a named pipe is given a line each time instead
+pub struct LiveStats {
+    path: PathBuf,
+    json: bool,
+    /// open for the whole game when the path is a pipe
+    pipe: Option<File>,
+    last: Option<Stats>,
+}
+
+impl LiveStats {
+    /// opening a pipe waits for something to read it, like `--emit-events`
+    pub fn open(path: &Path) -> Result<Self> {
+        let pipe = is_pipe(path)
+            .then(|| File::options().write(true).open(path))
+            .transpose()
+            .map_err(|source| Error::Write {
+                path: path.to_path_buf(),
+                source,
+            })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            json: path
+                .extension()
+                .is_some_and(|extension| extension == "json"),
+            pipe,
+            last: None,
+        })
+    }
+
+    /// write the game's stats out if they changed since the last time
+    pub fn update(&mut self, game: &Game<KeyCode>) -> Result<()> {
+        let stats = Stats::new(game);
+
+        if self.last.as_ref() == Some(&stats) {
+            return Ok(());
+        }
+
+        let text = if self.json {
+            serde_json::to_string(&stats).map_err(|error| Error::Output(error.to_string()))? + "\n"
+        } else {
+            stats.text()
+        };
+
+        let result = if let Some(pipe) = &mut self.pipe {
+            // one line each so a reader can tell the updates apart
+            pipe.write_all(text.replace('\n', " ").trim_end().as_bytes())
+                .and_then(|()| pipe.write_all(b"\n"))
+                .and_then(|()| pipe.flush())
+        } else {
+            // renamed into place so an overlay never reads half a file
+            let mut temp = self.path.clone().into_os_string();
+            temp.push(".tmp");
+            std::fs::write(&temp, text).and_then(|()| std::fs::rename(&temp, &self.path))
+        };
+
+        self.last = Some(stats);
+
+        result.map_err(|source| Error::Write {
+            path: self.path.clone(),
+            source,
+        })
+    }
+}
+
+#[cfg(unix)]
+fn is_pipe(path: &Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+
+    std::fs::metadata(path).is_ok_and(|metadata| metadata.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+fn is_pipe(_path: &Path) -> bool {
+    false
+}
@@ -0,0 +1,771 @@
+use crate::{
+    error::Error,
+    goals::Period,
+    keymap::{Action, Context},
+    layout::{Finger, Hand},
+    names::Pack,
+    settings::Pin,
+};
+use std::fmt::{Display, Write};
+
+/// the language of the interface, the words of the tests are toki pona either way
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Locale {
+    #[default]
+    English,
+    TokiPona,
+}
+
+/// a piece of interface text, each {} in it is filled in by `Locale::fill`
+#[derive(Clone, Copy)]
+pub enum Message {
+    Action(Action),
+    Context(Context),
+    Hand(Hand),
+    Finger(Finger),
+    Pin(Pin),
+    Period(Period),
+    Pack(Pack),
+
+    // menus
+    AppTitle,
+    StartTest,
+    CustomTest,
+    NumbersDrill,
+    WeakBigrams,
+    TimeAttack,
+    Survival,
+    FallingWords,
+    ReverseQuiz,
+    FreeWriting,
+    Lessons,
+    Dictionary,
+    Stats,
+    About,
+    NamePacks,
+    Cartouches,
+    Quit,
+    Resume,
+    Restart,
+    ResumeLast,
+    StartNew,
+    SaveAndQuit,
+    QuitWithoutSaving,
+    Cancel,
+    Paused,
+    Settings,
+    StrictSpace,
+    SpaceCommits,
+    Forgiving,
+    AutoSpace,
+    Shadow,
+    WordPerLine,
+    SitelenPona,
+    QuitTest,
+    UnfinishedTest,
+
+    // popups
+    Error,
+    PressAnyKey,
+    EnlargeTerminal,
+    TagsAndNote,
+    AnnotateHint,
+    CustomExample,
+    CustomHint,
+    Goals,
+    Keys,
+    KeysFor,
+    Unbound,
+
+    // words and lessons
+    WordOfTheDay,
+    DrillIt,
+    Definition,
+    Etymology,
+    KuData,
+    PassAt,
+    NoLessons,
+    Nimi,
+    NimiPinned,
+
+    // typing
+    Wpm,
+    Combo,
+    Best,
+    Left,
+    Pace,
+    Afk,
+    ToBegin,
+    NewWords,
+    EscForMenu,
+    Grammar,
+    GrammarOn,
+    GrammarOff,
+    Caught,
+    Lives,
+    GameOver,
+    PlayAgain,
+    CaughtWords,
+    WpmAndAccuracy,
+    Right,
+    Wrong,
+    Accepted,
+    QuestionOf,
+    RightOf,
+    AnswerHint,
+
+    // grammar issues
+    NoLiAfterMiSina,
+    MissingLi,
+    NothingBeforeParticle,
+    PiNeedsWordBefore,
+    PiNeedsTwoWords,
+    SameParticleTwice,
+
+    // results and stats
+    Results,
+    Help,
+    Accuracy,
+    Time,
+    BestCombo,
+    Kspc,
+    Score,
+    LongestSurvival,
+    TestCode,
+    Passed,
+    Failed,
+    NotPassed,
+    TryAgain,
+    FirstTest,
+    BetterThan,
+    PersonalBest,
+    DailyGoalDone,
+    WpmOverTime,
+    Hesitations,
+    EveryWord,
+    Tests,
+    NextTab,
+    AverageWpm,
+    BestWpm,
+    AverageAccuracy,
+    AverageKspc,
+    TimeTyping,
+    Tags,
+    Note,
+    Summary,
+    Fingers,
+    NoStrokes,
+    SameFinger,
+    Alternation,
+    AllTime,
+    LastWeek,
+    LastMonth,
+    LastYear,
+    Tagged,
+    MinutesOf,
+    TestsOf,
+    AccuracyOf,
+
+    // charts
+    WpmOverTests,
+    TrendPerTest,
+    LastTests,
+    Trend,
+    MsBetweenKeys,
+    SlowPauses,
+    Errors,
+    Speed,
+
+    // about
+    DataSnapshot,
+    WordsInAll,
+    DataLicense,
+
+    // tutorial
+    Welcome,
+    WelcomeText,
+    Colors,
+    ColorsText,
+    KeysText,
+    Done,
+    DoneText,
+
+    // accessible mode
+    AccessibleMode,
+    NewTestOf,
+    WrongSpace,
+    WrongKey,
+    WordOf,
+    FinishedWith,
+    AnotherTest,
+
+    // errors
+    ReadFailed,
+    WriteFailed,
+    ParseFailed,
+    SerializeFailed,
+    NewerVersion,
+    InvalidSetting,
+    InvalidSettings,
+    MissingField,
+    NoWords,
+    NotEnoughHistory,
+    NoFrequencies,
+    InvalidCode,
+    OutputFailed,
+    HookFailed,
+    GeneratorFailed,
+    TerminalError,
+}
+
+impl Locale {
+    #[must_use]
+    pub const fn get(self, message: Message) -> &'static str {
+        match self {
+            Self::English => english(message),
+            Self::TokiPona => toki_pona(message),
+        }
+    }
+
+    /// the message with each {} replaced by the next argument
+    pub fn fill(self, message: Message, args: &[&dyn Display]) -> String {
+        let mut args = args.iter();
+        let mut parts = self.get(message).split("{}");
+        let mut text = parts.next().unwrap_or_default().to_string();
+
+        for part in parts {
+            if let Some(arg) = args.next() {
+                _ = write!(text, "{arg}");
+            }
+
+            text.push_str(part);
+        }
+
+        text
+    }
+
+    /// the message padded to sit in a border
+    #[must_use]
+    pub fn title(self, message: Message) -> String {
+        format!(" {} ", self.get(message))
+    }
+
+    #[must_use]
+    pub fn error(self, error: &Error) -> String {
+        match error {
+            Error::Read { path, source } => {
+                self.fill(Message::ReadFailed, &[&path.display(), source])
+            }
+            Error::Write { path, source } => {
+                self.fill(Message::WriteFailed, &[&path.display(), source])
+            }
+            Error::Parse { path, source } => {
+                self.fill(Message::ParseFailed, &[&path.display(), source])
+            }
+            Error::Json { path, source } => {
+                self.fill(Message::ParseFailed, &[&path.display(), source])
+            }
+            Error::Serialize { path, source } => {
+                self.fill(Message::SerializeFailed, &[&path.display(), source])
+            }
+            Error::NewerVersion { path, version } => {
+                self.fill(Message::NewerVersion, &[&path.display(), version])
+            }
+            Error::Setting { layer, key, source } => {
+                self.fill(Message::InvalidSetting, &[key, layer, source])
+            }
+            Error::Settings { source } => self.fill(Message::InvalidSettings, &[source]),
+            Error::MissingField { field } => self.fill(Message::MissingField, &[field]),
+            Error::NoWords => self.get(Message::NoWords).to_string(),
+            Error::NotEnoughHistory => self.get(Message::NotEnoughHistory).to_string(),
+            Error::NoFrequencies => self.get(Message::NoFrequencies).to_string(),
+            Error::InvalidCode(code) => self.fill(Message::InvalidCode, &[&format!("{code:?}")]),
+            Error::Output(text) => self.fill(Message::OutputFailed, &[text]),
+            Error::Hook { command, source } => {
+                self.fill(Message::HookFailed, &[&format!("{command:?}"), source])
+            }
+            Error::Generator { command, message } => self.fill(
+                Message::GeneratorFailed,
+                &[&format!("{command:?}"), message],
+            ),
+            Error::Usage(text) => text.clone(),
+            Error::Terminal(source) => self.fill(Message::TerminalError, &[source]),
+        }
+    }
+}
+
+// one arm a message
+#[allow(clippy::too_many_lines)]
+const fn english(message: Message) -> &'static str {
+    match message {
+        Message::Action(action) => action.description(),
+        Message::Context(context) => context.name(),
+        Message::Hand(hand) => hand.name(),
+        Message::Finger(finger) => finger.name(),
+        Message::Pin(pin) => pin.label(),
+        Message::Period(period) => period.name(),
+        Message::Pack(pack) => pack.name(),
+
+        Message::AppTitle => "toki pona typing",
+        Message::StartTest => "start test",
+        Message::CustomTest => "custom test",
+        Message::NumbersDrill => "numbers drill",
+        Message::WeakBigrams => "weak bigrams",
+        Message::TimeAttack => "time attack",
+        Message::Survival => "survival",
+        Message::FallingWords => "falling words",
+        Message::ReverseQuiz => "reverse quiz",
+        Message::FreeWriting => "free writing",
+        Message::Lessons => "lessons",
+        Message::Dictionary => "dictionary",
+        Message::Stats => "stats",
+        Message::About => "about",
+        Message::NamePacks => "name packs",
+        Message::Cartouches => "cartouches",
+        Message::Quit => "quit",
+        Message::Resume => "resume",
+        Message::Restart => "restart",
+        Message::ResumeLast => "resume last session",
+        Message::StartNew => "start a new test",
+        Message::SaveAndQuit => "save and quit",
+        Message::QuitWithoutSaving => "quit without saving",
+        Message::Cancel => "cancel",
+        Message::Paused => "paused",
+        Message::Settings => "settings",
+        Message::StrictSpace => "strict space",
+        Message::SpaceCommits => "space ends the word",
+        Message::Forgiving => "forgive swapped letters",
+        Message::AutoSpace => "auto space",
+        Message::Shadow => "hide typed words",
+        Message::WordPerLine => "one word a line",
+        Message::SitelenPona => "sitelen pona",
+        Message::QuitTest => "quit test?",
+        Message::UnfinishedTest => "unfinished test found",
+
+        Message::Error => "error",
+        Message::PressAnyKey => "press any key",
+        Message::EnlargeTerminal => "please enlarge your terminal\n(need {}x{}, have {}x{})",
+        Message::TagsAndNote => "tags and note",
+        Message::AnnotateHint => "tab switches, enter saves, esc cancels",
+        Message::CustomExample => "words like 30 or seconds like 45s",
+        Message::CustomHint => "enter starts, esc cancels",
+        Message::Goals => "goals",
+        Message::Keys => "keys",
+        Message::KeysFor => "keys: {}",
+        Message::Unbound => "(unbound)",
+
+        Message::WordOfTheDay => "nimi pi tenpo suno: {}",
+        Message::DrillIt => "{} drill it",
+        Message::Definition => "DEFINITION",
+        Message::Etymology => "ETYMOLOGY",
+        Message::KuData => "KU DATA",
+        Message::PassAt => "pass at {} wpm and {}% accuracy",
+        Message::NoLessons => {
+            "no lessons yet, put lesson files in the lessons directory next to config.toml"
+        }
+        Message::Nimi => "nimi",
+        Message::NimiPinned => "nimi, pinned first",
+
+        Message::Wpm => "wpm",
+        Message::Combo => "combo",
+        Message::Best => "best",
+        Message::Left => "left",
+        Message::Pace => "pace {} of {} wpm",
+        Message::Afk => "afk, timer paused",
+        Message::ToBegin => "{} to begin",
+        Message::NewWords => "{} for new words",
+        Message::EscForMenu => "esc for the menu",
+        Message::Grammar => "grammar",
+        Message::GrammarOn => "{} turns the grammar check on,",
+        Message::GrammarOff => "{} turns the grammar check off,",
+        Message::Caught => "caught",
+        Message::Lives => "lives",
+        Message::GameOver => "game over",
+        Message::PlayAgain => "{} to play again, {} for the menu",
+        Message::CaughtWords => "caught {} words",
+        Message::WpmAndAccuracy => "{} wpm, {}% accuracy",
+        Message::Right => "right",
+        Message::Wrong => "wrong",
+        Message::Accepted => "{}: {}, accepted {}",
+        Message::QuestionOf => "question {} of {}",
+        Message::RightOf => "{} of {} right",
+        Message::AnswerHint => "enter answers, esc for the menu",
+
+        Message::NoLiAfterMiSina => "no li after mi or sina",
+        Message::MissingLi => "missing li before the verb",
+        Message::NothingBeforeParticle => "nothing before the particle",
+        Message::PiNeedsWordBefore => "pi needs a word before it",
+        Message::PiNeedsTwoWords => "pi needs two or more words after it",
+        Message::SameParticleTwice => "the same particle twice",
+
+        Message::Results => "results",
+        Message::Help => "help",
+        Message::Accuracy => "accuracy",
+        Message::Time => "time",
+        Message::BestCombo => "best combo",
+        Message::Kspc => "keystrokes per character",
+        Message::Score => "score {} words",
+        Message::LongestSurvival => "longest survival",
+        Message::TestCode => "test code",
+        Message::Passed => "passed",
+        Message::Failed => "failed, too many mistakes",
+        Message::NotPassed => "not passed, needs {} wpm and {}% accuracy",
+        Message::TryAgain => ", {} to try again",
+        Message::FirstTest => "the first test like this one",
+        Message::BetterThan => "better than {}% of your {} word {} tests",
+        Message::PersonalBest => "a personal best, {} wpm",
+        Message::DailyGoalDone => "today's goal is done",
+        Message::WpmOverTime => "wpm over time",
+        Message::Hesitations => "hesitations",
+        Message::EveryWord => "every word",
+        Message::Tests => "tests",
+        Message::NextTab => "{} next tab",
+        Message::AverageWpm => "average wpm",
+        Message::BestWpm => "best wpm",
+        Message::AverageAccuracy => "average accuracy",
+        Message::AverageKspc => "average keystrokes per character",
+        Message::TimeTyping => "time typing {} minutes",
+        Message::Tags => "tags",
+        Message::Note => "note",
+        Message::Summary => "summary",
+        Message::Fingers => "fingers",
+        Message::NoStrokes => "no strokes recorded for this test",
+        Message::SameFinger => "same finger pairs",
+        Message::Alternation => "hand alternation",
+        Message::AllTime => "all time",
+        Message::LastWeek => "last 7 days",
+        Message::LastMonth => "last 30 days",
+        Message::LastYear => "last year",
+        Message::Tagged => "tagged {}",
+        Message::MinutesOf => "{} of {} minutes",
+        Message::TestsOf => "{} of {} tests",
+        Message::AccuracyOf => "{}% of {}% accuracy",
+
+        Message::WpmOverTests => "wpm over tests",
+        Message::TrendPerTest => "wpm over tests, trend {} per test",
+        Message::LastTests => "last {}",
+        Message::Trend => "trend",
+        Message::MsBetweenKeys => "ms between keys",
+        Message::SlowPauses => "{} pauses over a second",
+        Message::Errors => "errors",
+        Message::Speed => "speed",
+
+        Message::DataSnapshot => "linku data from {}, commit {}",
+        Message::WordsInAll => "words in all",
+        Message::DataLicense => "word data license: {}",
+
+        Message::Welcome => "welcome",
+        Message::WelcomeText => {
+            "type the words at the bottom of the screen, space moves on to the next word. \
+             the panels at the top explain the current and next word, {} hides them."
+        }
+        Message::Colors => "colors",
+        Message::ColorsText => {
+            "green letters were typed correctly and red ones were wrong. yellow letters are \
+             extra ones past the end of a word and light red ones were skipped by pressing \
+             space early."
+        }
+        Message::KeysText => {
+            "{} pauses, {} skips a word and {} deletes the current word. {} lists every key."
+        }
+        Message::Done => "pona!",
+        Message::DoneText => {
+            "that's the whole test. results are saved and can be seen from the stats screen \
+             in the menu."
+        }
+
+        Message::AccessibleMode => "accessible mode, {} quits",
+        Message::NewTestOf => "new test of {} words",
+        Message::WrongSpace => "wrong space",
+        Message::WrongKey => "wrong {}",
+        Message::WordOf => "word {} of {}: {}",
+        Message::FinishedWith => "finished, {} words per minute, {} percent accuracy",
+        Message::AnotherTest => "{} for another test, {} to quit",
+
+        Message::ReadFailed => "failed to read {}: {}",
+        Message::WriteFailed => "failed to write {}: {}",
+        Message::ParseFailed => "failed to parse {}: {}",
+        Message::SerializeFailed => "failed to serialize {}: {}",
+        Message::NewerVersion => {
+            "{} was saved by a newer version of tt (format {}), update tt to read it"
+        }
+        Message::InvalidSetting => "invalid {} in {}: {}",
+        Message::InvalidSettings => "invalid settings: {}",
+        Message::MissingField => "word data has no {} field",
+        Message::NoWords => "no words matched the current settings",
+        Message::NotEnoughHistory => "not enough typing history yet, finish a few more tests first",
+        Message::NoFrequencies => {
+            "weighting = \"frequency\" needs src/res/frequency.tsv, which this build was made without"
+        }
+        Message::InvalidCode => "{} is not a test code",
+        Message::OutputFailed => "failed to format output: {}",
+        Message::HookFailed => "failed to run hook {}: {}",
+        Message::GeneratorFailed => "word generator {} failed: {}",
+        Message::TerminalError => "terminal error: {}",
+    }
+}
+
+// messages that are apart in english can read the same in toki pona
+#[allow(clippy::match_same_arms, clippy::too_many_lines)]
+const fn toki_pona(message: Message) -> &'static str {
+    match message {
+        Message::Action(action) => match action {
+            Action::Quit => "o pini",
+            Action::Help => "o lukin e lipu ni",
+            Action::Pause => "o awen",
+            Action::Begin => "o open e sitelen lukin",
+            Action::TogglePanels => "o len anu lukin e lipu nimi",
+            Action::ToggleGrammar => "o open anu pini e lukin pi nasin toki",
+            Action::ToggleGlosses => "o len anu lukin e toki inli lon anpa nimi",
+            Action::SkipWord => "o tawa nimi kama",
+            Action::DeleteWord => "o weka e nimi ni",
+            Action::Up => "o tawa sewi",
+            Action::Down => "o tawa anpa",
+            Action::PageUp => "o tawa lipu sewi",
+            Action::PageDown => "o tawa lipu anpa",
+            Action::Top => "o tawa open",
+            Action::Bottom => "o tawa pini",
+            Action::Search => "o alasa",
+            Action::Select => "o kama jo",
+            Action::Back => "o tawa monsi",
+            Action::Restart => "o open e sitelen sin",
+            Action::Repeat => "o sitelen sin e nimi sama",
+            Action::PracticeMissed => "o pali e nimi pakala",
+            Action::Review => "o lukin e nimi ale",
+            Action::Annotate => "o pana e nimi lili e toki tawa sitelen",
+            Action::NextTab => "o tawa lipu kama",
+            Action::FilterTag => "o nanpa e nimi lili wan taso",
+            Action::FilterMode => "o nanpa e nasin wan taso",
+            Action::FilterDate => "o nanpa e sitelen sin taso",
+            Action::WordOfTheDay => "o pali e nimi pi tenpo suno",
+            Action::Pin => "o pana e nimi tawa mute anu lili anu ala",
+            Action::Sort => "o ante e nasin nanpa",
+        },
+        Message::Context(context) => match context {
+            Context::Global => "ma ale",
+            Context::Game => "sitelen",
+            Context::Menu => "lipu wile",
+            Context::Results => "pini",
+        },
+        Message::Hand(hand) => match hand {
+            Hand::Left => "soto",
+            Hand::Right => "te",
+        },
+        Message::Finger(finger) => match finger {
+            Finger::Pinky => "lili",
+            Finger::Ring => "sike",
+            Finger::Middle => "insa",
+            Finger::Index => "lawa",
+        },
+        Message::Pin(pin) => match pin {
+            Pin::Normal => "",
+            Pin::Often => "mute",
+            Pin::Rare => "lili",
+            Pin::Never => "ala",
+        },
+        Message::Period(period) => match period {
+            Period::Day => "tenpo suno",
+            Period::Week => "tenpo esun",
+        },
+        Message::Pack(pack) => match pack {
+            Pack::People => "nimi jan",
+            Pack::Countries => "nimi ma",
+            Pack::Languages => "nimi toki",
+        },
+
+        Message::AppTitle => "sitelen pi toki pona",
+        Message::StartTest => "o open",
+        Message::CustomTest => "o open e sitelen sina",
+        Message::NumbersDrill => "o sitelen e nanpa",
+        Message::WeakBigrams => "sitelen tu pi pona lili",
+        Message::TimeAttack => "tenpo lili",
+        Message::Survival => "o awen lon",
+        Message::FallingWords => "nimi li anpa",
+        Message::ReverseQuiz => "toki inli tawa toki pona",
+        Message::FreeWriting => "o sitelen taso",
+        Message::Lessons => "kama sona",
+        Message::Dictionary => "lipu nimi",
+        Message::Stats => "sona nanpa",
+        Message::About => "sona pi ilo ni",
+        Message::NamePacks => "nimi jan en nimi ma",
+        Message::Cartouches => "sitelen pi nimi jan",
+        Message::Quit => "o pini",
+        Message::Resume => "o awen sitelen",
+        Message::Restart => "o open sin",
+        Message::ResumeLast => "o awen e sitelen pini",
+        Message::StartNew => "o open e sitelen sin",
+        Message::SaveAndQuit => "o awen e ona o pini",
+        Message::QuitWithoutSaving => "o pini, o awen ala",
+        Message::Cancel => "ala",
+        Message::Paused => "tenpo lape",
+        Message::Settings => "nasin ilo",
+        Message::StrictSpace => "lupa li ken ala kipisi e nimi",
+        Message::SpaceCommits => "lupa li pini e nimi",
+        Message::Forgiving => "sitelen tu pi ante nasin li pakala wan",
+        Message::AutoSpace => "lupa li kama lon tenpo pona",
+        Message::Shadow => "o weka e nimi pona",
+        Message::WordPerLine => "nimi wan lon linja wan",
+        Message::SitelenPona => "sitelen pona",
+        Message::QuitTest => "sina wile pini anu seme?",
+        Message::UnfinishedTest => "sitelen pi pini ala li lon",
+
+        Message::Error => "pakala",
+        Message::PressAnyKey => "o luka e nena",
+        Message::EnlargeTerminal => "o suli e lipu\n(wile {}x{}, jo {}x{})",
+        Message::TagsAndNote => "nimi lili en toki",
+        Message::AnnotateHint => "tab li ante, enter li awen, esc li weka",
+        Message::CustomExample => "nimi mute sama 30 anu tenpo sama 45s",
+        Message::CustomHint => "enter li open, esc li weka",
+        Message::Goals => "wile",
+        Message::Keys => "nena",
+        Message::KeysFor => "nena: {}",
+        Message::Unbound => "(nena ala)",
+
+        Message::WordOfTheDay => "nimi pi tenpo suno: {}",
+        Message::DrillIt => "{} li pali e ona",
+        Message::Definition => "KON",
+        Message::Etymology => "MAMA",
+        Message::KuData => "SONA KU",
+        Message::PassAt => "o kama jo e {} nanpa pi sitelen tawa e {}% pona",
+        Message::NoLessons => {
+            "kama sona li lon ala. o pana e lipu kama sona lon poki lessons poka config.toml"
+        }
+        Message::Nimi => "nimi",
+        Message::NimiPinned => "nimi, nimi awen li lon sewi",
+
+        Message::Wpm => "nanpa pi sitelen tawa",
+        Message::Combo => "linja",
+        Message::Best => "pona nanpa wan",
+        Message::Left => "tenpo kama",
+        Message::Pace => "tawa {} pi {} nanpa pi sitelen tawa",
+        Message::Afk => "sina lon ala, tenpo li awen",
+        Message::ToBegin => "{} li open",
+        Message::NewWords => "{} li pana e nimi sin",
+        Message::EscForMenu => "esc li tawa lipu wile",
+        Message::Grammar => "nasin toki",
+        Message::GrammarOn => "{} li open e lukin pi nasin toki,",
+        Message::GrammarOff => "{} li pini e lukin pi nasin toki,",
+        Message::Caught => "jo",
+        Message::Lives => "lon",
+        Message::GameOver => "musi li pini",
+        Message::PlayAgain => "{} li musi sin, {} li tawa lipu wile",
+        Message::CaughtWords => "sina jo e nimi {}",
+        Message::WpmAndAccuracy => "{} nanpa pi sitelen tawa, {}% pona",
+        Message::Right => "pona",
+        Message::Wrong => "ike",
+        Message::Accepted => "{}: {}, nimi pona li {}",
+        Message::QuestionOf => "wile sona {} lon {}",
+        Message::RightOf => "{} lon {} li pona",
+        Message::AnswerHint => "enter li pana, esc li tawa lipu wile",
+
+        Message::NoLiAfterMiSina => "li o lon ala monsi mi anu sina",
+        Message::MissingLi => "li o lon sinpin pi nimi pali",
+        Message::NothingBeforeParticle => "nimi ala li lon sinpin pi nimi lili ni",
+        Message::PiNeedsWordBefore => "pi li wile e nimi lon sinpin ona",
+        Message::PiNeedsTwoWords => "pi li wile e nimi tu anu mute lon monsi ona",
+        Message::SameParticleTwice => "nimi lili sama li lon tu",
+
+        Message::Results => "pini",
+        Message::Help => "sona",
+        Message::Accuracy => "nanpa pona",
+        Message::Time => "tenpo",
+        Message::BestCombo => "linja pona nanpa wan",
+        Message::Kspc => "nena tawa sitelen wan",
+        Message::Score => "nimi {}",
+        Message::LongestSurvival => "awen lon suli nanpa wan",
+        Message::TestCode => "nimi pi sitelen ni",
+        Message::Passed => "pali pona!",
+        Message::Failed => "pakala, ike li mute",
+        Message::NotPassed => "pini ala. o kama jo e {} nanpa pi sitelen tawa e {}% pona",
+        Message::TryAgain => ", {} li open sin",
+        Message::FirstTest => "sitelen sama nanpa wan",
+        Message::BetterThan => "ni li pona tawa {}% pi sitelen sina pi nimi {} lon nasin {}",
+        Message::PersonalBest => "sina pona nanpa wan, nanpa {}",
+        Message::DailyGoalDone => "wile pi tenpo suno ni li pini",
+        Message::WpmOverTime => "nanpa pi sitelen tawa lon tenpo",
+        Message::Hesitations => "tenpo awen",
+        Message::EveryWord => "nimi ale",
+        Message::Tests => "sitelen",
+        Message::NextTab => "{} li lipu kama",
+        Message::AverageWpm => "nanpa pi sitelen tawa meso",
+        Message::BestWpm => "nanpa pi sitelen tawa pona",
+        Message::AverageAccuracy => "nanpa pona meso",
+        Message::AverageKspc => "nena meso tawa sitelen wan",
+        Message::TimeTyping => "tenpo sitelen: tenpo lili {}",
+        Message::Tags => "nimi lili",
+        Message::Note => "toki",
+        Message::Summary => "sona lili",
+        Message::Fingers => "luka",
+        Message::NoStrokes => "nena ala li lon sitelen ni",
+        Message::SameFinger => "luka sama lon tu",
+        Message::Alternation => "luka ante",
+        Message::AllTime => "tenpo ale",
+        Message::LastWeek => "suno 7 pini",
+        Message::LastMonth => "suno 30 pini",
+        Message::LastYear => "sike suno pini",
+        Message::Tagged => "nimi lili {}",
+        Message::MinutesOf => "tenpo lili {} lon {}",
+        Message::TestsOf => "sitelen {} lon {}",
+        Message::AccuracyOf => "{}% lon {}% pona",
+
+        Message::WpmOverTests => "nanpa pi sitelen tawa lon sitelen ale",
+        Message::TrendPerTest => "nanpa pi sitelen tawa lon sitelen ale, {} lon sitelen wan",
+        Message::LastTests => "sitelen {} pini",
+        Message::Trend => "nasin",
+        Message::MsBetweenKeys => "tenpo lili lili lon nena tu",
+        Message::SlowPauses => "tenpo awen {} li suli",
+        Message::Errors => "ike",
+        Message::Speed => "wawa",
+
+        Message::DataSnapshot => "sona nimi tan linku lon {}, {}",
+        Message::WordsInAll => "nimi ale",
+        Message::DataLicense => "nasin lawa pi sona nimi: {}",
+
+        Message::Welcome => "kama pona",
+        Message::WelcomeText => {
+            "o sitelen e nimi lon anpa. nena suli (space) li tawa nimi kama. \
+             lipu lon sewi li pana e sona pi nimi ni en nimi kama, {} li len e ona."
+        }
+        Message::Colors => "kule",
+        Message::ColorsText => {
+            "sitelen laso li pona, sitelen loje li ike. sitelen jelo li namako lon pini nimi. \
+             sitelen loje walo li weka tan ni: sina luka e nena suli lon tenpo lili."
+        }
+        Message::KeysText => {
+            "{} li awen, {} li tawa nimi kama, {} li weka e nimi ni. {} li pana e nena ale."
+        }
+        Message::Done => "pona!",
+        Message::DoneText => {
+            "sitelen li pini. nanpa li awen, sina ken lukin e ona lon sona nanpa \
+             lon lipu wile."
+        }
+
+        Message::AccessibleMode => "nasin pi lukin ala. {} li pini",
+        Message::NewTestOf => "sitelen sin pi nimi {}",
+        Message::WrongSpace => "lupa li ike",
+        Message::WrongKey => "{} li ike",
+        Message::WordOf => "nimi {} lon {}: {}",
+        Message::FinishedWith => "pini. nimi {} lon tenpo lili wan, pona {} lon 100",
+        Message::AnotherTest => "{} li open e sitelen sin, {} li pini",
+
+        Message::ReadFailed => "mi ken ala lukin e {}: {}",
+        Message::WriteFailed => "mi ken ala sitelen e {}: {}",
+        Message::ParseFailed => "{} li nasa: {}",
+        Message::SerializeFailed => "mi ken ala sitelen e {}: {}",
+        Message::NewerVersion => "ilo tt sin li pali e {} (nanpa {}). o kama jo e ilo tt sin",
+        Message::InvalidSetting => "nasin {} lon {} li ike: {}",
+        Message::InvalidSettings => "nasin li ike: {}",
+        Message::MissingField => "sona nimi li jo ala e {}",
+        Message::NoWords => "nimi ala li pona tawa nasin sina",
+        Message::NotEnoughHistory => "sitelen pini li lili. o sitelen e mute",
+        Message::NoFrequencies => {
+            "weighting = \"frequency\" li wile e src/res/frequency.tsv. ilo ni li jo ala e ona"
+        }
+        Message::InvalidCode => "{} li nimi pi sitelen ala",
+        Message::OutputFailed => "mi ken ala pana e sona: {}",
+        Message::HookFailed => "mi ken ala open e ilo {}: {}",
+        Message::GeneratorFailed => "ilo nimi {} li pakala: {}",
+        Message::TerminalError => "ilo sitelen li pakala: {}",
+    }
+}
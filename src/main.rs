@@ -1,396 +1,3996 @@
-#![allow(unused)]
 #![warn(clippy::all, clippy::pedantic, clippy::nursery)]
 
-use rand::{rngs::ThreadRng, seq::SliceRandom};
+use rand::seq::SliceRandom;
 use ratatui::{
     crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers},
     layout::{
-        Constraint,
+        Alignment, Constraint,
         Direction::{Horizontal, Vertical},
-        Layout,
+        Flex, Layout, Rect,
     },
-    style::{Color, Modifier, Style, Styled, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
-    widgets::{Block, BorderType::Rounded, Paragraph, Wrap},
+    widgets::{
+        Block, BorderType::Rounded, Clear, LineGauge, List, ListItem, ListState, Paragraph,
+        Sparkline, Wrap,
+    },
+    DefaultTerminal, Frame,
 };
 use std::{
+    cell::Cell,
     collections::HashMap,
-    io::{Read, Write},
-    ops::SubAssign,
-    str::Chars,
-    sync::LazyLock,
-    time::{Instant, SystemTime},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
 };
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
 const APPLICATION: &str = "tt";
 
-#[cfg(not(feature = "compressed"))]
-static WORDS: LazyLock<HashMap<String, toml::Table>> = LazyLock::new(|| {
-    toml::from_str(include_str!("res/words.toml")).expect("failed to parse words.toml")
-});
+/// smallest terminal the game will try to draw in
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 15;
+/// narrower terminals get one column instead of panels side by side
+const NARROW_WIDTH: u16 = 60;
+/// height of the info panel under a list in one column
+const NARROW_INFO_HEIGHT: u16 = 8;
+/// how often the config files are checked for changes
+const CONFIG_CHECK: Duration = Duration::from_secs(1);
 
-#[cfg(feature = "compressed")]
-static WORDS: LazyLock<HashMap<String, toml::Table>> = LazyLock::new(|| {
-    let bz2 = include_bytes!("res/words.toml.bz2").as_slice();
-    let mut toml = String::new();
-    let mut decompressor = bzip2::read::BzDecoder::new(bz2);
+mod accessible;
+mod annotation;
+mod cli;
+mod completions;
+mod config;
+mod custom;
+mod events;
+mod filter;
+mod generator;
+mod heatmap;
+mod histogram;
+mod hooks;
+mod import;
+mod keylog;
+mod list;
+mod live;
+mod notify;
+mod output;
+mod report;
+mod simulate;
+mod sounds;
+mod trend;
+mod tutorial;
+mod vocabulary;
+mod websocket;
+mod weekly;
 
-    decompressor
-        .read_to_string(&mut toml)
-        .expect("failed to decompress words");
+use annotation::Annotation;
+use cli::{Cli, Command, Start};
+use config::{Config, ConfigWatch};
+use custom::{CustomEntry, CustomTest};
+use events::{EventLog, GameEvent};
+use filter::StatsFilter;
+use hooks::Hooks;
+use list::ListView;
+use live::LiveStats;
+use output::Format;
+use sounds::{Sound, Soundpack};
+use tt::{
+    about,
+    arcade::Arcade,
+    code::{self, TestCode},
+    date,
+    fingers::FingerStats,
+    goals::{Goals, Period, Progress},
+    grammar,
+    history::{self, History, Privacy, Session},
+    keymap::{self, Action, Context, Keymap},
+    layout::{KeyboardLayout, FINGERS},
+    lesson::{Lesson, PassCriteria},
+    locale::{Locale, Message},
+    names::{self, Pack},
+    punctuation,
+    quiz::Quiz,
+    settings::Pin,
+    snapshot::Snapshot,
+    storage,
+    summary::{self, Summary},
+    survival, time_attack, Clock, Error, Game, GameSettings, GameSpan, Result, WordReview, WORDS,
+};
+use tutorial::Tutorial;
+use websocket::EventServer;
 
-    toml::from_str(&toml).expect("failed to parse words.toml")
-});
+/// the definition and other information shown about a word
+fn word_info(toml: &toml::Table, locale: Locale) -> Text<'static> {
+    [
+        toml.get("definition").map(toml::Value::to_string).map(|s| {
+            format!(
+                "{} {}",
+                locale.get(Message::Definition),
+                s.trim_matches('\"')
+            )
+        }),
+        Some(String::new()),
+        toml.get("pu_verbatim")
+            .and_then(|value| value.get("en"))
+            .map(toml::Value::to_string)
+            .map(|s| s.trim_matches('\"').to_string()),
+        Some(String::new()),
+        toml.get("ku_data")
+            .and_then(|value| value.as_table())
+            .map(|table| {
+                table
+                    .keys()
+                    .fold(locale.get(Message::KuData).to_string(), |mut s, k| {
+                        s.push(' ');
+                        s.push_str(k);
+                        s
+                    })
+            }),
+    ]
+    .into_iter()
+    .flatten()
+    .map(Line::raw)
+    .collect()
+}
 
-#[derive(serde::Deserialize, serde::Serialize)]
-struct WordResults {}
+/// the source language and words a word comes from, as linku has them
+fn etymology(toml: &toml::Table) -> Option<String> {
+    let language = toml.get("source_language").and_then(toml::Value::as_str);
+    let words: Vec<&str> = toml
+        .get("etymology")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.get("word").and_then(toml::Value::as_str))
+        .collect();
 
-enum GameSpan<T> {
-    Correct(T),
-    Wrong(T),
-    Overflow(T),
-    Skipped(T),
-    Hidden(T),
+    match (language, words.is_empty()) {
+        (Some(language), false) => Some(format!("{language}: {}", words.join(", "))),
+        (Some(language), true) => Some(language.to_string()),
+        (None, false) => Some(words.join(", ")),
+        (None, true) => None,
+    }
 }
 
-impl<T> GameSpan<T> {
-    fn map<T2, F: Fn(&T) -> T2>(&self, f: F) -> GameSpan<T2> {
-        match self {
-            Self::Correct(v) => GameSpan::Correct(f(v)),
-            Self::Wrong(v) => GameSpan::Wrong(f(v)),
-            Self::Overflow(v) => GameSpan::Overflow(f(v)),
-            Self::Skipped(v) => GameSpan::Skipped(f(v)),
-            Self::Hidden(v) => GameSpan::Hidden(f(v)),
+/// the styles of correct, wrong, overflow and skipped text, kept apart by modifiers alone
+/// when there are no colours
+const fn span_styles(monochrome: bool) -> [Style; 4] {
+    const CORRECT: Style = Style::new().fg(Color::Green);
+
+    const WRONG: Style = Style::new()
+        .fg(Color::Red)
+        .add_modifier(Modifier::UNDERLINED)
+        .add_modifier(Modifier::BOLD);
+
+    const OVERFLOW: Style = Style::new().fg(Color::Yellow);
+
+    const SKIPPED: Style = Style::new().fg(Color::LightRed);
+
+    // without colours each kind of text needs its own modifiers
+    if monochrome {
+        [
+            Style::new().add_modifier(Modifier::BOLD),
+            Style::new().add_modifier(Modifier::UNDERLINED.union(Modifier::REVERSED)),
+            Style::new().add_modifier(Modifier::UNDERLINED),
+            Style::new().add_modifier(Modifier::DIM.union(Modifier::CROSSED_OUT)),
+        ]
+    } else {
+        [CORRECT, WRONG, OVERFLOW, SKIPPED]
+    }
+}
+
+/// a row of the review: the target, what was typed over it and every key typed in the word
+fn review_line(review: &WordReview, monochrome: bool, locale: Locale) -> Line<'static> {
+    let [correct, wrong, overflow, skipped] = span_styles(monochrome);
+
+    let target: Vec<&str> = review.target.graphemes(true).collect();
+    let typed: Vec<&str> = review.typed.graphemes(true).collect();
+
+    let mut spans = vec![Span::raw(format!("{:<16}", review.target))];
+
+    for (i, grapheme) in typed.iter().enumerate() {
+        let style = match target.get(i) {
+            Some(t) if t == grapheme => correct,
+            Some(_) => wrong,
+            None => overflow,
+        };
+        spans.push(Span::styled((*grapheme).to_string(), style));
+    }
+
+    // the rest of a word that was skipped or not reached
+    let rest = target.get(typed.len()..).unwrap_or_default().concat();
+    let width = typed.len().max(target.len());
+    spans.push(Span::styled(rest, skipped));
+    spans.push(Span::raw(" ".repeat(16usize.saturating_sub(width))));
+
+    // only keys that weren't simply right, the corrections are what the row is for
+    if review.keys.iter().any(|(_, right)| !right) {
+        spans.push(Span::styled(
+            format!("{} ", locale.get(Message::Keys)),
+            Style::new().dim(),
+        ));
+
+        for &(c, right) in &review.keys {
+            let c = if c == ' ' { '␣' } else { c };
+            spans.push(Span::styled(
+                c.to_string(),
+                if right { Style::new().dim() } else { wrong },
+            ));
         }
     }
+
+    Line::from(spans)
 }
 
-struct GameSettings<T> {
-    core: T,
-    common: T,
-    uncommon: T,
-    obscure: T,
-    sandbox: T,
-    deprecated: T,
-    nondeprecated: T,
-    words: HashMap<String, T>,
-    len: usize,
+/// a duration as m:ss
+fn minutes(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
 }
 
-impl GameSettings<usize> {
-    const DEFAULT: usize = 1000;
+/// what is left of the error budget as a bar that shrinks with each mistake, empty without one
+#[allow(clippy::cast_precision_loss)]
+fn budget_bar(settings: &GameSettings<usize>, game: &Game<KeyCode>) -> Span<'static> {
+    const WIDTH: usize = 10;
 
-    fn get_word(&self, word: &str) -> usize {
-        *self.words.get(word).unwrap_or(&Self::DEFAULT)
+    if !settings.budget.is_set() {
+        return Span::default();
     }
+
+    let left = settings.budget.left(game);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let full = (left * WIDTH as f64).ceil() as usize;
+    let colour = if left > 0.3 {
+        Color::Yellow
+    } else {
+        Color::Red
+    };
+
+    Span::styled(
+        format!("  {}{}", "█".repeat(full), "░".repeat(WIDTH - full)),
+        Style::new().fg(colour),
+    )
 }
 
-impl Default for GameSettings<usize> {
-    fn default() -> Self {
-        Self {
-            core: Self::DEFAULT,
-            common: Self::DEFAULT * 200,
-            uncommon: Self::DEFAULT * 400,
-            obscure: Self::DEFAULT * 600,
-            sandbox: Self::DEFAULT * 800,
-            deprecated: Self::DEFAULT * 800,
-            nondeprecated: Self::DEFAULT,
-            words: HashMap::new(),
-            len: 60,
-        }
+/// how much of the words still to type is drawn
+#[derive(Clone, Copy)]
+enum Reveal {
+    All,
+    /// the first few graphemes of each word, the rest as underscores
+    Hint(usize),
+    /// not even how long the words are
+    Blank,
+}
+
+/// how much a test shows of the words still to type, `previewing` while it can still be rerolled
+/// and `memorizing` while the memory preview is on
+const fn reveal(settings: &GameSettings<usize>, previewing: bool, memorizing: bool) -> Reveal {
+    if previewing || memorizing {
+        Reveal::All
+    } else if settings.memory_secs > 0 {
+        Reveal::Blank
+    } else {
+        Reveal::Hint(settings.hint_letters)
     }
 }
 
-struct Game<K> {
-    words: Vec<&'static toml::map::Map<String, toml::Value>>,
-    key_log: Vec<(K, Instant)>,
-    target: String,
-    input: String,
-    spans: Vec<GameSpan<String>>,
+/// text still to type as much as `reveal` shows of it, hidden graphemes keep their width so the
+/// text doesn't shift as it is typed, `column` carries how far into a word the text starts across
+/// calls
+fn mask(text: &str, column: &Cell<usize>, reveal: Reveal) -> String {
+    text.graphemes(true)
+        .map(|grapheme| {
+            if grapheme == " " {
+                column.set(0);
+                return grapheme.to_string();
+            }
+
+            column.set(column.get() + 1);
+
+            match reveal {
+                Reveal::Hint(shown) if column.get() > shown => "_".repeat(grapheme.width()),
+                Reveal::Blank => " ".repeat(grapheme.width()),
+                _ => grapheme.to_string(),
+            }
+        })
+        .collect()
 }
 
-impl Game<KeyCode> {
-    fn new(settings: &GameSettings<usize>) -> Self {
-        let mut words: Vec<_> = WORDS.values().collect();
+/// the spans without the words marked to vanish, spaces in the spans separate the words
+fn shadow(spans: &[GameSpan<String>], vanish: &[bool]) -> Vec<GameSpan<String>> {
+    let word = Cell::new(0);
 
-        words.sort_by_cached_key(|toml| {
-            let category_weight = toml
-                .get("usage_category")
-                .and_then(toml::Value::as_str)
-                .map(|cat| match cat {
-                    "core" => settings.core,
-                    "common" => settings.common,
-                    "uncommon" => settings.uncommon,
-                    "obscure" => settings.obscure,
-                    "sandbox" => settings.sandbox,
-                    _ => todo!(),
-                })
-                .expect("failed to get category");
-
-            let deprecated_weight = toml
-                .get("deprecated")
-                .and_then(toml::Value::as_bool)
-                .map(|b| {
-                    if b {
-                        settings.deprecated
-                    } else {
-                        settings.nondeprecated
-                    }
+    spans
+        .iter()
+        .map(|span| {
+            span.map(|line| {
+                line.chars()
+                    .filter(|c| {
+                        let gone = vanish.get(word.get()).copied().unwrap_or_default();
+
+                        if *c == ' ' {
+                            word.set(word.get() + 1);
+                        }
+
+                        !gone
+                    })
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// the test as glyphs coloured by how each word went, with the word being typed under it,
+/// names are spelled in cartouches and words without a glyph are left in latin letters
+fn sitelen_pona_lines(
+    game: &Game<KeyCode>,
+    [correct, wrong, current, ahead]: [Style; 4],
+) -> Vec<Line<'static>> {
+    let results = game.word_results();
+    let finished = game.input.matches(' ').count();
+
+    let glyphs: Vec<Span> = game
+        .target
+        .split(' ')
+        .enumerate()
+        .flat_map(|(index, word)| {
+            let bare = punctuation::bare(word);
+            let glyph = tt::words::glyph(bare)
+                .map(String::from)
+                .or_else(|| {
+                    bare.starts_with(char::is_uppercase)
+                        .then(|| names::cartouche(bare))
+                        .flatten()
                 })
-                .expect("failed to get deprecation");
+                .unwrap_or_else(|| word.to_string());
+            let style = match results.get(index) {
+                _ if index == finished => current,
+                Some((_, true)) if index < finished => correct,
+                Some(_) if index < finished => wrong,
+                _ => ahead,
+            };
 
-            let word_weight = settings.get_word(
-                toml.get("word")
-                    .and_then(toml::Value::as_str)
-                    .expect("failed to get word field"),
-            );
+            [Span::styled(glyph, style), Span::raw(" ")]
+        })
+        .collect();
 
-            category_weight * deprecated_weight * word_weight * rand::random_range(900..1100)
-        });
+    vec![
+        Line::from(glyphs),
+        Line::default(),
+        Line::from(format!(
+            "> {}",
+            game.input.rsplit(' ').next().unwrap_or_default()
+        ))
+        .bold(),
+    ]
+}
 
-        words.truncate(settings.len);
+/// cut the last word of free writing and the spaces after it, spaces of any width
+fn delete_last_word(text: &mut String) {
+    let end = text.trim_end().len();
+    let start = text[..end]
+        .char_indices()
+        .rfind(|(_, c)| c.is_whitespace())
+        .map_or(0, |(index, c)| index + c.len_utf8());
+    text.truncate(start);
+}
 
-        let mut target = String::new();
-        let mut iter = words
-            .iter()
-            .filter_map(|word| word.get("word"))
-            .filter_map(toml::Value::as_str);
+/// spans don't follow word boundaries, so cut them at the spaces with the space kept on the word
+fn word_spans(spans: Vec<Span>) -> Vec<Vec<Span<'static>>> {
+    let mut words: Vec<Vec<Span<'static>>> = vec![Vec::new()];
+
+    for span in spans {
+        for (index, part) in span.content.split(' ').enumerate() {
+            if index > 0 {
+                if let Some(word) = words.last_mut() {
+                    word.push(Span::styled(" ", span.style));
+                }
+                words.push(Vec::new());
+            }
+
+            if let Some(word) = words.last_mut().filter(|_| !part.is_empty()) {
+                word.push(Span::styled(part.to_string(), span.style));
+            }
+        }
+    }
+
+    words
+}
+
+/// the test one word a line with its gloss beside it
+fn word_lines(spans: Vec<Span>, target: &str) -> Vec<Line<'static>> {
+    let words = word_spans(spans);
+    let targets: Vec<&str> = target.split(' ').collect();
+    let widths: Vec<usize> = words
+        .iter()
+        .map(|word| word.iter().map(Span::width).sum())
+        .collect();
+    let column = widths.iter().max().copied().unwrap_or_default() + 2;
+
+    words
+        .into_iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(index, (word, width))| {
+            let gloss = targets
+                .get(index)
+                .and_then(|target| tt::words::gloss(punctuation::bare(target)))
+                .unwrap_or_default();
+
+            let mut line = Line::from(word);
+            line.push_span(" ".repeat(column - width));
+            line.push_span(Span::styled(gloss, Style::new().dim()));
+            line
+        })
+        .collect()
+}
+
+/// the test wrapped a word at a time with a dim line of glosses under each row,
+/// every word padded to fit its gloss so the gloss starts under it
+fn glossed_lines(spans: Vec<Span>, target: &str, width: u16) -> Vec<Line<'static>> {
+    let words = word_spans(spans);
+    let targets: Vec<&str> = target.split(' ').collect();
+    let width = usize::from(width);
+    let mut lines = Vec::new();
+    let mut row = (Line::default(), Line::default());
+    let mut row_width = 0;
 
-        target.push_str(iter.next().expect("words list was empty"));
-        for word in iter {
-            target.push(' ');
-            target.push_str(word);
+    for (index, word) in words.into_iter().enumerate() {
+        let gloss = targets
+            .get(index)
+            .and_then(|target| tt::words::gloss(punctuation::bare(target)))
+            .unwrap_or_default();
+        let typed: usize = word.iter().map(Span::width).sum();
+        let column = typed.max(gloss.width() + 1);
+
+        if row_width > 0 && row_width + column > width {
+            lines.extend([std::mem::take(&mut row.0), std::mem::take(&mut row.1)]);
+            row_width = 0;
         }
 
-        Self {
-            words,
-            key_log: Vec::new(),
-            target: target.clone(),
-            input: String::new(),
-            spans: Vec::new(),
+        for span in word {
+            row.0.push_span(span);
         }
+        row.0.push_span(" ".repeat(column - typed));
+        row.1
+            .push_span(Span::styled(format!("{gloss:column$}"), Style::new().dim()));
+        row_width += column;
     }
 
-    fn calculate_spans(&mut self) {
-        let mut spans = Vec::new();
+    lines.push(row.0);
+    lines.push(row.1);
+    lines
+}
 
-        let mut targ = self.target.chars().peekable();
-        let mut inpt = self.input.chars().peekable();
+/// the `NO_COLOR` convention, set to anything but empty to turn colours off
+fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
 
-        loop {
-            match (targ.peek(), inpt.peek()) {
-                (Some(t), Some(i)) if t == i => {
-                    spans.push(GameSpan::Correct(*t));
-                    targ.next();
-                    inpt.next();
-                }
-                (Some(t), Some(' ')) => {
-                    spans.push(GameSpan::Skipped(*t));
-                    targ.next();
-                }
-                (Some(' ') | None, Some(i)) => {
-                    spans.push(GameSpan::Overflow(*i));
-                    inpt.next();
-                }
-                (Some(t), Some(i)) => {
-                    spans.push(GameSpan::Wrong(*t));
-                    targ.next();
-                    inpt.next();
-                }
-                (Some(t), None) => {
-                    spans.push(GameSpan::Hidden(if *t == ' ' { ' ' } else { '_' }));
-                    targ.next();
-                }
-                _ => break,
-            }
+/// the words pinned from the dictionary, kept apart from the config so editing it can't lose
+/// them, a file that can't be read pins nothing and its error is collected
+fn load_pins(path: Option<&Path>, errors: &mut Vec<Error>) -> HashMap<String, Pin> {
+    path.map_or(Ok(None), storage::load)
+        .unwrap_or_else(|error| {
+            errors.push(error);
+            None
+        })
+        .unwrap_or_default()
+}
+
+/// a test as it is kept in the history
+fn session(game: &Game<KeyCode>, mode: Mode, finished: bool, passed: Option<bool>) -> Session {
+    Session {
+        id: history::new_id(),
+        date: date::now(),
+        words: game.target.split_whitespace().count(),
+        target: game.target.clone(),
+        input: game.input.clone(),
+        wpm: game.wpm(),
+        accuracy: game.accuracy(),
+        seconds: game.elapsed().as_secs_f64(),
+        best_combo: game.best_combo,
+        finished,
+        strokes: game.saved_strokes(),
+        keystrokes: game.key_log.len(),
+        passed,
+        mode: mode.name().to_string(),
+        tags: Vec::new(),
+        note: String::new(),
+        goals_met: Vec::new(),
+    }
+}
+
+/// a falling words run as it is kept in the history, the words in the order they were
+/// caught or landed
+fn arcade_session(arcade: &Arcade, finished: bool) -> Session {
+    let best_combo = arcade
+        .resolved
+        .split(|(word, typed)| word != typed)
+        .map(<[_]>::len)
+        .max()
+        .unwrap_or_default();
+
+    let target = arcade
+        .resolved
+        .iter()
+        .map(|(word, _)| word.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let input = arcade
+        .resolved
+        .iter()
+        .map(|(_, typed)| typed.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Session {
+        id: history::new_id(),
+        date: date::now(),
+        words: arcade.resolved.len(),
+        target,
+        input,
+        wpm: arcade.wpm(),
+        accuracy: arcade.accuracy(),
+        seconds: arcade.elapsed.as_secs_f64(),
+        best_combo,
+        finished,
+        strokes: arcade.strokes.clone(),
+        keystrokes: arcade.strokes.len(),
+        passed: None,
+        // not a mode a restart can start, so it has no `Mode`
+        mode: "arcade".to_string(),
+        tags: Vec::new(),
+        note: String::new(),
+        goals_met: Vec::new(),
+    }
+}
+
+/// mark the goals the last session completed on it
+fn record_goals(history: &mut History, goals: &Goals) {
+    let completed = goals.completed(&history.sessions, date::now());
+
+    if let Some(session) = history.sessions.last_mut() {
+        session.goals_met = completed
+            .into_iter()
+            .map(|period| period.name().to_string())
+            .collect();
+    }
+}
+
+/// path of a file in the config directory, a profile keeps its config with its data
+fn config_path(profile: Option<&str>, file: &str) -> Option<PathBuf> {
+    match profile {
+        Some(_) => data_path(profile, file),
+        None => std::env::var_os("TT_CONFIG_DIR")
+            .map(PathBuf::from)
+            .or_else(|| {
+                directories::ProjectDirs::from("", "", APPLICATION)
+                    .map(|dirs| dirs.config_dir().to_path_buf())
+            })
+            .map(|dir| dir.join(file)),
+    }
+}
+
+/// the config.toml files read, the global one then the profile's
+/// the key sounds a config asks for, from the sounds directory next to the global config
+fn sounds(config: &Config) -> Option<Soundpack> {
+    config_path(None, "sounds").and_then(|dir| Soundpack::load(&dir, &config.sound))
+}
+
+fn config_paths(profile: Option<&str>) -> Vec<PathBuf> {
+    [
+        config_path(None, "config.toml"),
+        profile.and_then(|profile| config_path(Some(profile), "config.toml")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// add the words from words.toml in the config directory and the profile's to the dictionary,
+/// a word in both is taken from the profile's
+fn load_user_words(profile: Option<&str>, errors: &mut Vec<Error>) {
+    let mut words = HashMap::new();
+
+    for path in [
+        config_path(None, "words.toml"),
+        profile.and_then(|profile| config_path(Some(profile), "words.toml")),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        match storage::load::<HashMap<String, tt::words::UserWord>>(&path) {
+            Ok(layer) => words.extend(layer.unwrap_or_default()),
+            Err(error) => errors.push(error),
         }
+    }
 
-        let mut spans = spans.iter().peekable();
-        self.spans.clear();
+    tt::words::add_user_words(words);
+}
 
-        loop {
-            match (self.spans.last_mut(), spans.peek()) {
-                (Some(GameSpan::Correct(s_span)), Some(GameSpan::Correct(c_span)))
-                | (Some(GameSpan::Wrong(s_span)), Some(GameSpan::Wrong(c_span)))
-                | (Some(GameSpan::Overflow(s_span)), Some(GameSpan::Overflow(c_span)))
-                | (Some(GameSpan::Skipped(s_span)), Some(GameSpan::Skipped(c_span)))
-                | (Some(GameSpan::Hidden(s_span)), Some(GameSpan::Hidden(c_span))) => {
-                    s_span.push(*c_span);
-                    spans.next();
-                }
-                (_, Some(c_span)) => {
-                    self.spans
-                        .push(c_span.map(std::string::ToString::to_string));
-                    spans.next();
-                }
-                _ => break,
-            }
+/// path of a file in the data directory, or in the directory of a profile
+fn data_path(profile: Option<&str>, file: &str) -> Option<PathBuf> {
+    std::env::var_os("TT_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(|| {
+            directories::ProjectDirs::from("", "", APPLICATION)
+                .map(|dirs| dirs.data_dir().to_path_buf())
+        })
+        .map(|dir| {
+            profile.map_or_else(
+                || dir.join(file),
+                |profile| dir.join("profiles").join(profile).join(file),
+            )
+        })
+}
+
+const fn is_narrow(area: Rect) -> bool {
+    area.width < NARROW_WIDTH
+}
+
+/// a list with its info panel on the right, or under it on narrow terminals
+fn list_and_info(area: Rect, list_width: u16) -> [Rect; 2] {
+    if is_narrow(area) {
+        Layout::vertical([Constraint::Fill(1), Constraint::Length(NARROW_INFO_HEIGHT)]).areas(area)
+    } else {
+        Layout::horizontal([Constraint::Length(list_width), Constraint::Fill(1)]).areas(area)
+    }
+}
+
+/// a rect of at most the given size in the middle of area
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+
+    area
+}
+
+#[derive(Clone, Copy)]
+enum MenuItem {
+    Start,
+    Custom,
+    Numbers,
+    Names,
+    Cartouches,
+    WeakBigrams,
+    TimeAttack,
+    Survival,
+    Arcade,
+    Quiz,
+    Writing,
+    Lessons,
+    Dictionary,
+    Stats,
+    About,
+    Quit,
+}
+
+impl MenuItem {
+    const ALL: [Self; 16] = [
+        Self::Start,
+        Self::Custom,
+        Self::Numbers,
+        Self::Names,
+        Self::Cartouches,
+        Self::WeakBigrams,
+        Self::TimeAttack,
+        Self::Survival,
+        Self::Arcade,
+        Self::Quiz,
+        Self::Writing,
+        Self::Lessons,
+        Self::Dictionary,
+        Self::Stats,
+        Self::About,
+        Self::Quit,
+    ];
+
+    const fn label(self) -> Message {
+        match self {
+            Self::Start => Message::StartTest,
+            Self::Custom => Message::CustomTest,
+            Self::Numbers => Message::NumbersDrill,
+            Self::Names => Message::NamePacks,
+            Self::Cartouches => Message::Cartouches,
+            Self::WeakBigrams => Message::WeakBigrams,
+            Self::TimeAttack => Message::TimeAttack,
+            Self::Survival => Message::Survival,
+            Self::Arcade => Message::FallingWords,
+            Self::Quiz => Message::ReverseQuiz,
+            Self::Writing => Message::FreeWriting,
+            Self::Lessons => Message::Lessons,
+            Self::Dictionary => Message::Dictionary,
+            Self::Stats => Message::Stats,
+            Self::About => Message::About,
+            Self::Quit => Message::Quit,
         }
     }
+}
 
-    fn crossterm_event(&mut self, event: &Event) {
-        if let Event::Key(key_event) = event {
-            self.key_log.push((key_event.code, Instant::now()));
+#[derive(Clone, Copy)]
+enum PauseItem {
+    Resume,
+    Restart,
+    Settings,
+    Quit,
+}
 
-            match key_event.code {
-                KeyCode::Char(c) => self.input.push(c),
-                KeyCode::Backspace => _ = self.input.pop(),
-                _ => (),
-            }
+impl PauseItem {
+    const ALL: [Self; 4] = [Self::Resume, Self::Restart, Self::Settings, Self::Quit];
+
+    const fn label(self) -> Message {
+        match self {
+            Self::Resume => Message::Resume,
+            Self::Restart => Message::Restart,
+            Self::Settings => Message::Settings,
+            Self::Quit => Message::Quit,
+        }
+    }
+}
+
+/// settings that can be flipped from the pause menu, for the rest of the session only
+#[derive(Clone, Copy)]
+enum SettingItem {
+    StrictSpace,
+    SpaceCommits,
+    Forgiving,
+    AutoSpace,
+    Shadow,
+    WordPerLine,
+    SitelenPona,
+}
+
+impl SettingItem {
+    const ALL: [Self; 7] = [
+        Self::StrictSpace,
+        Self::SpaceCommits,
+        Self::Forgiving,
+        Self::AutoSpace,
+        Self::Shadow,
+        Self::WordPerLine,
+        Self::SitelenPona,
+    ];
+
+    const fn label(self) -> Message {
+        match self {
+            Self::StrictSpace => Message::StrictSpace,
+            Self::SpaceCommits => Message::SpaceCommits,
+            Self::Forgiving => Message::Forgiving,
+            Self::AutoSpace => Message::AutoSpace,
+            Self::Shadow => Message::Shadow,
+            Self::WordPerLine => Message::WordPerLine,
+            Self::SitelenPona => Message::SitelenPona,
+        }
+    }
+
+    const fn is_on(self, settings: &GameSettings<usize>) -> bool {
+        match self {
+            Self::StrictSpace => settings.strict_space,
+            Self::SpaceCommits => settings.space_commits,
+            Self::Forgiving => settings.forgiving,
+            Self::AutoSpace => settings.auto_space,
+            Self::Shadow => settings.shadow,
+            Self::WordPerLine => settings.word_per_line,
+            Self::SitelenPona => settings.sitelen_pona,
+        }
+    }
+
+    const fn value_mut(self, settings: &mut GameSettings<usize>) -> &mut bool {
+        match self {
+            Self::StrictSpace => &mut settings.strict_space,
+            Self::SpaceCommits => &mut settings.space_commits,
+            Self::Forgiving => &mut settings.forgiving,
+            Self::AutoSpace => &mut settings.auto_space,
+            Self::Shadow => &mut settings.shadow,
+            Self::WordPerLine => &mut settings.word_per_line,
+            Self::SitelenPona => &mut settings.sitelen_pona,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum ResumeItem {
+    Resume,
+    New,
+}
+
+impl ResumeItem {
+    const ALL: [Self; 2] = [Self::Resume, Self::New];
+
+    const fn label(self) -> Message {
+        match self {
+            Self::Resume => Message::ResumeLast,
+            Self::New => Message::StartNew,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum QuitItem {
+    Save,
+    Discard,
+    Cancel,
+}
+
+impl QuitItem {
+    const ALL: [Self; 3] = [Self::Save, Self::Discard, Self::Cancel];
+
+    const fn label(self) -> Message {
+        match self {
+            Self::Save => Message::SaveAndQuit,
+            Self::Discard => Message::QuitWithoutSaving,
+            Self::Cancel => Message::Cancel,
         }
+    }
+}
+
+/// what kind of test a restart starts
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Words,
+    Numbers,
+    WeakBigrams,
+    /// the lesson in `App::lesson`
+    Lesson,
+    /// words with the word of the day every few words
+    WordOfTheDay,
+    /// a short clock that every right word adds to
+    TimeAttack,
+    /// keeping up with a pace that keeps rising
+    Survival,
+    /// words with the length in `App::custom`
+    Custom,
+    /// names from `App::pack` with their head words
+    Names,
+    /// names alone, always shown as sitelen pona cartouches
+    Cartouches,
+}
+
+impl Mode {
+    const ALL: [Self; 10] = [
+        Self::Words,
+        Self::Numbers,
+        Self::WeakBigrams,
+        Self::Lesson,
+        Self::WordOfTheDay,
+        Self::TimeAttack,
+        Self::Survival,
+        Self::Custom,
+        Self::Names,
+        Self::Cartouches,
+    ];
 
-        self.calculate_spans();
+    /// as it is kept in the history
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Words => "words",
+            Self::Numbers => "numbers",
+            Self::WeakBigrams => "weak_bigrams",
+            Self::Lesson => "lesson",
+            Self::WordOfTheDay => "word_of_the_day",
+            Self::TimeAttack => "time_attack",
+            Self::Survival => "survival",
+            Self::Custom => "custom",
+            Self::Names => "names",
+            Self::Cartouches => "cartouches",
+        }
     }
+}
 
-    fn draw_game_ratatui<B: ratatui::backend::Backend>(&self, terminal: &mut ratatui::Terminal<B>) {
-        const CORRECT: Style = Style::new().fg(Color::Green);
+/// the order of the dictionary list
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DictionarySort {
+    Name,
+    /// pinned words first, then by name
+    Pin,
+}
 
-        const WRONG: Style = Style::new()
-            .fg(Color::Red)
-            .add_modifier(Modifier::UNDERLINED)
-            .add_modifier(Modifier::BOLD);
+/// the right side of the stats screen
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StatsTab {
+    Summary,
+    /// finger usage of the selected test
+    Fingers,
+    /// wpm of every test counted with its trend
+    Progress,
+}
 
-        const OVERFLOW: Style = Style::new().fg(Color::Yellow);
+enum Screen {
+    Menu,
+    Lessons,
+    Dictionary,
+    Stats,
+    Game,
+    /// the game with the pause menu over it, holds the selected item
+    Pause(usize),
+    /// the settings flipped from the pause menu, holds the selected item
+    PauseSettings(usize),
+    /// asks before abandoning a test in progress, holds the selected item
+    ConfirmQuit(usize),
+    /// a saved game was found on launch, holds the selected item
+    ResumePrompt(usize),
+    Results,
+    /// every word of the finished test
+    Review,
+    /// the falling words in `App::arcade`
+    Arcade,
+    /// english glosses to answer in toki pona, from `App::quiz`
+    Quiz,
+    /// typing anything into `App::writing`, nothing to copy
+    Writing,
+    /// the main menu with the name packs over it, holds the selected pack
+    Packs(usize),
+    /// the version and the word data it was built with
+    About,
+}
 
-        const SKIPPED: Style = Style::new().fg(Color::LightRed);
+#[allow(clippy::struct_excessive_bools)]
+struct App {
+    settings: GameSettings<usize>,
+    game: Game<KeyCode>,
+    mode: Mode,
+    /// shared so others can type the same test, None when the test can't be reproduced
+    code: Option<TestCode>,
+    /// drilling the words missed in the last test until none are left
+    practicing: bool,
+    history: History,
+    history_path: Option<PathBuf>,
+    /// what the history may keep, taken out each time it is saved
+    privacy: Privacy,
+    hooks: Hooks,
+    /// the key sounds of the theme in the config, None without one
+    sounds: Option<Soundpack>,
+    /// the command making the words of word and custom tests, empty to pick them
+    generator: String,
+    snapshot_path: Option<PathBuf>,
+    /// a saved game waiting for the user to decide whether to resume it
+    snapshot: Option<Snapshot>,
+    last_autosave: Instant,
+    screen: Screen,
+    menu: ListView,
+    dictionary: ListView,
+    stats: ListView,
+    stats_filter: StatsFilter,
+    review: ListView,
+    /// lesson files from the lessons directory next to the config
+    lessons: Vec<Lesson>,
+    lesson_list: ListView,
+    /// the lesson being played
+    lesson: Option<Lesson>,
+    /// picked by the date, shown on the menu
+    word_of_the_day: Option<&'static str>,
+    stats_tab: StatsTab,
+    /// every word in the dictionary, sorted
+    dictionary_words: Vec<&'static str>,
+    dictionary_sort: DictionarySort,
+    /// pins set from the dictionary, saved apart from the config
+    pinned: HashMap<String, Pin>,
+    pins_path: Option<PathBuf>,
+    keymap: Keymap,
+    layout: KeyboardLayout,
+    /// no colours anywhere
+    monochrome: bool,
+    /// only draw when something changed
+    reduced_motion: bool,
+    /// the profile from the command line, None for the default one
+    profile: Option<String>,
+    /// show the word information panels while typing
+    panels: bool,
+    /// show an english gloss under each word while typing
+    glosses: bool,
+    locale: Locale,
+    /// the key help is shown over the screen
+    help: bool,
+    /// tags and a note being written for the test just saved
+    annotation: Option<Annotation>,
+    /// running the first launch tutorial
+    tutorial: Option<Tutorial>,
+    /// where game events are written as they happen
+    events: Option<EventLog>,
+    /// the stats of the test being typed, kept in a file for stream overlays
+    live_stats: Option<LiveStats>,
+    /// websocket clients the game events are sent to as well
+    event_server: Option<EventServer>,
+    /// reloads the config when its files change
+    config_watch: Option<ConfigWatch>,
+    last_config_check: Instant,
+    /// the falling words run, kept after it is over for its score
+    arcade: Option<Arcade>,
+    last_arcade_tick: Instant,
+    /// the reverse quiz, kept after it is over for its answers
+    quiz: Option<Quiz>,
+    /// when the current test was put on screen, for the memory preview
+    shown_at: Instant,
+    /// the test is shown to be rerolled or accepted before it can be typed
+    previewing: bool,
+    /// the name pack of `Mode::Names`
+    pack: Pack,
+    /// the last custom test length, saved apart from the config
+    custom: CustomTest,
+    custom_path: Option<PathBuf>,
+    /// a custom test length being typed
+    custom_entry: Option<CustomEntry>,
+    /// the free writing text, kept between visits
+    writing: String,
+    /// mark grammar mistakes in the free writing
+    check_grammar: bool,
+    errors: Vec<Error>,
+    running: bool,
+}
 
-        const HIDDEN: Style = Style::new();
+impl App {
+    #[allow(clippy::too_many_lines)]
+    fn new(config: Config, profile: Option<String>, mut errors: Vec<Error>) -> Result<Self> {
+        let sounds = sounds(&config);
+        let mut settings = config.game;
+
+        let pins_path = data_path(profile.as_deref(), "pins.toml");
+
+        let pinned = load_pins(pins_path.as_deref(), &mut errors);
+
+        let custom_path = data_path(profile.as_deref(), "custom.toml");
+        let custom = custom_path
+            .as_deref()
+            .map_or(Ok(None), storage::load)
+            .unwrap_or_else(|error| {
+                errors.push(error);
+                None
+            })
+            .unwrap_or_default();
+
+        settings.pins.extend(pinned.clone());
+
+        let mut history_path = data_path(profile.as_deref(), "history.toml");
+        let first_run = history_path.as_deref().is_some_and(|path| !path.exists());
+
+        let history = history_path
+            .as_deref()
+            .map_or_else(|| Ok(History::default()), History::load)
+            .unwrap_or_else(|error| {
+                errors.push(error);
+                // saving would write an empty history over the one that couldn't be read,
+                // it is left as it is to be fixed and this run's results aren't kept
+                history_path = None;
+                History::default()
+            });
+
+        let snapshot_path = data_path(profile.as_deref(), "session.toml");
+
+        let snapshot = snapshot_path
+            .as_deref()
+            .map_or(Ok(None), Snapshot::load)
+            .unwrap_or_else(|error| {
+                errors.push(error);
+                None
+            });
+
+        let tutorial = (first_run && snapshot.is_none()).then(Tutorial::new);
+
+        let lessons = config_path(None, "lessons")
+            .map(|dir| Lesson::load_dir(&dir, &mut errors))
+            .unwrap_or_default();
+
+        Ok(Self {
+            game: if tutorial.is_some() {
+                Game::new(
+                    &GameSettings {
+                        len: Tutorial::WORDS,
+                        ..settings.clone()
+                    },
+                    &mut rand::rng(),
+                )?
+            } else {
+                Game::new(&settings, &mut rand::rng())?
+            },
+            code: None,
+            settings,
+            mode: Mode::Words,
+            practicing: false,
+            history,
+            history_path,
+            privacy: config.privacy,
+            hooks: config.hooks.clone(),
+            sounds,
+            generator: config.generator.clone(),
+            snapshot_path,
+            screen: if snapshot.is_some() {
+                Screen::ResumePrompt(0)
+            } else if tutorial.is_some() {
+                Screen::Game
+            } else {
+                Screen::Menu
+            },
+            tutorial,
+            menu: ListView::default(),
+            dictionary: ListView::default(),
+            stats: ListView::default(),
+            stats_filter: StatsFilter::default(),
+            review: ListView::default(),
+            lessons,
+            lesson_list: ListView::default(),
+            lesson: None,
+            word_of_the_day: tt::words::word_of_the_day(date::now() / date::SECS_PER_DAY),
+            stats_tab: StatsTab::Summary,
+            dictionary_words: {
+                let mut words: Vec<&str> = WORDS.keys().map(String::as_str).collect();
+                words.sort_unstable();
+                words
+            },
+            dictionary_sort: DictionarySort::Name,
+            pinned,
+            pins_path,
+            snapshot,
+            last_autosave: Instant::now(),
+            keymap: Keymap::new(&config.keys),
+            layout: config.layout,
+            monochrome: config.monochrome || no_color(),
+            reduced_motion: config.reduced_motion,
+            profile,
+            panels: true,
+            glosses: config.glosses,
+            locale: config.locale,
+            help: false,
+            annotation: None,
+            events: None,
+            live_stats: None,
+            event_server: None,
+            config_watch: None,
+            last_config_check: Instant::now(),
+            arcade: None,
+            last_arcade_tick: Instant::now(),
+            quiz: None,
+            shown_at: Instant::now(),
+            previewing: false,
+            pack: Pack::People,
+            custom,
+            custom_path,
+            custom_entry: None,
+            writing: String::new(),
+            check_grammar: config.check_grammar,
+            errors,
+            running: true,
+        })
+    }
+
+    /// start again on exactly the same words
+    fn repeat(&mut self) {
+        let code = self.code;
+
+        self.start(Game {
+            prompts: self.game.prompts.clone(),
+            ..Game::from_target(self.game.target.clone())
+        });
+        self.code = code;
+    }
+
+    /// switch to a new game with the current settings
+    fn start(&mut self, game: Game<KeyCode>) {
+        self.discard_snapshot();
+        self.code = None;
+        self.game = Game {
+            rules: (&self.settings).into(),
+            ..game
+        };
+        self.shown_at = Instant::now();
+        self.previewing = self.settings.preview;
+        self.screen = Screen::Game;
+    }
+
+    /// the keys to reroll or accept the test while it is previewed
+    fn preview_hint(&self) -> String {
+        if !self.previewing {
+            return String::new();
+        }
+
+        let reroll = self
+            .keymap
+            .key(Context::Game, Action::Restart)
+            .map(|key| format!(", {}", self.locale.fill(Message::NewWords, &[&key])))
+            .unwrap_or_default();
+
+        let begin = self
+            .keymap
+            .key(Context::Game, Action::Begin)
+            .map(|key| self.locale.fill(Message::ToBegin, &[&key]))
+            .unwrap_or_default();
+
+        format!("  {begin}{reroll}")
+    }
+
+    /// the keys that start another round of a game that is over or go back to the menu
+    fn play_again_hint(&self) -> String {
+        let key = |action| {
+            self.keymap
+                .key(Context::Menu, action)
+                .map(|key| key.to_string())
+                .unwrap_or_default()
+        };
+
+        format!(
+            " {} ",
+            self.locale.fill(
+                Message::PlayAgain,
+                &[&key(Action::Select), &key(Action::Back)]
+            )
+        )
+    }
+
+    /// the whole test is on screen to be memorized, until the preview runs out or typing starts
+    fn memorizing(&self) -> bool {
+        self.settings.memory_secs > 0
+            && self.game.key_log.is_empty()
+            && self.shown_at.elapsed() < Duration::from_secs(self.settings.memory_secs)
+    }
+
+    /// start a short test of only the words that had mistakes, returns false if there were none
+    fn practice_missed(&mut self) -> bool {
+        let mut missed: Vec<&str> = self
+            .game
+            .word_results()
+            .into_iter()
+            .filter(|(_, correct)| !correct)
+            .map(|(word, _)| word)
+            .collect();
+
+        missed.sort_unstable();
+        missed.dedup();
+
+        if missed.is_empty() {
+            return false;
+        }
+
+        missed.shuffle(&mut rand::rng());
+        let target = missed.join(" ");
+
+        self.start(Game::from_target(target));
+        self.practicing = true;
+
+        true
+    }
+
+    fn restart(&mut self) -> Result<()> {
+        self.practicing = false;
+
+        let seed = rand::random();
+        let mut rng = code::rng(seed);
+
+        self.start(match self.mode {
+            Mode::Words | Mode::Custom if !self.generator.trim().is_empty() => {
+                let settings = if self.mode == Mode::Custom {
+                    self.custom.settings(&self.settings)
+                } else {
+                    self.settings.clone()
+                };
+
+                Game::from_target(generator::generate(&self.generator, &settings, seed)?)
+            }
+            Mode::Words => Game::new(&self.settings, &mut rng)?,
+            Mode::Numbers => Game::numbers(&self.settings, &mut rng),
+            Mode::WeakBigrams => Game::weak_bigrams(&self.settings, &self.history, &mut rng)?,
+            Mode::Lesson => Game::from_target(
+                self.lesson
+                    .as_ref()
+                    .ok_or(Error::NoWords)?
+                    .target(self.settings.len, &mut rng)?,
+            ),
+            Mode::TimeAttack => Game::new(
+                &GameSettings {
+                    len: time_attack::WORDS,
+                    ..self.settings.clone()
+                },
+                &mut rng,
+            )?,
+            Mode::Survival => Game::new(
+                &GameSettings {
+                    len: survival::WORDS,
+                    ..self.settings.clone()
+                },
+                &mut rng,
+            )?,
+            Mode::Custom => Game::new(&self.custom.settings(&self.settings), &mut rng)?,
+            Mode::Names => Game::names(&self.settings, self.pack, &mut rng),
+            Mode::Cartouches => Game::cartouches(&self.settings, &mut rng),
+            Mode::WordOfTheDay => Game::featuring(
+                &self.settings,
+                self.word_of_the_day.ok_or(Error::NoWords)?,
+                &mut rng,
+            )?,
+        });
+
+        self.code = match self.mode {
+            // the generator's text can't be made again from a code
+            Mode::Words if !self.generator.trim().is_empty() => None,
+            Mode::Words => TestCode::new(seed, &self.settings, false),
+            Mode::Numbers => TestCode::new(seed, &self.settings, true),
+            Mode::WeakBigrams
+            | Mode::Lesson
+            | Mode::WordOfTheDay
+            | Mode::TimeAttack
+            | Mode::Survival
+            | Mode::Custom
+            | Mode::Names
+            | Mode::Cartouches => None,
+        };
+
+        Ok(())
+    }
+
+    fn play_lesson(&mut self, lesson: Lesson) -> Result<()> {
+        self.lesson = Some(lesson);
+        self.mode = Mode::Lesson;
+        self.tutorial = None;
 
-        let current_index = self.input.chars().filter(|c| *c == ' ').count();
-        let mut words = self.target.split_whitespace();
+        self.restart()
+    }
 
-        let word_1 = if self.input.ends_with(' ') {
-            words.nth(current_index)
+    /// start the test a code was made from, with the code's settings from now on
+    fn play_code(&mut self, code: TestCode) -> Result<()> {
+        self.settings = code.settings(&self.settings);
+        self.mode = if code.numbers {
+            Mode::Numbers
         } else {
-            words.nth(current_index.checked_sub(1).unwrap_or_default())
+            Mode::Words
         };
+        self.tutorial = None;
+        self.practicing = false;
 
-        let word_2 = words.next();
+        self.start(if code.numbers {
+            Game::numbers(&self.settings, &mut code.rng())
+        } else {
+            Game::new(&self.settings, &mut code.rng())?
+        });
+        self.code = Some(code);
 
-        terminal
-            .draw(|frame| {
-                let [top, main] = Layout::new(Vertical, [Constraint::Fill(1), Constraint::Fill(3)])
-                    .areas(frame.area());
-                let [top_l, top_r] =
-                    Layout::new(Horizontal, [Constraint::Fill(1), Constraint::Fill(1)]).areas(top);
-
-                let ratatui_spans = self.spans.iter().map(|span| match span {
-                    GameSpan::Correct(line) => Span::styled(line, CORRECT),
-                    GameSpan::Wrong(line) => Span::styled(line, WRONG),
-                    GameSpan::Overflow(line) => Span::styled(line, OVERFLOW),
-                    GameSpan::Skipped(line) => Span::styled(line, SKIPPED),
-                    GameSpan::Hidden(line) => Span::styled(line, HIDDEN),
-                });
+        Ok(())
+    }
 
-                for (word, area) in [(word_1, top_l), (word_2, top_r)] {
-                    if let Some(toml) = word.and_then(|w| WORDS.get(w)) {
-                        frame.render_widget(
-                            Paragraph::new(
-                                [
-                                    toml.get("definition")
-                                        .map(toml::Value::to_string)
-                                        .map(|s| "DEFINITION ".to_string() + s.trim_matches('\"')),
-                                    Some(String::new()),
-                                    toml.get("pu_verbatim")
-                                        .and_then(|value| value.get("en"))
-                                        .map(toml::Value::to_string)
-                                        .map(|s| s.trim_matches('\"').to_string()),
-                                    Some(String::new()),
-                                    toml.get("ku_data").and_then(|value| value.as_table()).map(
-                                        |table| {
-                                            table.keys().fold("KU DATA".to_string(), |mut s, k| {
-                                                s.push(' ');
-                                                s.push_str(k);
-                                                s
-                                            })
-                                        },
-                                    ),
-                                ]
-                                .iter()
-                                .flatten()
-                                .map(Line::raw)
-                                .collect::<Text>(),
-                            )
-                            .wrap(Wrap { trim: false })
-                            .block(Block::bordered()),
-                            area,
-                        );
-                    }
-                }
+    /// the results the current test needs, a lesson's own over the settings
+    fn pass_criteria(&self) -> Option<PassCriteria> {
+        self.lesson
+            .as_ref()
+            .filter(|_| self.mode == Mode::Lesson)
+            .map(|lesson| lesson.pass)
+            .filter(PassCriteria::is_set)
+            .or_else(|| Some(self.settings.pass).filter(PassCriteria::is_set))
+    }
 
-                frame.render_widget(
-                    Paragraph::new(ratatui_spans.collect::<Line>()).wrap(Wrap::default()),
-                    main,
-                );
-            })
-            .expect("failed to draw frame");
+    /// whether the current test passed, None when there is nothing to pass,
+    /// a spent error budget fails it whatever else there is
+    fn passed(&self) -> Option<bool> {
+        if self.settings.budget.is_spent(&self.game) {
+            return Some(false);
+        }
+
+        self.pass_criteria()
+            .map(|pass| pass.passed(self.game.wpm(), self.game.accuracy()))
     }
-}
 
-fn main() {
-    let mut terminal = ratatui::init();
+    /// save the game in progress so it can be resumed after a crash
+    fn autosave(&mut self) {
+        self.last_autosave = Instant::now();
 
-    ratatui::crossterm::execute!(
-        terminal.backend_mut(),
-        ratatui::crossterm::event::EnableMouseCapture
-    );
+        if self.game.key_log.is_empty() || self.game.is_finished() {
+            return;
+        }
 
-    // get user history
-    // let history_path = directories::ProjectDirs::from("", "", APPLICATION)
-    //     .map(|base_dirs| {
-    //         if !base_dirs.config_dir().exists() {
-    //             std::fs::create_dir_all(base_dirs.config_dir());
-    //         }
-
-    //         base_dirs.config_dir().to_path_buf()
-    //     })
-    //     .unwrap()
-    //     .join("config.toml");
-
-    // parse user profile
-    // let history: std::collections::HashMap<String, Vec<WordResults>> =
-    //     std::fs::read_to_string(&history_path)
-    //         .map(|data| toml::from_str(&data).ok())
-    //         .ok()
-    //         .flatten()
-    //         .unwrap();
-
-    // initialization
-    let mut game: Game<KeyCode> = Game::new(&GameSettings::default());
-
-    // game
-    loop {
-        let event = ratatui::crossterm::event::read().expect("failed to read event");
-
-        if let Event::Key(
-            KeyEvent {
-                code: KeyCode::Esc, ..
-            }
-            | KeyEvent {
-                code: KeyCode::Char('c' | 'd'),
-                modifiers: KeyModifiers::CONTROL,
-                ..
-            },
-        ) = event
-        {
-            break;
+        // the snapshot is the keys typed so far
+        if !self.privacy.keeps_keystrokes() {
+            return;
+        }
+
+        if let Some(path) = &self.snapshot_path {
+            let snapshot = Snapshot {
+                mode: self.mode.name().to_string(),
+                custom_len: self.custom.len,
+                custom_secs: self.custom.time_secs,
+                pack: self.pack.name().to_string(),
+                lesson: self.lesson.clone(),
+                ..self.game.snapshot(&self.settings)
+            };
+
+            if let Err(error) = snapshot.save(path) {
+                self.errors.push(error);
+                // don't repeat the same error every few seconds
+                self.snapshot_path = None;
+            }
         }
+    }
 
-        game.crossterm_event(&event);
-        game.draw_game_ratatui(&mut terminal);
+    fn discard_snapshot(&mut self) {
+        if let Some(path) = &self.snapshot_path {
+            if let Err(error) = Snapshot::remove(path) {
+                self.errors.push(error);
+            }
+        }
     }
 
-    // results
+    fn exit(&mut self) {
+        self.discard_snapshot();
+        self.running = false;
+    }
 
-    // write user data to file
-    // std::fs::File::create(&history_path)
-    //     .unwrap()
-    //     .write(toml::to_string(&history).unwrap().as_bytes());
+    fn resume(&mut self) {
+        self.game.resume();
+        self.screen = Screen::Game;
+    }
 
-    ratatui::crossterm::execute!(
-        terminal.backend_mut(),
-        ratatui::crossterm::event::DisableMouseCapture
-    );
+    /// quit, asking first if a test is in progress
+    fn quit(&mut self) {
+        let in_progress = matches!(
+            self.screen,
+            Screen::Game | Screen::Pause(_) | Screen::PauseSettings(_)
+        ) && !self.game.key_log.is_empty();
 
-    ratatui::restore();
+        if in_progress {
+            self.game.pause();
+            self.screen = Screen::ConfirmQuit(0);
+        } else {
+            self.exit();
+        }
+    }
+
+    fn emit(&mut self, event: &GameEvent) {
+        if let Some(server) = &self.event_server {
+            server.broadcast(event);
+        }
+
+        if let Some(events) = &mut self.events {
+            if let Err(error) = events.emit(event) {
+                self.errors.push(error);
+                // don't repeat the same error on every key
+                self.events = None;
+            }
+        }
+    }
+
+    /// events for a key the game just handled, given the words done and strokes before it
+    fn emit_key_events(&mut self, key_event: &KeyEvent, words: usize, strokes: usize) {
+        if self.events.is_none() && self.event_server.is_none() {
+            return;
+        }
+
+        let ms = self.game.millis(self.game.clock.now());
+        let mut events = vec![GameEvent::Keystroke {
+            key: keymap::Key::from(key_event).to_string(),
+            ms,
+        }];
+
+        if let Some((typed, false, _, word)) = self.game.strokes.get(strokes) {
+            events.push(GameEvent::Error {
+                typed: *typed,
+                word: self.game.target.split(' ').nth(*word).unwrap_or_default(),
+                ms,
+            });
+        }
+
+        // the last word has no space after it, finishing completes it
+        let done = if self.game.is_finished() {
+            usize::MAX
+        } else {
+            self.game.input.matches(' ').count()
+        };
+
+        let results = self.game.word_results();
+
+        for (word, correct) in results.iter().take(done).skip(words) {
+            events.push(GameEvent::WordCompleted {
+                word,
+                correct: *correct,
+                ms,
+            });
+        }
+
+        if let Some(server) = &self.event_server {
+            for event in &events {
+                server.broadcast(event);
+            }
+        }
+
+        if let Some(Err(error)) = self
+            .events
+            .as_mut()
+            .map(|log| events.iter().try_for_each(|event| log.emit(event)))
+        {
+            self.errors.push(error);
+            self.events = None;
+        }
+    }
+
+    /// the theme's sound for a key the game just handled, given the words done and strokes before it
+    fn play_key_sound(&mut self, words: usize, strokes: usize) {
+        let Some(sounds) = &self.sounds else {
+            return;
+        };
+
+        let Some(sound) = Sound::for_key(&self.game, words, strokes) else {
+            return;
+        };
+
+        if let Err(error) = sounds.play(sound) {
+            self.errors.push(error);
+            // a player that can't start won't on the next key either
+            self.sounds = None;
+        }
+    }
+
+    /// add the current game to the history and write it to disk
+    fn save_session(&mut self, finished: bool) -> Result<()> {
+        self.emit(&GameEvent::TestEnd {
+            wpm: self.game.wpm(),
+            accuracy: self.game.accuracy(),
+            seconds: self.game.elapsed().as_secs_f64(),
+            finished,
+        });
+
+        self.history.sessions.push(session(
+            &self.game,
+            self.mode,
+            finished,
+            self.passed().filter(|_| finished),
+        ));
+
+        record_goals(&mut self.history, &self.settings.goals);
+
+        self.save_history()
+    }
+
+    /// write the history and start the after save hook
+    fn save_history(&mut self) -> Result<()> {
+        let Some(path) = &self.history_path else {
+            return Ok(());
+        };
+
+        self.history.save(path, self.privacy)?;
+        self.hooks.after_save(path)
+    }
+
+    /// put the tags and note on the last session, the one the results are for
+    fn save_annotation(&mut self) {
+        let Some(annotation) = self.annotation.take() else {
+            return;
+        };
+
+        if let Some(session) = self.history.sessions.last_mut() {
+            annotation.apply(session);
+        }
+
+        if let Err(error) = self.save_history() {
+            self.errors.push(error);
+        }
+    }
+
+    /// use a reloaded config, the test in progress keeps its words until the next one
+    fn apply_config(&mut self, config: Config) {
+        self.sounds = sounds(&config);
+        self.settings = config.game;
+        self.settings.pins.extend(self.pinned.clone());
+        self.keymap = Keymap::new(&config.keys);
+        self.layout = config.layout;
+        self.monochrome = config.monochrome || no_color();
+        self.reduced_motion = config.reduced_motion;
+        self.check_grammar = config.check_grammar;
+        self.glosses = config.glosses;
+        self.locale = config.locale;
+        self.privacy = config.privacy;
+        self.hooks = config.hooks;
+        self.generator = config.generator;
+    }
+
+    /// called once per loop whether or not there was input, true when it changed the screen
+    fn tick(&mut self) -> bool {
+        let errors = self.errors.len();
+        let afk = self.game.afk;
+        let mut changed = false;
+
+        if self.last_config_check.elapsed() >= CONFIG_CHECK {
+            self.last_config_check = Instant::now();
+
+            if let Some(config) = self
+                .config_watch
+                .as_mut()
+                .and_then(|watch| watch.changed(&mut self.errors))
+            {
+                self.apply_config(config);
+                changed = true;
+            }
+        }
+
+        if matches!(self.screen, Screen::Arcade) {
+            changed |= self.tick_arcade();
+        }
+
+        // redraw as the preview ends, nothing else changes on screen then
+        changed |= matches!(self.screen, Screen::Game)
+            && self.settings.memory_secs > 0
+            && self.game.key_log.is_empty()
+            && self.shown_at.elapsed().as_secs() == self.settings.memory_secs;
+
+        let over = match self.mode {
+            Mode::TimeAttack => time_attack::is_over(&self.game),
+            Mode::Survival => survival::is_over(&self.game),
+            _ => self
+                .time_limit()
+                .is_some_and(|limit| self.game.elapsed() >= limit),
+        } || self.settings.budget.is_spent(&self.game);
+
+        if matches!(self.screen, Screen::Game) && over {
+            self.finish();
+            changed = true;
+        }
+
+        if let Some(tutorial) = &mut self.tutorial {
+            if tutorial.due(
+                self.game.input.matches(' ').count(),
+                self.game.is_finished(),
+            ) {
+                tutorial.open = true;
+                self.game.pause();
+                changed = true;
+            }
+        }
+
+        if matches!(self.screen, Screen::Game) && self.settings.afk_timeout_secs > 0 {
+            self.game
+                .check_afk(Duration::from_secs(self.settings.afk_timeout_secs));
+        }
+
+        if !self.game.key_log.is_empty() {
+            if let Some(Err(error)) = self.live_stats.as_mut().map(|live| live.update(&self.game)) {
+                self.errors.push(error);
+                // don't repeat the same error every tick
+                self.live_stats = None;
+            }
+        }
+
+        if matches!(
+            self.screen,
+            Screen::Game | Screen::Pause(_) | Screen::PauseSettings(_)
+        ) && self.settings.autosave_secs > 0
+            && self.last_autosave.elapsed() >= Duration::from_secs(self.settings.autosave_secs)
+        {
+            self.autosave();
+        }
+
+        changed || afk != self.game.afk || errors != self.errors.len()
+    }
+
+    /// the test is over, by its last word or by the clock
+    fn finish(&mut self) {
+        // freeze the timer for the results
+        self.game.pause();
+        self.screen = Screen::Results;
+        self.discard_snapshot();
+
+        if let Err(error) = self.save_session(true) {
+            self.errors.push(error);
+        }
+
+        self.notify();
+
+        if let Some(Err(error)) = self
+            .history
+            .sessions
+            .last()
+            .map(|session| self.hooks.after_test(session))
+        {
+            self.errors.push(error);
+        }
+
+        // keep looping over whatever is still missed
+        if self.practicing && !self.practice_missed() {
+            self.practicing = false;
+        }
+    }
+
+    /// a desktop notification when the test just saved is a personal best or met today's goal
+    fn notify(&self) {
+        let Some(session) = self.history.sessions.last() else {
+            return;
+        };
+
+        let title = self.locale.get(Message::AppTitle);
+
+        if self.history.is_personal_best() {
+            notify::send(
+                title,
+                &self
+                    .locale
+                    .fill(Message::PersonalBest, &[&format!("{:.0}", session.wpm)]),
+            );
+        }
+
+        if session
+            .goals_met
+            .iter()
+            .any(|period| period == Period::Day.name())
+        {
+            notify::send(title, self.locale.get(Message::DailyGoalDone));
+        }
+    }
+
+    /// how long the test lasts, None when it ends with its words or keeps time its own way
+    fn time_limit(&self) -> Option<Duration> {
+        let secs = match self.mode {
+            Mode::TimeAttack | Mode::Survival => 0,
+            Mode::Custom => self.custom.time_secs,
+            _ => self.settings.time_secs,
+        };
+
+        Some(Duration::from_secs(secs)).filter(|limit| !limit.is_zero())
+    }
+
+    /// the clock of the modes that are timed differently
+    fn mode_clock(&self) -> Option<String> {
+        if let Some(limit) = self.time_limit() {
+            return Some(format!(
+                "{} {}",
+                self.locale.get(Message::Left),
+                minutes(limit.saturating_sub(self.game.elapsed()))
+            ));
+        }
+
+        match self.mode {
+            Mode::TimeAttack => Some(format!(
+                "{} {}",
+                self.locale.get(Message::Left),
+                minutes(time_attack::remaining(&self.game))
+            )),
+            Mode::Survival => Some(format!(
+                "{}  {}",
+                minutes(self.game.elapsed()),
+                self.locale.fill(
+                    Message::Pace,
+                    &[
+                        &format!("{:.0}", survival::pace(&self.game)),
+                        &format!("{:.0}", survival::required(&self.game)),
+                    ]
+                )
+            )),
+            _ => None,
+        }
+    }
+
+    /// the longest finished survival run
+    fn best_survival(&self) -> Duration {
+        self.history
+            .sessions
+            .iter()
+            .filter(|session| session.finished && session.mode == Mode::Survival.name())
+            .map(|session| Duration::from_secs_f64(session.seconds))
+            .max()
+            .unwrap_or_default()
+    }
+
+    fn start_arcade(&mut self) -> Result<()> {
+        self.arcade = Some(Arcade::new(&self.settings, &mut rand::rng())?);
+        self.last_arcade_tick = Instant::now();
+        self.screen = Screen::Arcade;
+
+        Ok(())
+    }
+
+    /// remember the length for next time and start a test with it
+    fn start_custom(&mut self, custom: CustomTest) -> Result<()> {
+        self.custom = custom;
+
+        if let Some(path) = &self.custom_path {
+            storage::save(path, &self.custom)?;
+        }
+
+        self.mode = Mode::Custom;
+        self.restart()
+    }
+
+    fn start_quiz(&mut self) -> Result<()> {
+        self.quiz = Some(Quiz::new(&self.settings, &mut rand::rng())?);
+        self.screen = Screen::Quiz;
+
+        Ok(())
+    }
+
+    /// move the falling words on, true while they are moving
+    fn tick_arcade(&mut self) -> bool {
+        let dt = self.last_arcade_tick.elapsed();
+        self.last_arcade_tick = Instant::now();
+
+        let Some(arcade) = self.arcade.as_mut().filter(|arcade| !arcade.is_over()) else {
+            return false;
+        };
+
+        if let Err(error) = arcade.tick(dt, &mut rand::rng()) {
+            self.errors.push(error);
+        }
+
+        if arcade.is_over() {
+            self.save_arcade(true);
+        }
+
+        true
+    }
+
+    fn save_arcade(&mut self, finished: bool) {
+        let Some(arcade) = &self.arcade else {
+            return;
+        };
+
+        self.history.sessions.push(arcade_session(arcade, finished));
+        record_goals(&mut self.history, &self.settings.goals);
+
+        if let Err(error) = self.save_history() {
+            self.errors.push(error);
+        }
+    }
+
+    const fn context(&self) -> Context {
+        match self.screen {
+            Screen::Game | Screen::Arcade | Screen::Quiz | Screen::Writing => Context::Game,
+            Screen::Menu
+            | Screen::Lessons
+            | Screen::Dictionary
+            | Screen::Stats
+            | Screen::About
+            | Screen::Review
+            | Screen::Pause(_)
+            | Screen::PauseSettings(_)
+            | Screen::ConfirmQuit(_)
+            | Screen::ResumePrompt(_)
+            | Screen::Packs(_) => Context::Menu,
+            Screen::Results => Context::Results,
+        }
+    }
+
+    /// the list being shown if the screen is one
+    const fn list_mut(&mut self) -> Option<&mut ListView> {
+        match self.screen {
+            Screen::Menu => Some(&mut self.menu),
+            Screen::Lessons => Some(&mut self.lesson_list),
+            Screen::Dictionary => Some(&mut self.dictionary),
+            Screen::Stats => Some(&mut self.stats),
+            Screen::Review => Some(&mut self.review),
+            _ => None,
+        }
+    }
+
+    fn menu_items(&self) -> Vec<MenuItem> {
+        MenuItem::ALL
+            .into_iter()
+            .filter(|item| self.menu.matches(self.locale.get(item.label())))
+            .collect()
+    }
+
+    fn lesson_items(&self) -> Vec<&Lesson> {
+        self.lessons
+            .iter()
+            .filter(|lesson| self.lesson_list.matches(&lesson.title))
+            .collect()
+    }
+
+    fn dictionary_items(&self) -> Vec<&'static str> {
+        let mut words: Vec<&'static str> = self
+            .dictionary_words
+            .iter()
+            .copied()
+            .filter(|word| self.dictionary.matches(word))
+            .collect();
+
+        if self.dictionary_sort == DictionarySort::Pin {
+            words.sort_by_key(|word| match self.settings.pin(word) {
+                Pin::Often => 0,
+                Pin::Rare => 1,
+                Pin::Never => 2,
+                Pin::Normal => 3,
+            });
+        }
+
+        words
+    }
+
+    /// move the selected dictionary word on to its next pin and save the pins
+    fn pin_selected(&mut self) {
+        let Some(word) = self
+            .dictionary_items()
+            .get(self.dictionary.selected)
+            .copied()
+        else {
+            return;
+        };
+
+        let pin = self.settings.pin(word).next();
+        self.settings.pins.insert(word.to_string(), pin);
+        self.pinned.insert(word.to_string(), pin);
+
+        // keep the word selected when sorting by pin moves it
+        if let Some(selected) = self.dictionary_items().iter().position(|w| *w == word) {
+            self.dictionary.selected = selected;
+        }
+
+        if let Some(path) = &self.pins_path {
+            if let Err(error) = storage::save(path, &self.pinned) {
+                self.errors.push(error);
+            }
+        }
+    }
+
+    /// sessions matching the search, newest first
+    fn stats_items(&self) -> Vec<&Session> {
+        let now = date::now();
+
+        self.history
+            .sessions
+            .iter()
+            .rev()
+            .filter(|session| self.stats.matches(&session.target))
+            .filter(|session| self.stats_filter.matches(session, now))
+            .collect()
+    }
+
+    /// words of the last test matching the search, by their target
+    fn review_items(&self) -> Vec<WordReview<'_>> {
+        self.game
+            .review()
+            .into_iter()
+            .filter(|review| self.review.matches(review.target))
+            .collect()
+    }
+
+    /// number of items in the list being shown
+    fn list_len(&self) -> usize {
+        match self.screen {
+            Screen::Menu => self.menu_items().len(),
+            Screen::Lessons => self.lesson_items().len(),
+            Screen::Dictionary => self.dictionary_items().len(),
+            Screen::Stats => self.stats_items().len(),
+            Screen::Review => self.review_items().len(),
+            _ => 0,
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn crossterm_event(&mut self, event: &Event) -> Result<()> {
+        let Event::Key(key_event) = event else {
+            return Ok(());
+        };
+
+        // any key dismisses the oldest error
+        if !self.errors.is_empty() {
+            self.errors.remove(0);
+            return Ok(());
+        }
+
+        if let Some(list) = self.list_mut().filter(|list| list.searching) {
+            list.search_key(key_event);
+            return Ok(());
+        }
+
+        if let Some(annotation) = &mut self.annotation {
+            match annotation.key(key_event) {
+                Some(true) => self.save_annotation(),
+                Some(false) => self.annotation = None,
+                None => (),
+            }
+
+            return Ok(());
+        }
+
+        if let Some(entry) = &mut self.custom_entry {
+            match entry.key(key_event) {
+                Some(Some(custom)) => {
+                    self.custom_entry = None;
+                    self.start_custom(custom)?;
+                }
+                Some(None) => self.custom_entry = None,
+                None => (),
+            }
+
+            return Ok(());
+        }
+
+        // any key moves the tutorial on
+        if let Some(tutorial) = self.tutorial.as_mut().filter(|tutorial| tutorial.open) {
+            tutorial.open = false;
+
+            if tutorial.is_last() {
+                self.tutorial = None;
+                self.screen = Screen::Menu;
+            } else {
+                tutorial.step += 1;
+                self.game.resume();
+            }
+
+            return Ok(());
+        }
+
+        // any key closes the help
+        if self.help {
+            self.help = false;
+
+            if matches!(self.screen, Screen::Game) {
+                self.game.resume();
+            }
+
+            return Ok(());
+        }
+
+        let action = self.keymap.action(self.context(), key_event);
+
+        match action {
+            Some(Action::Quit) => {
+                match self.screen {
+                    // pressing it again while asking quits without saving
+                    Screen::ConfirmQuit(_) => self.exit(),
+                    // keep the saved game for next time
+                    Screen::ResumePrompt(_) => self.running = false,
+                    _ => self.quit(),
+                }
+
+                return Ok(());
+            }
+            Some(Action::Help) => {
+                self.game.pause();
+                self.help = true;
+
+                return Ok(());
+            }
+            _ => (),
+        }
+
+        if let Some(action) = action {
+            let len = self.list_len();
+
+            if self.list_mut().is_some_and(|list| list.action(action, len)) {
+                return Ok(());
+            }
+        }
+
+        match self.screen {
+            Screen::Menu => {
+                if action == Some(Action::WordOfTheDay) && self.word_of_the_day.is_some() {
+                    self.mode = Mode::WordOfTheDay;
+                    self.restart()?;
+                }
+
+                if action == Some(Action::Select) {
+                    match self.menu_items().get(self.menu.selected) {
+                        Some(MenuItem::Start) => {
+                            self.mode = Mode::Words;
+                            self.restart()?;
+                        }
+                        Some(MenuItem::Custom) => {
+                            self.custom_entry = Some(CustomEntry::new(self.custom));
+                        }
+                        Some(MenuItem::Numbers) => {
+                            self.mode = Mode::Numbers;
+                            self.restart()?;
+                        }
+                        Some(MenuItem::Names) => self.screen = Screen::Packs(0),
+                        Some(MenuItem::Cartouches) => {
+                            self.mode = Mode::Cartouches;
+                            self.restart()?;
+                        }
+                        Some(MenuItem::WeakBigrams) => {
+                            self.mode = Mode::WeakBigrams;
+
+                            // stay on the menu until there is enough history
+                            if let Err(error) = self.restart() {
+                                self.errors.push(error);
+                            }
+                        }
+                        Some(MenuItem::TimeAttack) => {
+                            self.mode = Mode::TimeAttack;
+                            self.restart()?;
+                        }
+                        Some(MenuItem::Survival) => {
+                            self.mode = Mode::Survival;
+                            self.restart()?;
+                        }
+                        Some(MenuItem::Arcade) => self.start_arcade()?,
+                        Some(MenuItem::Quiz) => self.start_quiz()?,
+                        Some(MenuItem::Writing) => self.screen = Screen::Writing,
+                        Some(MenuItem::Lessons) => self.screen = Screen::Lessons,
+                        Some(MenuItem::Dictionary) => self.screen = Screen::Dictionary,
+                        Some(MenuItem::Stats) => self.screen = Screen::Stats,
+                        Some(MenuItem::About) => self.screen = Screen::About,
+                        Some(MenuItem::Quit) => self.exit(),
+                        None => (),
+                    }
+                }
+            }
+            Screen::Lessons => match action {
+                Some(Action::Back) => self.screen = Screen::Menu,
+                Some(Action::Select) => {
+                    let selected = self.lesson_items().get(self.lesson_list.selected).copied();
+
+                    if let Some(lesson) = selected.cloned() {
+                        self.play_lesson(lesson)?;
+                    }
+                }
+                _ => (),
+            },
+            Screen::Dictionary | Screen::Stats => match action {
+                Some(Action::Back) => self.screen = Screen::Menu,
+                Some(Action::Pin) if matches!(self.screen, Screen::Dictionary) => {
+                    self.pin_selected();
+                }
+                Some(Action::Sort) if matches!(self.screen, Screen::Dictionary) => {
+                    self.dictionary_sort = match self.dictionary_sort {
+                        DictionarySort::Name => DictionarySort::Pin,
+                        DictionarySort::Pin => DictionarySort::Name,
+                    };
+                }
+                Some(Action::FilterTag) if matches!(self.screen, Screen::Stats) => {
+                    self.stats_filter.next_tag(&self.history.sessions);
+                    self.stats.selected = 0;
+                }
+                Some(Action::FilterMode) if matches!(self.screen, Screen::Stats) => {
+                    self.stats_filter.next_mode(&self.history.sessions);
+                    self.stats.selected = 0;
+                }
+                Some(Action::FilterDate) if matches!(self.screen, Screen::Stats) => {
+                    self.stats_filter.range = self.stats_filter.range.next();
+                    self.stats.selected = 0;
+                }
+                Some(Action::NextTab) if matches!(self.screen, Screen::Stats) => {
+                    self.stats_tab = match self.stats_tab {
+                        StatsTab::Summary => StatsTab::Fingers,
+                        StatsTab::Fingers => StatsTab::Progress,
+                        StatsTab::Progress => StatsTab::Summary,
+                    };
+                }
+                _ => (),
+            },
+            Screen::Game => match action {
+                Some(Action::Pause) => {
+                    self.game.pause();
+                    self.screen = Screen::Pause(0);
+                }
+                Some(Action::Restart) => self.restart()?,
+                Some(Action::TogglePanels) => self.panels = !self.panels,
+                Some(Action::ToggleGlosses) => self.glosses = !self.glosses,
+                // only its own key leaves the preview, so typing can't start the test by accident
+                Some(Action::Begin) if self.previewing => {
+                    self.previewing = false;
+                    self.shown_at = Instant::now();
+                }
+                _ if self.previewing => (),
+                _ => {
+                    let words = self.game.input.matches(' ').count();
+                    let strokes = self.game.strokes.len();
+
+                    self.game.crossterm_event(event, action);
+                    self.emit_key_events(key_event, words, strokes);
+                    self.play_key_sound(words, strokes);
+
+                    if self.game.is_finished() {
+                        self.finish();
+                    }
+                }
+            },
+            Screen::Pause(selected) => match action {
+                Some(Action::Back) => self.resume(),
+                Some(Action::Select) => match PauseItem::ALL[selected] {
+                    PauseItem::Resume => self.resume(),
+                    PauseItem::Restart => self.restart()?,
+                    PauseItem::Settings => self.screen = Screen::PauseSettings(0),
+                    PauseItem::Quit => self.quit(),
+                },
+                action => {
+                    self.screen =
+                        Screen::Pause(menu_selection(selected, PauseItem::ALL.len(), action));
+                }
+            },
+            Screen::PauseSettings(selected) => match action {
+                Some(Action::Back) => self.screen = Screen::Pause(PauseItem::Settings as usize),
+                Some(Action::Select) => {
+                    let value = SettingItem::ALL[selected].value_mut(&mut self.settings);
+                    *value = !*value;
+                    self.game.set_rules((&self.settings).into());
+                }
+                action => {
+                    self.screen = Screen::PauseSettings(menu_selection(
+                        selected,
+                        SettingItem::ALL.len(),
+                        action,
+                    ));
+                }
+            },
+            Screen::ConfirmQuit(selected) => match action {
+                Some(Action::Back) => self.resume(),
+                Some(Action::Select) => match QuitItem::ALL[selected] {
+                    QuitItem::Save => match self.save_session(false) {
+                        Ok(()) => self.exit(),
+                        // stay so the error can be read
+                        Err(error) => {
+                            self.errors.push(error);
+                            self.screen = Screen::Pause(0);
+                        }
+                    },
+                    QuitItem::Discard => self.exit(),
+                    QuitItem::Cancel => self.resume(),
+                },
+                action => {
+                    self.screen =
+                        Screen::ConfirmQuit(menu_selection(selected, QuitItem::ALL.len(), action));
+                }
+            },
+            Screen::ResumePrompt(selected) => match action {
+                Some(Action::Select) => match ResumeItem::ALL[selected] {
+                    ResumeItem::Resume => {
+                        if let Some(snapshot) = self.snapshot.take() {
+                            self.game = Game::from_snapshot(&snapshot);
+                            self.mode = Mode::ALL
+                                .into_iter()
+                                .find(|mode| mode.name() == snapshot.mode)
+                                .unwrap_or(Mode::Words);
+                            self.custom = CustomTest {
+                                len: snapshot.custom_len,
+                                time_secs: snapshot.custom_secs,
+                            };
+                            self.pack = Pack::ALL
+                                .into_iter()
+                                .find(|pack| pack.name() == snapshot.pack)
+                                .unwrap_or(self.pack);
+                            if snapshot.lesson.is_some() {
+                                self.lesson = snapshot.lesson;
+                            }
+                            self.settings = snapshot.settings;
+                        }
+
+                        self.screen = Screen::Pause(0);
+                    }
+                    ResumeItem::New => {
+                        self.snapshot = None;
+                        self.restart()?;
+                    }
+                },
+                action => {
+                    self.screen = Screen::ResumePrompt(menu_selection(
+                        selected,
+                        ResumeItem::ALL.len(),
+                        action,
+                    ));
+                }
+            },
+            Screen::Packs(selected) => match action {
+                Some(Action::Back) => self.screen = Screen::Menu,
+                Some(Action::Select) => {
+                    self.pack = Pack::ALL[selected];
+                    self.mode = Mode::Names;
+                    self.restart()?;
+                }
+                action => {
+                    self.screen = Screen::Packs(menu_selection(selected, Pack::ALL.len(), action));
+                }
+            },
+            Screen::Results => match action {
+                Some(Action::Back) => self.screen = Screen::Menu,
+                Some(Action::Restart) => self.restart()?,
+                Some(Action::Repeat) => self.repeat(),
+                Some(Action::PracticeMissed) => _ = self.practice_missed(),
+                Some(Action::Annotate) => {
+                    self.annotation = self.history.sessions.last().map(Annotation::new);
+                }
+                Some(Action::Review) => {
+                    self.review = ListView::default();
+                    self.screen = Screen::Review;
+                }
+                _ => (),
+            },
+            Screen::Review => {
+                if action == Some(Action::Back) {
+                    self.screen = Screen::Results;
+                }
+            }
+            Screen::About => {
+                if action == Some(Action::Back) {
+                    self.screen = Screen::Menu;
+                }
+            }
+            Screen::Arcade => self.arcade_key(key_event, action)?,
+            Screen::Quiz => self.quiz_key(key_event, action)?,
+            Screen::Writing => self.writing_key(key_event, action),
+        }
+
+        Ok(())
+    }
+
+    fn arcade_key(&mut self, key_event: &KeyEvent, action: Option<Action>) -> Result<()> {
+        let Some(arcade) = &mut self.arcade else {
+            return Ok(());
+        };
+
+        if arcade.is_over() {
+            match self.keymap.action(Context::Menu, key_event) {
+                Some(Action::Select) => self.start_arcade()?,
+                Some(Action::Back) => self.screen = Screen::Menu,
+                _ => (),
+            }
+
+            return Ok(());
+        }
+
+        match (action, key_event.code) {
+            // leaving part way is saved like an abandoned test
+            (Some(Action::Pause), _) => {
+                self.save_arcade(false);
+                self.screen = Screen::Menu;
+            }
+            (_, KeyCode::Char(_))
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {}
+            (_, KeyCode::Char(c)) => arcade.key(c),
+            (_, KeyCode::Backspace) => arcade.backspace(),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn quiz_key(&mut self, key_event: &KeyEvent, action: Option<Action>) -> Result<()> {
+        let Some(quiz) = &mut self.quiz else {
+            return Ok(());
+        };
+
+        if quiz.is_over() {
+            match self.keymap.action(Context::Menu, key_event) {
+                Some(Action::Select) => self.start_quiz()?,
+                Some(Action::Back) => self.screen = Screen::Menu,
+                _ => (),
+            }
+
+            return Ok(());
+        }
+
+        match (action, key_event.code) {
+            (Some(Action::Pause), _) => self.screen = Screen::Menu,
+            (_, KeyCode::Char(_))
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {}
+            (_, KeyCode::Char(c)) => quiz.input.push(c),
+            (_, KeyCode::Backspace) => {
+                quiz.input.pop();
+            }
+            (_, KeyCode::Enter) => quiz.submit(),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    fn writing_key(&mut self, key_event: &KeyEvent, action: Option<Action>) {
+        match (action, key_event.code) {
+            (Some(Action::Pause), _) => self.screen = Screen::Menu,
+            (Some(Action::ToggleGrammar), _) => self.check_grammar = !self.check_grammar,
+            (Some(Action::DeleteWord), _) => delete_last_word(&mut self.writing),
+            (_, KeyCode::Char(_))
+                if key_event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {}
+            (_, KeyCode::Char(c)) => self.writing.push(c),
+            (_, KeyCode::Enter) => self.writing.push('\n'),
+            (_, KeyCode::Backspace) => {
+                self.writing.pop();
+            }
+            _ => (),
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn draw(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+            frame.render_widget(
+                Paragraph::new(self.locale.fill(
+                    Message::EnlargeTerminal,
+                    &[&MIN_WIDTH, &MIN_HEIGHT, &area.width, &area.height],
+                ))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true }),
+                centered(area, area.width, 4),
+            );
+
+            return;
+        }
+
+        match self.screen {
+            Screen::Menu => self.render_main_menu(frame),
+            Screen::Lessons => self.render_lessons(frame),
+            Screen::Dictionary => self.render_dictionary(frame),
+            Screen::Stats => self.render_stats(frame),
+            Screen::Game => self.render_game(frame),
+            Screen::Pause(selected) => {
+                self.render_game(frame);
+                render_menu(
+                    frame,
+                    &self.locale.title(Message::Paused),
+                    &PauseItem::ALL.map(|item| self.locale.get(item.label())),
+                    selected,
+                );
+            }
+            Screen::PauseSettings(selected) => {
+                self.render_game(frame);
+                let items = SettingItem::ALL.map(|item| {
+                    let mark = if item.is_on(&self.settings) { 'x' } else { ' ' };
+                    format!("[{mark}] {}", self.locale.get(item.label()))
+                });
+                render_menu(
+                    frame,
+                    &self.locale.title(Message::Settings),
+                    &items.each_ref().map(String::as_str),
+                    selected,
+                );
+            }
+            Screen::ConfirmQuit(selected) => {
+                self.render_game(frame);
+                render_menu(
+                    frame,
+                    &self.locale.title(Message::QuitTest),
+                    &QuitItem::ALL.map(|item| self.locale.get(item.label())),
+                    selected,
+                );
+            }
+            Screen::ResumePrompt(selected) => render_menu(
+                frame,
+                &self.locale.title(Message::UnfinishedTest),
+                &ResumeItem::ALL.map(|item| self.locale.get(item.label())),
+                selected,
+            ),
+            Screen::Results => self.render_results(frame),
+            Screen::Review => self.render_review(frame),
+            Screen::Arcade => self.render_arcade(frame),
+            Screen::Quiz => self.render_quiz(frame),
+            Screen::Writing => self.render_writing(frame),
+            Screen::About => self.render_about(frame),
+            Screen::Packs(selected) => {
+                self.render_main_menu(frame);
+                render_menu(
+                    frame,
+                    &self.locale.title(Message::NamePacks),
+                    &Pack::ALL.map(|pack| self.locale.get(Message::Pack(pack))),
+                    selected,
+                );
+            }
+        }
+
+        if self.help {
+            render_help(frame, &self.keymap, self.context(), self.locale);
+        }
+
+        if let Some(annotation) = &self.annotation {
+            let area = centered(frame.area(), 60, 6);
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(annotation.text())
+                    .wrap(Wrap { trim: false })
+                    .block(
+                        Block::bordered()
+                            .border_type(Rounded)
+                            .title(self.locale.title(Message::TagsAndNote))
+                            .title_bottom(self.locale.title(Message::AnnotateHint)),
+                    ),
+                area,
+            );
+        }
+
+        if let Some(entry) = &self.custom_entry {
+            let area = centered(frame.area(), 44, 4);
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(self.locale.get(Message::CustomExample)).dim(),
+                    Line::from(format!("> {}_", entry.text)),
+                ])
+                .block(
+                    Block::bordered()
+                        .border_type(Rounded)
+                        .title(self.locale.title(Message::CustomTest))
+                        .title_bottom(self.locale.title(Message::CustomHint)),
+                ),
+                area,
+            );
+        }
+
+        if let Some(tutorial) = self.tutorial.as_ref().filter(|tutorial| tutorial.open) {
+            let (title, text) = tutorial.popup(&self.keymap, self.locale);
+            render_popup(frame, self.locale, &title, text, Style::new());
+        }
+
+        if let Some(error) = self.errors.first() {
+            render_popup(
+                frame,
+                self.locale,
+                &self.locale.title(Message::Error),
+                self.locale.error(error),
+                Style::new().fg(Color::Red),
+            );
+        }
+
+        if self.monochrome {
+            for cell in &mut frame.buffer_mut().content {
+                cell.set_fg(Color::Reset).set_bg(Color::Reset);
+            }
+        }
+    }
+
+    fn render_main_menu(&self, frame: &mut Frame) {
+        let goals = self.goal_parts();
+        let goals_height = if goals.is_empty() {
+            0
+        } else {
+            u16::try_from(goals.len()).unwrap_or_default() + 2
+        };
+
+        let [menu, goals_area, word] = Layout::vertical([
+            Constraint::Length(18),
+            Constraint::Length(goals_height),
+            Constraint::Length(7),
+        ])
+        .areas(centered(frame.area(), 60, 18 + goals_height + 7));
+
+        render_list(
+            frame,
+            centered(menu, 30, 18),
+            &self.profile.as_ref().map_or_else(
+                || self.locale.title(Message::AppTitle),
+                |profile| format!(" {}, {profile} ", self.locale.get(Message::AppTitle)),
+            ),
+            self.menu_items()
+                .into_iter()
+                .map(|item| self.locale.get(item.label())),
+            &self.menu,
+        );
+
+        if !goals.is_empty() {
+            render_goals(frame, goals_area, &goals, self.locale);
+        }
+
+        let Some((name, toml)) = self
+            .word_of_the_day
+            .and_then(|name| WORDS.get(name).map(|toml| (name, toml)))
+        else {
+            return;
+        };
+
+        let drill_hint = self
+            .keymap
+            .key(Context::Menu, Action::WordOfTheDay)
+            .map(|key| format!(" {} ", self.locale.fill(Message::DrillIt, &[&key])))
+            .unwrap_or_default();
+
+        frame.render_widget(
+            Paragraph::new(
+                [
+                    toml.get("definition")
+                        .and_then(toml::Value::as_str)
+                        .map(|definition| {
+                            format!("{} {definition}", self.locale.get(Message::Definition))
+                        }),
+                    etymology(toml).map(|etymology| {
+                        format!("{} {etymology}", self.locale.get(Message::Etymology))
+                    }),
+                ]
+                .into_iter()
+                .flatten()
+                .map(Line::raw)
+                .collect::<Text>(),
+            )
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(format!(
+                        " {} ",
+                        self.locale.fill(Message::WordOfTheDay, &[&name])
+                    ))
+                    .title_bottom(Line::from(drill_hint).right_aligned()),
+            ),
+            word,
+        );
+    }
+
+    /// every part of the goals that are set, with how far along it is
+    fn goal_parts(&self) -> Vec<(String, f64)> {
+        let now = date::now();
+
+        Period::ALL
+            .into_iter()
+            .filter(|period| self.settings.goals.get(*period).is_set())
+            .flat_map(|period| {
+                let progress = Progress::since(&self.history.sessions, period.start(now));
+
+                self.settings
+                    .goals
+                    .get(period)
+                    .parts(&progress, self.locale)
+                    .into_iter()
+                    .map(move |(label, ratio)| {
+                        (
+                            format!("{} {label}", self.locale.get(Message::Period(period))),
+                            ratio,
+                        )
+                    })
+            })
+            .collect()
+    }
+
+    fn render_lessons(&self, frame: &mut Frame) {
+        let lessons = self.lesson_items();
+        let [list, info] = list_and_info(frame.area(), 32);
+
+        render_list(
+            frame,
+            list,
+            &self.locale.title(Message::Lessons),
+            lessons.iter().map(|lesson| lesson.title.as_str()),
+            &self.lesson_list,
+        );
+
+        let text = match lessons.get(self.lesson_list.selected) {
+            Some(lesson) => {
+                let mut text = Text::from(lesson.notes.as_str());
+
+                if lesson.pass.is_set() {
+                    text.push_line("");
+                    text.push_line(
+                        self.locale
+                            .fill(Message::PassAt, &[&lesson.pass.wpm, &lesson.pass.accuracy]),
+                    );
+                }
+
+                text
+            }
+            None if self.lessons.is_empty() => Text::from(self.locale.get(Message::NoLessons)),
+            None => Text::default(),
+        };
+
+        frame.render_widget(
+            Paragraph::new(text)
+                .wrap(Wrap { trim: false })
+                .block(Block::bordered().border_type(Rounded)),
+            info,
+        );
+    }
+
+    fn render_dictionary(&self, frame: &mut Frame) {
+        let words = self.dictionary_items();
+        let [list, info] = list_and_info(frame.area(), 24);
+
+        render_list(
+            frame,
+            list,
+            &self.locale.title(match self.dictionary_sort {
+                DictionarySort::Name => Message::Nimi,
+                DictionarySort::Pin => Message::NimiPinned,
+            }),
+            words.iter().map(|word| {
+                format!(
+                    "{word:<16}{}",
+                    self.locale.get(Message::Pin(self.settings.pin(word)))
+                )
+            }),
+            &self.dictionary,
+        );
+
+        if let Some((word, toml)) = words
+            .get(self.dictionary.selected)
+            .and_then(|word| WORDS.get(*word).map(|toml| (word, toml)))
+        {
+            frame.render_widget(
+                Paragraph::new(word_info(toml, self.locale))
+                    .wrap(Wrap { trim: false })
+                    .block(
+                        Block::bordered()
+                            .border_type(Rounded)
+                            .title(format!(" {word} ")),
+                    ),
+                info,
+            );
+        }
+    }
+
+    /// draw a game, `panels` shows the word information above the text
+    #[allow(clippy::too_many_lines)]
+    fn render_game(&self, frame: &mut Frame) {
+        const HIDDEN: Style = Style::new();
+
+        const CARET: Style = Style::new().add_modifier(Modifier::REVERSED);
+
+        let game = &self.game;
+        let settings = &self.settings;
+        let (panels, glosses, monochrome) = (self.panels, self.glosses, self.monochrome);
+
+        let [correct, wrong, overflow, skipped] = span_styles(monochrome);
+
+        let current_index = game.input.chars().filter(|c| *c == ' ').count();
+        let index = if game.input.ends_with(' ') {
+            current_index
+        } else {
+            current_index.saturating_sub(1)
+        };
+
+        let mut words = game.target.split_whitespace();
+        let word_1 = words.nth(index);
+        let word_2 = words.next();
+
+        // a prompt covers every word of its phrase, so the next one is the next different prompt
+        let prompt_1 = game.prompts.get(index);
+        let prompt_2 = prompt_1.and_then(|prompt_1| {
+            game.prompts[index..]
+                .iter()
+                .find(|prompt_2| *prompt_2 != prompt_1)
+        });
+
+        let [top, main] = Layout::new(
+            Vertical,
+            [
+                if panels {
+                    Constraint::Fill(1)
+                } else {
+                    Constraint::Length(0)
+                },
+                Constraint::Fill(3),
+            ],
+        )
+        .areas(frame.area());
+        // only the current word's panel fits on narrow terminals
+        let [top_l, top_r] = Layout::new(
+            Horizontal,
+            [
+                Constraint::Fill(1),
+                if is_narrow(top) {
+                    Constraint::Length(0)
+                } else {
+                    Constraint::Fill(1)
+                },
+            ],
+        )
+        .areas(top);
+        let [hud, main] =
+            Layout::new(Vertical, [Constraint::Length(1), Constraint::Fill(1)]).areas(main);
+
+        // graphemes since the last space, to know which letters a hint shows
+        let column = Cell::new(0);
+        let reveal = reveal(settings, self.previewing, self.memorizing());
+        let spans: Vec<GameSpan<String>> = game
+            .spans
+            .iter()
+            .map(|span| match span {
+                GameSpan::Hidden(line) => GameSpan::Hidden(mask(line, &column, reveal)),
+                span => span.map(|line| mask(line, &column, Reveal::All)),
+            })
+            .collect();
+
+        // in shadow mode the words finished without a mistake are left out
+        let vanish: Vec<bool> = if settings.shadow {
+            game.word_results()
+                .into_iter()
+                .take(game.input.matches(' ').count())
+                .map(|(_, correct)| correct)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let spans = shadow(&spans, &vanish);
+        let target = game
+            .target
+            .split(' ')
+            .enumerate()
+            .filter(|(index, _)| !vanish.get(*index).copied().unwrap_or_default())
+            .map(|(_, word)| word)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let mut ratatui_spans = Vec::new();
+        let mut caret = true;
+
+        for span in &spans {
+            match span {
+                GameSpan::Correct(line) => ratatui_spans.push(Span::styled(line, correct)),
+                GameSpan::Wrong(line) => ratatui_spans.push(Span::styled(line, wrong)),
+                GameSpan::Overflow(line) => ratatui_spans.push(Span::styled(line, overflow)),
+                GameSpan::Skipped(line) => ratatui_spans.push(Span::styled(line, skipped)),
+                // the caret sits on the first grapheme still to be typed
+                GameSpan::Hidden(line) if caret => {
+                    caret = false;
+                    let split = line
+                        .grapheme_indices(true)
+                        .nth(1)
+                        .map_or(line.len(), |(i, _)| i);
+                    ratatui_spans.push(Span::styled(&line[..split], CARET));
+                    ratatui_spans.push(Span::styled(&line[split..], HIDDEN));
+                }
+                GameSpan::Hidden(line) => ratatui_spans.push(Span::styled(line, HIDDEN)),
+            }
+        }
+
+        let panels = if game.prompts.is_empty() {
+            [word_1, word_2].map(|word| {
+                word.and_then(|w| WORDS.get(punctuation::bare(w)))
+                    .map(|toml| {
+                        Paragraph::new(word_info(toml, self.locale)).wrap(Wrap { trim: false })
+                    })
+            })
+        } else {
+            [prompt_1, prompt_2].map(|prompt| {
+                prompt.map(|prompt| {
+                    Paragraph::new(prompt.as_str())
+                        .bold()
+                        .alignment(Alignment::Center)
+                })
+            })
+        };
+
+        for (panel, area) in panels.into_iter().zip([top_l, top_r]) {
+            if let Some(panel) = panel {
+                frame.render_widget(panel.block(Block::bordered()), area);
+            }
+        }
+
+        frame.render_widget(
+            Line::from(vec![
+                Span::raw(self.mode_clock().unwrap_or_else(|| minutes(game.elapsed()))),
+                Span::raw(format!(
+                    "  {} {:.0}",
+                    self.locale.get(Message::Wpm),
+                    game.wpm()
+                )),
+                Span::raw(format!(
+                    "  {} {}",
+                    self.locale.get(Message::Combo),
+                    game.combo
+                )),
+                Span::styled(
+                    format!("  {} {}", self.locale.get(Message::Best), game.best_combo),
+                    Style::new().dim(),
+                ),
+                budget_bar(settings, game),
+                Span::styled(self.preview_hint(), Style::new().fg(Color::Yellow)),
+                Span::styled(
+                    if game.afk {
+                        format!("  {}", self.locale.get(Message::Afk))
+                    } else {
+                        String::new()
+                    },
+                    Style::new().fg(Color::Yellow),
+                ),
+            ]),
+            hud,
+        );
+
+        // reading the cartouches is the point of the mode
+        if settings.sitelen_pona || self.mode == Mode::Cartouches {
+            frame.render_widget(
+                Paragraph::new(sitelen_pona_lines(game, [correct, wrong, CARET, HIDDEN]))
+                    .wrap(Wrap::default()),
+                main,
+            );
+        } else if settings.word_per_line {
+            // keep the word being typed a third of the way down
+            let current = game.input.matches(' ').count()
+                - vanish.iter().filter(|vanished| **vanished).count();
+            let scroll = current.saturating_sub(usize::from(main.height / 3));
+
+            frame.render_widget(
+                Paragraph::new(word_lines(ratatui_spans, &target))
+                    .scroll((u16::try_from(scroll).unwrap_or(u16::MAX), 0)),
+                main,
+            );
+        } else if glosses {
+            frame.render_widget(
+                Paragraph::new(glossed_lines(ratatui_spans, &target, main.width)),
+                main,
+            );
+        } else {
+            frame.render_widget(
+                Paragraph::new(Line::from(ratatui_spans)).wrap(Wrap::default()),
+                main,
+            );
+        }
+    }
+
+    fn render_arcade(&self, frame: &mut Frame) {
+        let Some(arcade) = &self.arcade else {
+            return;
+        };
+
+        let [field, hud] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+
+        let block = Block::bordered()
+            .border_type(Rounded)
+            .title(self.locale.title(Message::FallingWords));
+        let inner = block.inner(field);
+        frame.render_widget(block, field);
+
+        for word in &arcade.falling {
+            let width = u16::try_from(word.word.width()).unwrap_or(u16::MAX);
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let (x, y) = (
+                (word.x * f64::from(inner.width.saturating_sub(width))) as u16,
+                (word.y * f64::from(inner.height)) as u16,
+            );
+
+            // the part already typed stands out
+            let typed = if word.word.starts_with(&arcade.input) {
+                arcade.input.len()
+            } else {
+                0
+            };
+
+            frame.render_widget(
+                Line::from(vec![
+                    Span::styled(&word.word[..typed], Style::new().fg(Color::Green)),
+                    Span::raw(&word.word[typed..]),
+                ]),
+                Rect::new(
+                    inner.x + x,
+                    inner.y + y.min(inner.height.saturating_sub(1)),
+                    width.min(inner.width),
+                    1,
+                ),
+            );
+        }
+
+        frame.render_widget(
+            Line::from(vec![
+                Span::styled(format!("> {}", arcade.input), Style::new().bold()),
+                Span::raw(format!(
+                    "  {} {}  {} {}  {:.0} {}",
+                    self.locale.get(Message::Caught),
+                    arcade.caught,
+                    self.locale.get(Message::Lives),
+                    arcade.lives,
+                    arcade.wpm(),
+                    self.locale.get(Message::Wpm)
+                )),
+            ]),
+            hud,
+        );
+
+        if arcade.is_over() {
+            let block = Block::bordered()
+                .border_type(Rounded)
+                .title(self.locale.title(Message::GameOver))
+                .title_bottom(self.play_again_hint());
+            let area = centered(frame.area(), 44, 5);
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(self.locale.fill(Message::CaughtWords, &[&arcade.caught])),
+                    Line::from(self.locale.fill(
+                        Message::WpmAndAccuracy,
+                        &[
+                            &format!("{:.1}", arcade.wpm()),
+                            &format!("{:.1}", arcade.accuracy() * 100.0),
+                        ],
+                    )),
+                ])
+                .alignment(Alignment::Center)
+                .block(block),
+                area,
+            );
+        }
+    }
+
+    fn render_writing(&self, frame: &mut Frame) {
+        let issues = if self.check_grammar {
+            grammar::check(&self.writing)
+        } else {
+            Vec::new()
+        };
+
+        let grammar_hint = self
+            .keymap
+            .key(Context::Game, Action::ToggleGrammar)
+            .map(|key| {
+                let message = if self.check_grammar {
+                    Message::GrammarOff
+                } else {
+                    Message::GrammarOn
+                };
+                format!(" {}", self.locale.fill(message, &[&key]))
+            })
+            .unwrap_or_default();
+
+        let mistake = Style::new().fg(Color::Red).underlined();
+        let mut lines = vec![Line::default()];
+        let mut at = 0;
+
+        // cut the text where issues start and end, then split the pieces into lines
+        let mut cuts: Vec<usize> = issues
+            .iter()
+            .flat_map(|issue| [issue.start, issue.end])
+            .chain([self.writing.len()])
+            .collect();
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        for cut in cuts {
+            let style = if issues
+                .iter()
+                .any(|issue| issue.start <= at && cut <= issue.end)
+            {
+                mistake
+            } else {
+                Style::new()
+            };
+
+            for (index, part) in self.writing[at..cut].split('\n').enumerate() {
+                if index > 0 {
+                    lines.push(Line::default());
+                }
+
+                if let Some(line) = lines.last_mut() {
+                    line.push_span(Span::styled(part, style));
+                }
+            }
+
+            at = cut;
+        }
+
+        if let Some(line) = lines.last_mut() {
+            line.push_span(Span::styled(" ", Style::new().reversed()));
+        }
+
+        let [text, notes] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(if self.check_grammar { 6 } else { 0 }),
+        ])
+        .areas(centered(frame.area(), 80, frame.area().height));
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::FreeWriting))
+                    .title_bottom(format!(
+                        "{grammar_hint} {} ",
+                        self.locale.get(Message::EscForMenu)
+                    )),
+            ),
+            text,
+        );
+
+        if self.check_grammar {
+            let messages: Vec<Line> = issues
+                .iter()
+                .map(|issue| {
+                    Line::from(format!(
+                        "{}: {}",
+                        &self.writing[issue.start..issue.end],
+                        self.locale.get(issue.message)
+                    ))
+                })
+                .collect();
+
+            frame.render_widget(
+                Paragraph::new(messages).block(
+                    Block::bordered()
+                        .border_type(Rounded)
+                        .title(self.locale.title(Message::Grammar)),
+                ),
+                notes,
+            );
+        }
+    }
+
+    fn render_quiz(&self, frame: &mut Frame) {
+        let Some(quiz) = &self.quiz else {
+            return;
+        };
+
+        // every question answered so far with whether it was right and all it accepts
+        let answers: Vec<Line> = quiz
+            .questions
+            .iter()
+            .zip(&quiz.answered)
+            .rev()
+            .map(|(question, answer)| {
+                let (mark, style) = if question.accepts(answer) {
+                    (Message::Right, Style::new().fg(Color::Green))
+                } else {
+                    (Message::Wrong, Style::new().fg(Color::Red))
+                };
+
+                Line::from(vec![
+                    Span::styled(format!("{:6}", self.locale.get(mark)), style),
+                    Span::raw(self.locale.fill(
+                        Message::Accepted,
+                        &[&question.gloss, answer, &question.answers.join(", ")],
+                    )),
+                ])
+            })
+            .collect();
+
+        let [prompt, list] = Layout::vertical([Constraint::Length(6), Constraint::Fill(1)])
+            .areas(centered(frame.area(), 80, frame.area().height));
+
+        #[allow(clippy::option_if_let_else)]
+        let prompt_text = match quiz.current() {
+            Some(question) => vec![
+                Line::from(self.locale.fill(
+                    Message::QuestionOf,
+                    &[&(quiz.answered.len() + 1), &quiz.questions.len()],
+                ))
+                .dim(),
+                Line::from(question.gloss.as_str()).bold(),
+                Line::default(),
+                Line::from(format!("> {}", quiz.input)),
+            ],
+            None => vec![Line::from(
+                self.locale
+                    .fill(Message::RightOf, &[&quiz.score(), &quiz.questions.len()]),
+            )
+            .bold()],
+        };
+
+        frame.render_widget(
+            Paragraph::new(prompt_text)
+                .alignment(Alignment::Center)
+                .block(
+                    Block::bordered()
+                        .border_type(Rounded)
+                        .title(self.locale.title(Message::ReverseQuiz))
+                        .title_bottom(if quiz.is_over() {
+                            self.play_again_hint()
+                        } else {
+                            self.locale.title(Message::AnswerHint)
+                        }),
+                ),
+            prompt,
+        );
+        frame.render_widget(
+            Paragraph::new(answers).block(Block::bordered().border_type(Rounded)),
+            list,
+        );
+    }
+
+    fn render_about(&self, frame: &mut Frame) {
+        let lines = about::lines(self.locale);
+        let height = u16::try_from(lines.len() + 2).unwrap_or(u16::MAX);
+
+        frame.render_widget(
+            Paragraph::new(lines.into_iter().map(Line::from).collect::<Text>()).block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::About)),
+            ),
+            centered(frame.area(), 60, height),
+        );
+    }
+
+    fn render_review(&self, frame: &mut Frame) {
+        render_list(
+            frame,
+            centered(frame.area(), 90, frame.area().height),
+            &self.locale.title(Message::EveryWord),
+            self.review_items()
+                .iter()
+                .map(|review| review_line(review, self.monochrome, self.locale)),
+            &self.review,
+        );
+    }
+
+    #[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+    fn render_stats(&self, frame: &mut Frame) {
+        let sessions = self.stats_items();
+        let [list, info] = list_and_info(frame.area(), 42);
+
+        render_list(
+            frame,
+            list,
+            &self.locale.title(Message::Tests),
+            sessions.iter().map(|session| {
+                format!(
+                    "{}  {:>5.1} {} {:>5.1}%",
+                    date::format(session.date),
+                    session.wpm,
+                    self.locale.get(Message::Wpm),
+                    session.accuracy * 100.0
+                )
+            }),
+            &self.stats,
+        );
+
+        let tab_hint = self
+            .keymap
+            .key(Context::Menu, Action::NextTab)
+            .map(|key| format!(" {} ", self.locale.fill(Message::NextTab, &[&key])))
+            .unwrap_or_default();
+
+        if self.stats_tab == StatsTab::Fingers {
+            self.render_fingers(
+                frame,
+                info,
+                sessions.get(self.stats.selected).copied(),
+                &tab_hint,
+            );
+            return;
+        }
+
+        if self.stats_tab == StatsTab::Progress {
+            trend::render(frame, info, &sessions, &tab_hint, self.locale);
+            return;
+        }
+
+        // the keyboards don't fit under a list
+        let keyboards_height = if is_narrow(frame.area()) {
+            0
+        } else {
+            heatmap::HEIGHT * 2
+        };
+        let [info, keyboards] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(keyboards_height)])
+                .areas(info);
+
+        let count = sessions.len();
+        let mean = |f: fn(&Session) -> f64| {
+            if count > 0 {
+                sessions.iter().map(|session| f(session)).sum::<f64>() / count as f64
+            } else {
+                0.0
+            }
+        };
+
+        let mut lines = vec![
+            Line::from(format!("{} {count}", self.locale.get(Message::Tests))),
+            Line::from(format!(
+                "{} {:.1}",
+                self.locale.get(Message::AverageWpm),
+                mean(|session| session.wpm)
+            )),
+            Line::from(format!(
+                "{} {:.1}",
+                self.locale.get(Message::BestWpm),
+                sessions
+                    .iter()
+                    .map(|session| session.wpm)
+                    .fold(0.0, f64::max)
+            )),
+            Line::from(format!(
+                "{} {:.1}%",
+                self.locale.get(Message::AverageAccuracy),
+                mean(|session| session.accuracy) * 100.0
+            )),
+            Line::from(format!(
+                "{} {:.2}",
+                self.locale.get(Message::AverageKspc),
+                mean_kspc(&sessions)
+            )),
+            Line::from(self.locale.fill(
+                Message::TimeTyping,
+                &[&format!(
+                    "{:.0}",
+                    sessions.iter().map(|session| session.seconds).sum::<f64>() / 60.0
+                )],
+            )),
+        ];
+
+        if self.stats_filter.is_set() {
+            lines.insert(0, Line::from(self.stats_filter.label(self.locale)).italic());
+        }
+
+        if let Some(session) = sessions.get(self.stats.selected) {
+            lines.push(Line::default());
+            lines.push(Line::from(session.target.clone()).dim());
+
+            if !session.tags.is_empty() {
+                lines.push(Line::from(format!(
+                    "{} {}",
+                    self.locale.get(Message::Tags),
+                    session.tags.join(" ")
+                )));
+            }
+
+            if !session.note.is_empty() {
+                lines.push(Line::from(format!(
+                    "{} {}",
+                    self.locale.get(Message::Note),
+                    session.note
+                )));
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::Summary))
+                    .title_bottom(tab_hint),
+            ),
+            info,
+        );
+
+        if keyboards.height > 0 {
+            heatmap::render(
+                frame,
+                keyboards,
+                &self.layout,
+                &heatmap::key_stats(sessions.iter().copied()),
+                self.locale,
+            );
+        }
+    }
+
+    /// finger load, same finger pairs and hand alternation of one test
+    #[allow(clippy::cast_precision_loss)]
+    fn render_fingers(&self, frame: &mut Frame, area: Rect, session: Option<&Session>, hint: &str) {
+        let mut lines = Vec::new();
+
+        if let Some(session) = session {
+            let stats = FingerStats::new(&session.strokes, &self.layout);
+
+            if stats.strokes == 0 {
+                lines.push(Line::from(self.locale.get(Message::NoStrokes)).dim());
+            } else {
+                for ((hand, finger), load) in FINGERS.iter().zip(stats.load) {
+                    let share = load as f64 / stats.strokes as f64;
+
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    let bar = "█".repeat((share * 40.0).round() as usize);
+
+                    lines.push(Line::from(format!(
+                        "{:>5} {:<6} {:>5.1}% {bar}",
+                        self.locale.get(Message::Hand(*hand)),
+                        self.locale.get(Message::Finger(*finger)),
+                        share * 100.0
+                    )));
+                }
+
+                lines.push(Line::default());
+                lines.push(Line::from(format!(
+                    "{} {:.1}%",
+                    self.locale.get(Message::SameFinger),
+                    stats.same_finger * 100.0
+                )));
+                lines.push(Line::from(format!(
+                    "{} {:.1}%",
+                    self.locale.get(Message::Alternation),
+                    stats.alternation * 100.0
+                )));
+            }
+        }
+
+        frame.render_widget(
+            Paragraph::new(lines).block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::Fingers))
+                    .title_bottom(hint),
+            ),
+            area,
+        );
+    }
+
+    /// whether the test passed, with how to try again when it didn't
+    fn pass_line(&self) -> Line<'static> {
+        if self.settings.budget.is_spent(&self.game) {
+            return Line::from(self.locale.get(Message::Failed)).red();
+        }
+
+        match (self.pass_criteria(), self.passed()) {
+            (_, Some(true)) => Line::from(self.locale.get(Message::Passed)).green(),
+            (Some(pass), Some(false)) => {
+                let retry = self
+                    .keymap
+                    .key(Context::Results, Action::Repeat)
+                    .map(|key| self.locale.fill(Message::TryAgain, &[&key]))
+                    .unwrap_or_default();
+
+                Line::from(
+                    self.locale
+                        .fill(Message::NotPassed, &[&pass.wpm, &pass.accuracy])
+                        + &retry,
+                )
+                .red()
+            }
+            _ => Line::default(),
+        }
+    }
+
+    /// how the test compares to earlier ones like it
+    fn percentile_line(&self) -> Line<'static> {
+        let Some(share) = self.history.percentile() else {
+            return Line::from(self.locale.get(Message::FirstTest)).dim();
+        };
+
+        Line::from(self.locale.fill(
+            Message::BetterThan,
+            &[
+                &format!("{:.0}", share * 100.0),
+                &self.game.target.split(' ').count(),
+                &self.mode.name().replace('_', " "),
+            ],
+        ))
+    }
+
+    fn render_results(&self, frame: &mut Frame) {
+        let elapsed = self.game.elapsed().as_secs();
+        let [results, flow, bottom] = Layout::vertical([
+            Constraint::Length(10),
+            Constraint::Length(5),
+            Constraint::Length(histogram::HEIGHT),
+        ])
+        .areas(centered(frame.area(), 90, 10 + 5 + histogram::HEIGHT));
+        // the hesitations are left out when only the chart fits
+        let [chart, hesitations] = if is_narrow(bottom) {
+            [bottom, Rect::default()]
+        } else {
+            Layout::horizontal([Constraint::Length(histogram::WIDTH), Constraint::Fill(1)])
+                .areas(bottom)
+        };
+
+        frame.render_widget(
+            Paragraph::new(Text::from(vec![
+                Line::from(format!(
+                    "{} {:.1}",
+                    self.locale.get(Message::Wpm),
+                    self.game.wpm()
+                )),
+                Line::from(format!(
+                    "{} {:.1}%",
+                    self.locale.get(Message::Accuracy),
+                    self.game.accuracy() * 100.0
+                )),
+                Line::from(format!(
+                    "{} {}:{:02}",
+                    self.locale.get(Message::Time),
+                    elapsed / 60,
+                    elapsed % 60
+                )),
+                Line::from(format!(
+                    "{} {}",
+                    self.locale.get(Message::BestCombo),
+                    self.game.best_combo
+                )),
+                Line::from(format!(
+                    "{} {:.2}",
+                    self.locale.get(Message::Kspc),
+                    self.game.kspc()
+                )),
+                self.percentile_line(),
+                Line::from(match self.mode {
+                    Mode::TimeAttack => self
+                        .locale
+                        .fill(Message::Score, &[&time_attack::score(&self.game)]),
+                    Mode::Survival => format!(
+                        "{} {}",
+                        self.locale.get(Message::LongestSurvival),
+                        minutes(self.best_survival())
+                    ),
+                    _ => self.code.map_or_else(String::new, |code| {
+                        format!("{} {code}", self.locale.get(Message::TestCode))
+                    }),
+                }),
+                self.pass_line(),
+            ]))
+            .alignment(Alignment::Center)
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::Results))
+                    .title_bottom(format!(" ? {} ", self.locale.get(Message::Help))),
+            ),
+            results,
+        );
+
+        frame.render_widget(
+            Sparkline::default()
+                .data(self.game.rolling_wpm())
+                .style(Style::new().fg(Color::Cyan))
+                .block(
+                    Block::bordered()
+                        .border_type(Rounded)
+                        .title(self.locale.title(Message::WpmOverTime)),
+                ),
+            flow,
+        );
+
+        histogram::render(frame, chart, &self.game.intervals(), self.locale);
+
+        let threshold = Duration::from_millis(self.settings.hesitation_ms);
+
+        frame.render_widget(
+            List::new(
+                self.game
+                    .hesitations(threshold)
+                    .into_iter()
+                    .map(|(word, gap)| format!("{:>5.1}s {word}", gap.as_secs_f64())),
+            )
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(self.locale.title(Message::Hesitations)),
+            ),
+            hesitations,
+        );
+    }
+}
+
+/// mean keystrokes per character over the sessions that counted them
+#[allow(clippy::cast_precision_loss)]
+fn mean_kspc(sessions: &[&Session]) -> f64 {
+    let kspc: Vec<f64> = sessions
+        .iter()
+        .filter_map(|session| session.kspc())
+        .collect();
+
+    if kspc.is_empty() {
+        1.0
+    } else {
+        kspc.iter().sum::<f64>() / kspc.len() as f64
+    }
+}
+
+/// move a menu selection up or down, wrapping around
+fn menu_selection(selected: usize, len: usize, action: Option<Action>) -> usize {
+    match action {
+        Some(Action::Up) => selected.checked_sub(1).unwrap_or(len - 1),
+        Some(Action::Down) => (selected + 1) % len,
+        _ => selected,
+    }
+}
+
+/// a message over the middle of the screen that any key closes
+fn render_popup(frame: &mut Frame, locale: Locale, title: &str, text: String, style: Style) {
+    let area = centered(frame.area(), 60, 8);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(text)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(title)
+                    .title_bottom(locale.title(Message::PressAnyKey)),
+            )
+            .style(style),
+        area,
+    );
+}
+
+/// a progress bar for each part of the goals
+fn render_goals(frame: &mut Frame, area: Rect, goals: &[(String, f64)], locale: Locale) {
+    let block = Block::bordered()
+        .border_type(Rounded)
+        .title(locale.title(Message::Goals));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let rows = Layout::vertical(vec![Constraint::Length(1); goals.len()]).split(inner);
+
+    for ((label, ratio), row) in goals.iter().zip(rows.iter()) {
+        frame.render_widget(
+            LineGauge::default()
+                .ratio(*ratio)
+                .label(label.clone())
+                .filled_style(Style::new().fg(if *ratio >= 1.0 {
+                    Color::Green
+                } else {
+                    Color::Cyan
+                })),
+            *row,
+        );
+    }
+}
+
+/// a bordered list showing the search query under it
+fn render_list<'a, I>(frame: &mut Frame, area: Rect, title: &str, items: I, view: &ListView)
+where
+    I: IntoIterator,
+    I::Item: Into<ListItem<'a>>,
+{
+    let search = if view.searching || !view.query.is_empty() {
+        format!(" /{} ", view.query)
+    } else {
+        String::new()
+    };
+
+    frame.render_widget(Clear, area);
+    frame.render_stateful_widget(
+        List::new(items)
+            .highlight_style(Style::new().reversed())
+            .block(
+                Block::bordered()
+                    .border_type(Rounded)
+                    .title(title)
+                    .title_bottom(search),
+            ),
+        area,
+        &mut ListState::default().with_selected(Some(view.selected)),
+    );
+}
+
+/// a popup listing the keys for a context
+fn render_help(frame: &mut Frame, keymap: &Keymap, context: Context, locale: Locale) {
+    let help = keymap.help(context);
+
+    let lines: Vec<(String, &str)> = help
+        .iter()
+        .map(|(action, keys)| {
+            (
+                keys.iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                locale.get(Message::Action(*action)),
+            )
+        })
+        .collect();
+
+    let keys_width = lines
+        .iter()
+        .map(|(keys, _)| keys.len())
+        .max()
+        .unwrap_or_default();
+    let width = lines
+        .iter()
+        .map(|(_, description)| keys_width + 2 + description.len())
+        .max()
+        .unwrap_or_default()
+        + 4;
+
+    let area = centered(
+        frame.area(),
+        u16::try_from(width).unwrap_or(u16::MAX),
+        u16::try_from(lines.len() + 2).unwrap_or(u16::MAX),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(
+            lines
+                .into_iter()
+                .map(|(keys, description)| {
+                    Line::from(vec![
+                        Span::styled(format!("{keys:>keys_width$}  "), Style::new().bold()),
+                        Span::raw(description),
+                    ])
+                })
+                .collect::<Text>(),
+        )
+        .block(
+            Block::bordered()
+                .border_type(Rounded)
+                .title(format!(
+                    " {} ",
+                    locale.fill(Message::KeysFor, &[&locale.get(Message::Context(context))])
+                ))
+                .title_bottom(locale.title(Message::PressAnyKey)),
+        ),
+        area,
+    );
+}
+
+/// a small popup listing items with the selected one highlighted
+fn render_menu(frame: &mut Frame, title: &str, items: &[&str], selected: usize) {
+    let width = items
+        .iter()
+        .map(|item| item.len())
+        .max()
+        .unwrap_or_default()
+        + 8;
+    let area = centered(
+        frame.area(),
+        u16::try_from(width).unwrap_or(u16::MAX),
+        u16::try_from(items.len() + 2).unwrap_or(u16::MAX),
+    );
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(
+            items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| {
+                    if i == selected {
+                        Line::from(*item).reversed()
+                    } else {
+                        Line::from(*item)
+                    }
+                })
+                .collect::<Text>(),
+        )
+        .alignment(Alignment::Center)
+        .block(Block::bordered().border_type(Rounded).title(title)),
+        area,
+    );
+}
+
+fn run(terminal: &mut DefaultTerminal, mut app: App) -> Result<()> {
+    let mut changed = true;
+    let mut shown_seconds = None;
+
+    while app.running {
+        // with reduced motion the timer is the only thing drawn without a change,
+        // once a second instead of every tick
+        let seconds = matches!(app.screen, Screen::Game).then(|| app.game.elapsed().as_secs());
+
+        if changed || !app.reduced_motion || seconds != shown_seconds {
+            terminal.draw(|frame| app.draw(frame))?;
+            shown_seconds = seconds;
+        }
+
+        // wait at most a tick so timers keep moving without input
+        changed = ratatui::crossterm::event::poll(app.settings.tick_rate())?;
+
+        if changed {
+            app.crossterm_event(&ratatui::crossterm::event::read()?)?;
+        }
+
+        changed |= app.tick();
+    }
+
+    Ok(())
+}
+
+/// the terminal game
+fn play(
+    profile: Option<String>,
+    overrides: toml::Table,
+    emit_events: Option<&Path>,
+    live_stats: Option<&Path>,
+    serve_events: Option<u16>,
+    start: Start,
+    mut errors: Vec<Error>,
+) -> Result<()> {
+    // fail before taking over the terminal
+    let events = emit_events.map(EventLog::open).transpose()?;
+    let live_stats = live_stats.map(LiveStats::open).transpose()?;
+    let event_server = serve_events.map(EventServer::bind).transpose()?;
+
+    let watch = ConfigWatch::new(config_paths(profile.as_deref()), overrides);
+    let config = watch.load(&mut errors)?;
+
+    if config.accessible {
+        return accessible::run(&config, profile.as_deref(), errors, start);
+    }
+
+    let mut app = App::new(config, profile, errors)?;
+    app.events = events;
+    app.live_stats = live_stats;
+    app.event_server = event_server;
+    app.config_watch = Some(watch);
+
+    match start {
+        Start::Menu => (),
+        Start::Code(code) => app.play_code(code)?,
+        Start::Lesson(lesson) => app.play_lesson(lesson)?,
+    }
+
+    let mut terminal = ratatui::init();
+
+    // the mouse only scrolls, the game plays the same without it
+    let _ = ratatui::crossterm::execute!(
+        terminal.backend_mut(),
+        ratatui::crossterm::event::EnableMouseCapture
+    );
+
+    let result = run(&mut terminal, app);
+
+    let _ = ratatui::crossterm::execute!(
+        terminal.backend_mut(),
+        ratatui::crossterm::event::DisableMouseCapture
+    );
+
+    ratatui::restore();
+
+    result
+}
+
+fn main() {
+    let result = Cli::parse(std::env::args().skip(1)).and_then(|cli| {
+        // before anything reads the words
+        let mut errors = Vec::new();
+        load_user_words(cli.profile.as_deref(), &mut errors);
+
+        if !matches!(cli.command, Command::Play { .. }) {
+            for error in std::mem::take(&mut errors) {
+                eprintln!("{error}");
+            }
+        }
+
+        match cli.command {
+            Command::Play {
+                emit_events,
+                live_stats,
+                serve_events,
+                start,
+            } => play(
+                cli.profile,
+                cli.overrides,
+                emit_events.as_deref(),
+                live_stats.as_deref(),
+                serve_events,
+                start,
+                errors,
+            ),
+            Command::Simulate { input_file, format } => simulate::run(&input_file, format),
+            Command::Report { files, format } => report::run(&files, format),
+            Command::Week { file, format, html } => {
+                let Some(path) = file.or_else(|| data_path(cli.profile.as_deref(), "history.toml"))
+                else {
+                    return Err(Error::Usage(
+                        "no data directory to find the history in, give a history file".to_string(),
+                    ));
+                };
+
+                weekly::run(&path, format, html)
+            }
+            Command::Import { files } => {
+                let Some(path) = data_path(cli.profile.as_deref(), "history.toml") else {
+                    return Err(Error::Usage(
+                        "no data directory to import the history into".to_string(),
+                    ));
+                };
+
+                let config = ConfigWatch::new(config_paths(cli.profile.as_deref()), cli.overrides)
+                    .load(&mut errors)?;
+
+                for error in errors {
+                    eprintln!("{error}");
+                }
+
+                import::run(&files, &path, config.privacy)?;
+                config.hooks.after_save(&path)
+            }
+            Command::Keylog { files, format } if files.is_empty() => {
+                let Some(path) = data_path(cli.profile.as_deref(), "history.toml") else {
+                    return Err(Error::Usage(
+                        "no data directory to find the history in, give a history file".to_string(),
+                    ));
+                };
+
+                keylog::run(&[path], format)
+            }
+            Command::Keylog { files, format } => keylog::run(&files, format),
+            Command::Stats { compact, format } => {
+                let summary = data_path(cli.profile.as_deref(), summary::FILE)
+                    .map(|path| storage::load::<Summary>(&path))
+                    .transpose()?
+                    .flatten()
+                    .unwrap_or_default()
+                    .on(date::now());
+
+                match format {
+                    Format::Text if compact => println!("{}", summary.compact()),
+                    Format::Text => {
+                        for line in summary.lines() {
+                            println!("{line}");
+                        }
+                    }
+                    Format::Tsv => output::print_tsv(&[summary]),
+                    Format::Json | Format::Toml => format.print_document(&summary)?,
+                }
+
+                Ok(())
+            }
+            Command::Words { filter, format } => vocabulary::run(&filter, format),
+            Command::Completions { shell } => completions::run(&shell),
+            Command::Version { verbose } => {
+                if verbose {
+                    for line in about::lines(Locale::default()) {
+                        println!("{line}");
+                    }
+                } else {
+                    println!("tt {}", env!("CARGO_PKG_VERSION"));
+                }
+
+                Ok(())
+            }
+        }
+    });
+
+    if let Err(error) = result {
+        eprintln!("{error}");
+
+        if let Error::Usage(_) = error {
+            eprintln!("{}", cli::USAGE);
+        }
+
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masked(texts: &[&str], reveal: Reveal) -> Vec<String> {
+        let column = Cell::new(0);
+        texts
+            .iter()
+            .map(|text| mask(text, &column, reveal))
+            .collect()
+    }
+
+    #[test]
+    fn delete_last_word_keeps_the_space_before_it() {
+        for (text, left) in [
+            ("mi moku", "mi "),
+            ("mi moku  ", "mi "),
+            ("moku", ""),
+            ("", ""),
+            // an ideographic space and a no-break space are more than a byte
+            ("mi\u{3000}moku", "mi\u{3000}"),
+            ("mi\u{a0}moku\u{a0}", "mi\u{a0}"),
+        ] {
+            let mut text = text.to_string();
+            delete_last_word(&mut text);
+            assert_eq!(text, left);
+        }
+    }
+
+    #[test]
+    fn hints_show_the_first_letters_of_each_word() {
+        assert_eq!(masked(&["tomo pona"], Reveal::Hint(2)), ["to__ po__"]);
+        assert_eq!(masked(&["mi"], Reveal::Hint(2)), ["mi"]);
+    }
+
+    #[test]
+    fn hints_count_the_letters_already_typed() {
+        // "to" is typed, the rest of the word comes in another span
+        assert_eq!(
+            masked(&["to", "mo pona"], Reveal::Hint(3)),
+            ["to", "m_ pon_"]
+        );
+    }
+
+    #[test]
+    fn no_hint_hides_every_letter_but_keeps_the_width() {
+        assert_eq!(masked(&["mi moku, 漢"], Reveal::Hint(0)), ["__ _____ __"]);
+    }
+
+    #[test]
+    fn memory_mode_shows_the_words_until_the_preview_ends() {
+        let settings = GameSettings {
+            memory_secs: 5,
+            hint_letters: 2,
+            ..GameSettings::default()
+        };
+
+        assert_eq!(
+            masked(&["mi moku"], reveal(&settings, false, true)),
+            ["mi moku"]
+        );
+        assert_eq!(
+            masked(&["mi moku"], reveal(&settings, false, false)),
+            ["       "]
+        );
+    }
+
+    #[test]
+    fn a_preview_shows_the_words() {
+        let settings = GameSettings {
+            preview: true,
+            ..GameSettings::default()
+        };
+
+        assert_eq!(
+            masked(&["mi moku"], reveal(&settings, true, false)),
+            ["mi moku"]
+        );
+        assert_eq!(
+            masked(&["mi moku"], reveal(&settings, false, false)),
+            ["__ ____"]
+        );
+    }
+
+    #[test]
+    fn blank_hides_the_words_and_their_lengths() {
+        assert_eq!(masked(&["mi moku"], Reveal::Blank), ["       "]);
+    }
 }
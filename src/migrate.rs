@@ -0,0 +1,233 @@
+use crate::{
+    error::{Error, Result},
+    history,
+};
+use std::path::Path;
+
+/// the history format written now, bumped with each change old files need upgrading for
+pub const HISTORY: i64 = 2;
+/// the config format read now
+pub const CONFIG: i64 = 1;
+/// the snapshot format written now
+pub const SNAPSHOT: i64 = 1;
+
+/// upgrade a history table read from a file to the current format
+///
+/// # Errors
+/// `Error::NewerVersion` when the file is from a newer version
+pub fn history(path: &Path, table: &mut toml::Table) -> Result<()> {
+    let version = version(path, table, HISTORY)?;
+
+    if version < 1 {
+        stroke_words(table);
+    }
+
+    if version < 2 {
+        ids_and_words(table);
+    }
+
+    table.insert("version".to_string(), HISTORY.into());
+
+    Ok(())
+}
+
+/// upgrade one config file's table to the current format, the version isn't a setting
+///
+/// # Errors
+/// `Error::NewerVersion` when the file is from a newer version
+pub fn config(path: &Path, table: &mut toml::Table) -> Result<()> {
+    version(path, table, CONFIG)?;
+    table.remove("version");
+
+    Ok(())
+}
+
+/// upgrade a snapshot table to the current format
+///
+/// # Errors
+/// `Error::NewerVersion` when the file is from a newer version
+pub fn snapshot(path: &Path, table: &mut toml::Table) -> Result<()> {
+    let version = version(path, table, SNAPSHOT)?;
+
+    // snapshots didn't keep their mode, only drills had prompts
+    if version < 1 {
+        let drill = table
+            .get("prompts")
+            .and_then(toml::Value::as_array)
+            .is_some_and(|prompts| !prompts.is_empty());
+
+        table
+            .entry("mode")
+            .or_insert_with(|| if drill { "numbers" } else { "words" }.into());
+    }
+
+    table.insert("version".to_string(), SNAPSHOT.into());
+
+    Ok(())
+}
+
+/// the format a file was written in, 0 for files from before there were versions
+fn version(path: &Path, table: &toml::Table, current: i64) -> Result<i64> {
+    let version = table
+        .get("version")
+        .and_then(toml::Value::as_integer)
+        .unwrap_or(0);
+
+    // saving it again would lose whatever the newer format added
+    if version > current {
+        return Err(Error::NewerVersion {
+            path: path.to_path_buf(),
+            version,
+        });
+    }
+
+    Ok(version)
+}
+
+/// strokes were saved without the index of the word they were typed in,
+/// every space typed before a stroke moves it on a word
+fn stroke_words(table: &mut toml::Table) {
+    let Some(toml::Value::Array(sessions)) = table.get_mut("sessions") else {
+        return;
+    };
+
+    for session in sessions {
+        let Some(toml::Value::Array(strokes)) = session.get_mut("strokes") else {
+            continue;
+        };
+
+        let mut word: i64 = 0;
+
+        for stroke in strokes {
+            let toml::Value::Array(stroke) = stroke else {
+                continue;
+            };
+
+            if stroke.len() == 3 {
+                stroke.push(word.into());
+            }
+
+            if stroke.first().and_then(toml::Value::as_str) == Some(" ") {
+                word += 1;
+            }
+        }
+    }
+}
+
+/// sessions were saved without an id or a word count, the words are counted from the target,
+/// which is still there since taking it out needs the count
+fn ids_and_words(table: &mut toml::Table) {
+    let Some(toml::Value::Array(sessions)) = table.get_mut("sessions") else {
+        return;
+    };
+
+    for session in sessions {
+        let Some(session) = session.as_table_mut() else {
+            continue;
+        };
+
+        let words = session
+            .get("target")
+            .and_then(toml::Value::as_str)
+            .unwrap_or_default()
+            .split_whitespace()
+            .count();
+
+        session
+            .entry("id")
+            .or_insert_with(|| history::new_id().into());
+        session
+            .entry("words")
+            .or_insert_with(|| i64::try_from(words).unwrap_or(i64::MAX).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::History;
+
+    const V0: &str = r#"
+        [[sessions]]
+        date = 86400
+        target = "mi moku"
+        input = "mi moku"
+        wpm = 40.0
+        accuracy = 1.0
+        seconds = 2.0
+        best_combo = 7
+        finished = true
+        strokes = [["m", true, 0], ["i", true, 100], [" ", true, 200], ["m", true, 300]]
+    "#;
+
+    #[test]
+    fn history_upgrades_a_table_from_before_versions() {
+        let mut table: toml::Table = toml::from_str(V0).unwrap();
+        history(Path::new("history.toml"), &mut table).unwrap();
+
+        assert_eq!(table["version"].as_integer(), Some(HISTORY));
+
+        let history: History = toml::Value::Table(table).try_into().unwrap();
+        let session = &history.sessions[0];
+
+        assert_eq!(
+            session.strokes,
+            [
+                ('m', true, 0, 0),
+                ('i', true, 100, 0),
+                (' ', true, 200, 0),
+                ('m', true, 300, 1)
+            ]
+        );
+        assert_eq!(session.words, 2);
+        assert_eq!(session.id.len(), 32);
+        assert!(session.id.chars().all(|c| c.is_ascii_hexdigit()));
+    }
+
+    #[test]
+    fn history_keeps_what_a_newer_version_already_has() {
+        let mut table: toml::Table = toml::from_str(V0).unwrap();
+        let session = table["sessions"][0].as_table_mut().unwrap();
+        session.insert("id".to_string(), "abc".into());
+        session.insert("words".to_string(), 5.into());
+        table.insert("version".to_string(), 1.into());
+
+        history(Path::new("history.toml"), &mut table).unwrap();
+
+        let session = &table["sessions"][0];
+        assert_eq!(session["id"].as_str(), Some("abc"));
+        assert_eq!(session["words"].as_integer(), Some(5));
+        // only tables from before version 1 get a word added to their strokes
+        assert_eq!(session["strokes"][0].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn snapshot_gets_the_mode_it_was_saved_in() {
+        for (prompts, mode) in [("[]", "words"), (r#"["7"]"#, "numbers")] {
+            let mut table: toml::Table = toml::from_str(&format!("prompts = {prompts}")).unwrap();
+            snapshot(Path::new("session.toml"), &mut table).unwrap();
+
+            assert_eq!(table["mode"].as_str(), Some(mode));
+            assert_eq!(table["version"].as_integer(), Some(SNAPSHOT));
+        }
+
+        let mut table: toml::Table = toml::from_str(r#"mode = "custom""#).unwrap();
+        table.insert("version".to_string(), SNAPSHOT.into());
+        snapshot(Path::new("session.toml"), &mut table).unwrap();
+
+        assert_eq!(table["mode"].as_str(), Some("custom"));
+    }
+
+    #[test]
+    fn history_refuses_a_newer_version() {
+        let mut table = toml::Table::new();
+        table.insert("version".to_string(), (HISTORY + 1).into());
+
+        let result = history(Path::new("history.toml"), &mut table);
+
+        assert!(matches!(
+            result,
+            Err(Error::NewerVersion { version, .. }) if version == HISTORY + 1
+        ));
+    }
+}
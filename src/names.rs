@@ -0,0 +1,235 @@
+use crate::words;
+use rand::{seq::IndexedRandom, Rng};
+
+/// the words whose glyphs spell each letter in a cartouche, by the sound they start with
+const LETTERS: [(char, &str); 14] = [
+    ('a', "akesi"),
+    ('e', "esun"),
+    ('i', "ijo"),
+    ('j', "jan"),
+    ('k', "kasi"),
+    ('l', "lipu"),
+    ('m', "mama"),
+    ('n', "nena"),
+    ('o', "olin"),
+    ('p', "pipi"),
+    ('s', "sona"),
+    ('t', "tawa"),
+    ('u', "uta"),
+    ('w', "waso"),
+];
+
+/// the ucsur characters around a cartouche
+const CARTOUCHE_START: char = '\u{F1990}';
+const CARTOUCHE_END: char = '\u{F1991}';
+
+/// loan names with the word that heads them, names always start with a capital,
+/// the ones mixed into tests, the packs below are for practicing names on their own
+pub const NAMES: &[(&str, &str)] = &[
+    ("jan", "Mali"),
+    ("jan", "Sonja"),
+    ("jan", "Lisa"),
+    ("jan", "Pata"),
+    ("jan", "Tomi"),
+    ("jan", "Ana"),
+    ("ma", "Kanata"),
+    ("ma", "Nijon"),
+    ("ma", "Kanse"),
+    ("ma", "Mewika"),
+    ("ma", "Tosi"),
+    ("ma", "Sumi"),
+    ("ma", "Italija"),
+    ("toki", "Inli"),
+    ("toki", "Epanja"),
+    ("toki", "Kanse"),
+];
+
+/// a list of names to practice on their own, all headed by the same word
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Pack {
+    People,
+    Countries,
+    Languages,
+}
+
+impl Pack {
+    pub const ALL: [Self; 3] = [Self::People, Self::Countries, Self::Languages];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::People => "people",
+            Self::Countries => "countries",
+            Self::Languages => "languages",
+        }
+    }
+
+    /// the word every name in the pack comes after
+    #[must_use]
+    pub const fn head(self) -> &'static str {
+        match self {
+            Self::People => "jan",
+            Self::Countries => "ma",
+            Self::Languages => "toki",
+        }
+    }
+
+    #[must_use]
+    pub const fn names(self) -> &'static [&'static str] {
+        match self {
+            Self::People => &[
+                "Sonja",
+                "Misali",
+                "Lipamanka",
+                "Mali",
+                "Lisa",
+                "Pata",
+                "Tomi",
+                "Ana",
+                "Ken",
+                "Nina",
+                "Sami",
+                "Pilipo",
+                "Jakopo",
+                "Mija",
+                "Wiljan",
+                "Emili",
+                "Keli",
+                "Pawi",
+            ],
+            Self::Countries => &[
+                "Kanata",
+                "Mewika",
+                "Mesiko",
+                "Pasila",
+                "Alensina",
+                "Inli",
+                "Sukosi",
+                "Kemuli",
+                "Kanse",
+                "Tosi",
+                "Epanja",
+                "Italija",
+                "Netelan",
+                "Tansi",
+                "Nosiki",
+                "Sumi",
+                "Losi",
+                "Elena",
+                "Tuki",
+                "Masu",
+                "Kenja",
+                "Isale",
+                "Ilan",
+                "Palata",
+                "Sonko",
+                "Anku",
+                "Nijon",
+                "Pilipina",
+                "Intonesija",
+                "Oselija",
+            ],
+            Self::Languages => &[
+                "Inli",
+                "Epanja",
+                "Kanse",
+                "Tosi",
+                "Italija",
+                "Potuke",
+                "Losi",
+                "Elena",
+                "Latina",
+                "Sumi",
+                "Nosiki",
+                "Tansi",
+                "Kemuli",
+                "Alapi",
+                "Ipiwi",
+                "Insi",
+                "Sonko",
+                "Nijon",
+                "Anku",
+                "Tuki",
+                "Intonesija",
+                "Esuka",
+                "Losupan",
+            ],
+        }
+    }
+
+    /// names from the pack with their head word until there are at least count words,
+    /// never the same name twice in a row
+    pub fn words(self, count: usize, rng: &mut impl Rng) -> Vec<&'static str> {
+        let names = self.names();
+        let mut words = Vec::new();
+        let mut last = None;
+
+        while words.len() < count {
+            let Some(&name) = names.choose(rng) else {
+                break;
+            };
+
+            if names.len() > 1 && last == Some(name) {
+                continue;
+            }
+
+            words.extend([self.head(), name]);
+            last = Some(name);
+        }
+
+        words
+    }
+}
+
+/// names from every pack without their head words until there are count,
+/// never the same name twice in a row
+pub fn bare_names(count: usize, rng: &mut impl Rng) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Pack::ALL
+        .iter()
+        .flat_map(|pack| pack.names().iter().copied())
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut words: Vec<&'static str> = Vec::new();
+
+    while words.len() < count {
+        let Some(&name) = names.choose(rng) else {
+            break;
+        };
+
+        if names.len() == 1 || words.last() != Some(&name) {
+            words.push(name);
+        }
+    }
+
+    words
+}
+
+/// a name spelled in sitelen pona, a glyph for each letter inside a cartouche,
+/// None when a letter has no glyph
+#[must_use]
+pub fn cartouche(name: &str) -> Option<String> {
+    let glyphs = name
+        .chars()
+        .map(|c| {
+            let c = c.to_lowercase().next()?;
+            let (_, word) = LETTERS.iter().find(|(letter, _)| *letter == c)?;
+            words::glyph(word)
+        })
+        .collect::<Option<String>>()?;
+
+    Some(format!("{CARTOUCHE_START}{glyphs}{CARTOUCHE_END}"))
+}
+
+/// insert count names with their head words at random places between the words
+pub fn mix_in(words: &mut Vec<&str>, count: usize, rng: &mut impl Rng) {
+    for _ in 0..count {
+        let Some((head, name)) = NAMES.choose(rng) else {
+            return;
+        };
+
+        let index = rng.random_range(0..=words.len());
+        words.splice(index..index, [*head, *name]);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    punctuation,
+    settings::GameSettings,
+    words::{word_field, WORDS},
+};
+use rand::{seq::IndexedRandom, Rng};
+
+/// usage categories of the words swapped in
+const RARE: [&str; 2] = ["obscure", "sandbox"];
+
+/// change about `settings.noise` of the words so the test can't be typed on autopilot,
+/// each one is swapped for a rare word, capitalized or has a letter doubled
+pub fn garble(target: &str, settings: &GameSettings<usize>, rng: &mut impl Rng) -> String {
+    let chance = settings.noise.clamp(0.0, 1.0);
+
+    if chance == 0.0 {
+        return target.to_string();
+    }
+
+    // the map's order changes between runs, so sort to keep a seed giving the same test
+    let mut rare: Vec<&str> = WORDS
+        .iter()
+        .filter(|(word, toml)| {
+            settings.allows(word)
+                && word_field(toml, "usage_category").is_ok_and(|category| RARE.contains(&category))
+        })
+        .map(|(word, _)| word.as_str())
+        .collect();
+    rare.sort_unstable();
+
+    target
+        .split(' ')
+        .map(|word| {
+            if !rng.random_bool(chance) {
+                return word.to_string();
+            }
+
+            match rng.random_range(0..3) {
+                0 => rare.choose(rng).map_or_else(
+                    || word.to_string(),
+                    |rare| word.replacen(punctuation::bare(word), rare, 1),
+                ),
+                1 => capitalize(word),
+                _ => double_letter(word, rng),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// one of the word's letters typed twice
+fn double_letter(word: &str, rng: &mut impl Rng) -> String {
+    let letters: Vec<usize> = word
+        .char_indices()
+        .filter(|(_, c)| c.is_alphabetic())
+        .map(|(index, _)| index)
+        .collect();
+
+    let Some(&index) = letters.choose(rng) else {
+        return word.to_string();
+    };
+
+    let letter = word[index..].chars().next().unwrap_or_default();
+    let mut word = word.to_string();
+    word.insert(index, letter);
+    word
+}
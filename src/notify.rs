@@ -0,0 +1,15 @@
+/// a desktop notification, for practice in a terminal that is out of sight
+///
+/// sent from a thread of its own since showing one can wait on the desktop, so a failure
+/// goes unseen like one of a hook
+#[cfg(feature = "notify")]
+pub fn send(summary: &str, body: &str) {
+    let mut notification = notify_rust::Notification::new();
+    notification.appname("tt").summary(summary).body(body);
+
+    std::thread::spawn(move || notification.show().map(drop));
+}
+
+/// built without the notify feature, nothing is sent
+#[cfg(not(feature = "notify"))]
+pub const fn send(_summary: &str, _body: &str) {}
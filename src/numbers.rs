@@ -0,0 +1,144 @@
+use rand::Rng;
+
+/// how numbers are written out in toki pona
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberSystem {
+    /// only wan and tu added together, as in pu
+    #[default]
+    Simple,
+    /// nasin nanpa pona, ale 100, mute 20, luka 5, tu 2 and wan 1 added together
+    Pona,
+}
+
+impl NumberSystem {
+    /// number words from largest to smallest with their values
+    const fn words(self) -> &'static [(u32, &'static str)] {
+        match self {
+            Self::Simple => &[(2, "tu"), (1, "wan")],
+            Self::Pona => &[
+                (100, "ale"),
+                (20, "mute"),
+                (5, "luka"),
+                (2, "tu"),
+                (1, "wan"),
+            ],
+        }
+    }
+
+    /// largest number the drill asks for
+    const fn max(self) -> u32 {
+        match self {
+            Self::Simple => 6,
+            Self::Pona => 120,
+        }
+    }
+
+    /// the toki pona phrase for a number
+    #[must_use]
+    pub fn phrase(self, mut n: u32) -> String {
+        if n == 0 {
+            return "ala".to_string();
+        }
+
+        let mut words = Vec::new();
+
+        for (value, word) in self.words() {
+            while n >= *value {
+                words.push(*word);
+                n -= value;
+            }
+        }
+
+        words.join(" ")
+    }
+
+    /// random numbers with their phrases until there are at least len words,
+    /// never the same number twice in a row
+    pub fn drill(self, len: usize, rng: &mut impl Rng) -> Vec<(u32, String)> {
+        let mut drill: Vec<(u32, String)> = Vec::new();
+        let mut words = 0;
+
+        while words < len {
+            let n = rng.random_range(1..=self.max());
+
+            if drill.last().is_none_or(|(last, _)| *last != n) {
+                let phrase = self.phrase(n);
+                words += phrase.split(' ').count();
+                drill.push((n, phrase));
+            }
+        }
+
+        drill
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn phrases() {
+        use NumberSystem::{Pona, Simple};
+
+        for (system, n, phrase) in [
+            (Simple, 0, "ala"),
+            (Simple, 1, "wan"),
+            (Simple, 2, "tu"),
+            (Simple, 3, "tu wan"),
+            (Simple, 6, "tu tu tu"),
+            (Pona, 0, "ala"),
+            (Pona, 1, "wan"),
+            (Pona, 4, "tu tu"),
+            (Pona, 17, "luka luka luka tu"),
+            (Pona, 20, "mute"),
+            (Pona, 99, "mute mute mute mute luka luka luka tu tu"),
+            (Pona, 100, "ale"),
+            (Pona, 120, "ale mute"),
+            (Pona, 248, "ale ale mute mute luka tu wan"),
+        ] {
+            assert_eq!(system.phrase(n), phrase, "{system:?} {n}");
+        }
+    }
+
+    #[test]
+    fn phrases_add_up_to_their_number() {
+        for system in [NumberSystem::Simple, NumberSystem::Pona] {
+            for n in 1..=system.max() * 3 {
+                let sum: u32 = system
+                    .phrase(n)
+                    .split(' ')
+                    .map(|word| system.words().iter().find(|(_, w)| *w == word).unwrap().0)
+                    .sum();
+
+                assert_eq!(sum, n, "{system:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn drills() {
+        for (system, len) in [
+            (NumberSystem::Simple, 0),
+            (NumberSystem::Simple, 1),
+            (NumberSystem::Simple, 50),
+            (NumberSystem::Pona, 1),
+            (NumberSystem::Pona, 50),
+        ] {
+            let drill = system.drill(len, &mut StdRng::seed_from_u64(7));
+            let words: usize = drill
+                .iter()
+                .map(|(_, phrase)| phrase.split(' ').count())
+                .sum();
+
+            assert!(words >= len, "{system:?} {len}");
+            // a prompt is only added while there are too few words
+            assert!(drill.is_empty() == (len == 0));
+            assert!(drill
+                .iter()
+                .all(|(n, phrase)| (1..=system.max()).contains(n) && *phrase == system.phrase(*n)));
+            assert!(drill.windows(2).all(|pair| pair[0].0 != pair[1].0));
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::str::FromStr;
+use tt::{
+    error::{Error, Result},
+    summary::Summary,
+};
+
+/// how a subcommand prints its results, from `--format`
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// for reading in the terminal
+    #[default]
+    Text,
+    Json,
+    Toml,
+    /// tab separated values with a header line
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "toml" => Ok(Self::Toml),
+            "tsv" => Ok(Self::Tsv),
+            _ => Err(Error::Usage(format!(
+                "unknown format {s:?}, use text, json, toml or tsv"
+            ))),
+        }
+    }
+}
+
+/// a row of tsv output
+pub trait Record {
+    /// the header, the same names as the json and toml keys
+    const FIELDS: &'static [&'static str];
+
+    fn values(&self) -> Vec<String>;
+}
+
+impl Format {
+    /// print a whole result as json, or as toml for every other format
+    pub fn print_document<T: serde::Serialize>(self, value: &T) -> Result<()> {
+        let text = match self {
+            Self::Json => serde_json::to_string_pretty(value)
+                .map_err(|error| Error::Output(error.to_string()))?,
+            Self::Toml | Self::Text | Self::Tsv => {
+                toml::to_string(value).map_err(|error| Error::Output(error.to_string()))?
+            }
+        };
+
+        println!("{}", text.trim_end());
+
+        Ok(())
+    }
+}
+
+/// print records as tab separated values under a header
+pub fn print_tsv<R: Record>(records: &[R]) {
+    println!("{}", R::FIELDS.join("\t"));
+
+    for record in records {
+        println!("{}", record.values().join("\t"));
+    }
+}
+
+impl Record for Summary {
+    const FIELDS: &'static [&'static str] = &["day", "streak", "tests_today", "best_wpm_today"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.day.to_string(),
+            self.streak.to_string(),
+            self.tests_today.to_string(),
+            self.best_wpm_today.to_string(),
+        ]
+    }
+}
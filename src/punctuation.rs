@@ -0,0 +1,40 @@
+use rand::Rng;
+
+/// fewest and most words in a generated sentence
+const SENTENCE: std::ops::RangeInclusive<usize> = 3..=8;
+
+/// join words into sentences ending in periods or question marks with the odd comma or colon
+pub fn sentences(words: &[&str], rng: &mut impl Rng) -> String {
+    let mut text = String::new();
+    let mut rest = words;
+
+    while !rest.is_empty() {
+        let len = rng.random_range(SENTENCE).min(rest.len());
+        let (sentence, tail) = rest.split_at(len);
+        // a break somewhere inside the sentence, never after its last word
+        let pause = (len > 3 && rng.random_bool(0.5)).then(|| rng.random_range(1..len - 1));
+
+        for (i, word) in sentence.iter().enumerate() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+
+            text.push_str(word);
+
+            if pause == Some(i) {
+                text.push(if rng.random_bool(0.75) { ',' } else { ':' });
+            }
+        }
+
+        text.push(if rng.random_bool(0.8) { '.' } else { '?' });
+        rest = tail;
+    }
+
+    text
+}
+
+/// a word of the target without the punctuation around it
+#[must_use]
+pub fn bare(word: &str) -> &str {
+    word.trim_matches(|c: char| c.is_ascii_punctuation())
+}
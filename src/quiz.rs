@@ -0,0 +1,123 @@
+use crate::{
+    error::{Error, Result},
+    game::Game,
+    punctuation,
+    settings::GameSettings,
+    words::{glosses, WORDS},
+};
+use rand::{seq::IndexedRandom, Rng};
+use ratatui::crossterm::event::KeyCode;
+
+/// questions in a quiz
+pub const QUESTIONS: usize = 20;
+
+/// a gloss is picked from this many of a word's most given translations
+const TOP_GLOSSES: usize = 3;
+
+pub struct Question {
+    /// an english translation from ku
+    pub gloss: String,
+    /// the word the gloss was picked from
+    pub word: String,
+    /// every word ku gives the gloss for, sorted
+    pub answers: Vec<&'static str>,
+}
+
+impl Question {
+    #[must_use]
+    pub fn accepts(&self, answer: &str) -> bool {
+        self.answers.contains(&answer.trim())
+    }
+}
+
+/// english glosses to answer with the toki pona word, any word with the gloss is right
+pub struct Quiz {
+    pub questions: Vec<Question>,
+    /// what was answered to each question so far
+    pub answered: Vec<String>,
+    pub input: String,
+}
+
+impl Quiz {
+    /// words picked like a test, the ones ku has no translations for are left out
+    ///
+    /// # Errors
+    /// `Error::NoWords` when none of the words have translations, and the errors of `Game::new`
+    pub fn new(settings: &GameSettings<usize>, rng: &mut impl Rng) -> Result<Self> {
+        let game: Game<KeyCode> = Game::new(
+            &GameSettings {
+                len: QUESTIONS,
+                proper_names: 0,
+                punctuation: false,
+                noise: 0.0,
+                ..settings.clone()
+            },
+            rng,
+        )?;
+
+        let questions: Vec<Question> = game
+            .target
+            .split(' ')
+            .filter_map(|word| question(punctuation::bare(word), rng))
+            .collect();
+
+        if questions.is_empty() {
+            return Err(Error::NoWords);
+        }
+
+        Ok(Self {
+            questions,
+            answered: Vec::new(),
+            input: String::new(),
+        })
+    }
+
+    #[must_use]
+    pub fn current(&self) -> Option<&Question> {
+        self.questions.get(self.answered.len())
+    }
+
+    #[must_use]
+    pub fn is_over(&self) -> bool {
+        self.current().is_none()
+    }
+
+    /// answer the current question with the input
+    pub fn submit(&mut self) {
+        if !self.is_over() && !self.input.trim().is_empty() {
+            self.answered.push(std::mem::take(&mut self.input));
+        }
+    }
+
+    /// right answers so far
+    #[must_use]
+    pub fn score(&self) -> usize {
+        self.questions
+            .iter()
+            .zip(&self.answered)
+            .filter(|(question, answer)| question.accepts(answer))
+            .count()
+    }
+}
+
+fn question(word: &str, rng: &mut impl Rng) -> Option<Question> {
+    let glosses = glosses(word);
+    let gloss = *glosses[..glosses.len().min(TOP_GLOSSES)].choose(rng)?;
+
+    let mut answers: Vec<&'static str> = WORDS
+        .iter()
+        .filter(|(_, toml)| {
+            toml.get("ku_data")
+                .and_then(toml::Value::as_table)
+                .is_some_and(|ku| ku.contains_key(gloss))
+        })
+        .map(|(word, _)| word.as_str())
+        .collect();
+    answers.sort_unstable();
+
+    Some(Question {
+        gloss: gloss.to_string(),
+        word: word.to_string(),
+        answers,
+    })
+}
@@ -0,0 +1,200 @@
+use crate::output::{self, Format, Record};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+use tt::{
+    error::{Error, Result},
+    history::{History, Session},
+    punctuation,
+    words::word_field,
+    WORDS,
+};
+
+/// class for target words that aren't in the dictionary, like names
+const UNKNOWN_CLASS: &str = "other";
+
+/// finished tests of one student
+#[derive(serde::Serialize)]
+struct Student {
+    name: String,
+    tests: usize,
+    wpm: f64,
+    /// a fraction like in the history
+    accuracy: f64,
+}
+
+impl Record for Student {
+    const FIELDS: &'static [&'static str] = &["name", "tests", "wpm", "accuracy"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.tests.to_string(),
+            self.wpm.to_string(),
+            self.accuracy.to_string(),
+        ]
+    }
+}
+
+/// words of one usage category typed across every student
+#[derive(Default, serde::Serialize)]
+struct Category {
+    class: &'static str,
+    words: usize,
+    errors: usize,
+    /// errors over words
+    error_rate: f64,
+}
+
+impl Record for Category {
+    const FIELDS: &'static [&'static str] = &["class", "words", "errors", "error_rate"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.class.to_string(),
+            self.words.to_string(),
+            self.errors.to_string(),
+            self.error_rate.to_string(),
+        ]
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Report {
+    students: Vec<Student>,
+    classes: Vec<Category>,
+}
+
+/// read the history files of a class of students and print one report over all of them
+#[allow(clippy::cast_precision_loss)]
+pub fn run(files: &[PathBuf], format: Format) -> Result<()> {
+    let mut students = Vec::new();
+    let mut classes: BTreeMap<&'static str, Category> = BTreeMap::new();
+
+    for path in files {
+        // a missing history loads as empty, which would hide a mistyped path
+        if !path.exists() {
+            return Err(Error::Read {
+                path: path.clone(),
+                source: std::io::ErrorKind::NotFound.into(),
+            });
+        }
+
+        let history = History::load(path)?;
+        let finished: Vec<_> = history
+            .sessions
+            .iter()
+            .filter(|session| session.finished)
+            .collect();
+
+        let tests = finished.len();
+        let mean = |value: fn(&Session) -> f64| {
+            finished.iter().map(|session| value(session)).sum::<f64>() / tests.max(1) as f64
+        };
+
+        students.push(Student {
+            name: student_name(path),
+            tests,
+            wpm: mean(|session| session.wpm),
+            accuracy: mean(|session| session.accuracy),
+        });
+
+        for session in &finished {
+            for (target, input) in session
+                .target
+                .split_whitespace()
+                .zip(session.input.split_whitespace())
+            {
+                let name = word_class(target);
+                let class = classes.entry(name).or_insert_with(|| Category {
+                    class: name,
+                    ..Category::default()
+                });
+                class.words += 1;
+                class.errors += usize::from(target != input);
+            }
+        }
+    }
+
+    let classes: Vec<Category> = classes
+        .into_values()
+        .map(|class| Category {
+            error_rate: class.errors as f64 / class.words as f64,
+            ..class
+        })
+        .collect();
+
+    match format {
+        Format::Text => print_text(&students, &classes),
+        Format::Tsv => {
+            output::print_tsv(&students);
+            println!();
+            output::print_tsv(&classes);
+        }
+        Format::Json | Format::Toml => {
+            format.print_document(&Report { students, classes })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// the report as two aligned tables
+fn print_text(students: &[Student], classes: &[Category]) {
+    println!(
+        "{:<20} {:>6} {:>8} {:>9}",
+        "student", "tests", "wpm", "accuracy"
+    );
+
+    for student in students {
+        if student.tests == 0 {
+            println!("{:<20} {:>6} {:>8} {:>9}", student.name, 0, "-", "-");
+        } else {
+            println!(
+                "{:<20} {:>6} {:>8.1} {:>8.1}%",
+                student.name,
+                student.tests,
+                student.wpm,
+                student.accuracy * 100.0
+            );
+        }
+    }
+
+    println!();
+    println!(
+        "{:<20} {:>6} {:>8} {:>9}",
+        "class", "words", "errors", "rate"
+    );
+
+    for class in classes {
+        println!(
+            "{:<20} {:>6} {:>8} {:>8.1}%",
+            class.class,
+            class.words,
+            class.errors,
+            class.error_rate * 100.0
+        );
+    }
+}
+
+/// the file name, or the profile directory's name for a profile's history.toml
+fn student_name(path: &Path) -> String {
+    let stem = path.file_stem().unwrap_or_default();
+
+    let name = if stem == "history" {
+        path.parent().and_then(Path::file_name).unwrap_or(stem)
+    } else {
+        stem
+    };
+
+    name.to_string_lossy().into_owned()
+}
+
+/// the linku usage category of a target word
+fn word_class(word: &str) -> &'static str {
+    WORDS
+        .get(punctuation::bare(word))
+        .and_then(|toml| word_field(toml, "usage_category").ok())
+        .unwrap_or(UNKNOWN_CLASS)
+}
@@ -0,0 +1,352 @@
+use crate::{
+    budget::ErrorBudget,
+    error::{Error, Result},
+    goals::Goals,
+    lesson::PassCriteria,
+    numbers::NumberSystem,
+    words::{word_field, HAS_FREQUENCIES},
+};
+use std::{collections::HashMap, time::Duration};
+
+/// words per second a timed test has room for, faster than anyone types
+const TIMED_WORDS_PER_SEC: usize = 5;
+
+/// what decides how often a word is picked
+#[derive(Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Weighting {
+    /// the weights of the linku usage categories
+    #[default]
+    Category,
+    /// how often the word is used in the corpus, words without counts come last
+    Frequency,
+}
+
+/// how often a single word comes up, set from the dictionary or `[game.pins]`
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pin {
+    /// the weight from `words` and the categories
+    #[default]
+    Normal,
+    Often,
+    Rare,
+    Never,
+}
+
+impl Pin {
+    /// the pin after this one when cycling through them
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Normal => Self::Often,
+            Self::Often => Self::Rare,
+            Self::Rare => Self::Never,
+            Self::Never => Self::Normal,
+        }
+    }
+
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Normal => "",
+            Self::Often => "often",
+            Self::Rare => "rare",
+            Self::Never => "never",
+        }
+    }
+}
+
+// every bool is a setting of its own that the player turns on or off
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct GameSettings<T> {
+    /// weights per linku usage category, config entries are added to the built in ones
+    #[serde(deserialize_with = "with_default_categories")]
+    pub categories: HashMap<String, T>,
+    /// weight for categories missing from `categories`
+    pub unknown_category: T,
+    pub weighting: Weighting,
+    pub deprecated: T,
+    pub nondeprecated: T,
+    pub words: HashMap<String, T>,
+    /// pinned words, these replace the weight in `words`
+    pub pins: HashMap<String, Pin>,
+    pub len: usize,
+    /// words can come more than once in a test, picked by weight each time, timed tests
+    /// always draw them this way
+    pub repeat_words: bool,
+    /// show each test to reroll or accept with enter before it can be typed
+    pub preview: bool,
+    /// seconds a test lasts, 0 for a test that ends with its words
+    pub time_secs: u64,
+    pub tick_rate_ms: u64,
+    /// seconds without a keystroke before the timer pauses itself, 0 disables it
+    pub afk_timeout_secs: u64,
+    /// seconds between saves of the game in progress, 0 disables it
+    pub autosave_secs: u64,
+    /// gaps between typed characters longer than this are flagged on the results screen
+    pub hesitation_ms: u64,
+    /// capitalized names like "jan Mali" mixed into each test, 0 disables them
+    pub proper_names: usize,
+    /// share of the words changed to break autopilot typing, each swapped for an obscure or
+    /// sandbox word, capitalized or given a doubled letter, 0 changes none
+    pub noise: f64,
+    /// group the words into sentences with punctuation that has to be typed
+    pub punctuation: bool,
+    /// letters shown at the start of each word still to type, the rest are hidden so the
+    /// spelling has to be remembered, 0 shows every word whole
+    pub hint_letters: usize,
+    /// seconds the whole test is shown before it is hidden and typed from memory,
+    /// typing early hides it too, 0 never hides it
+    pub memory_secs: u64,
+    /// words typed right disappear, so the text moves up to the caret
+    pub shadow: bool,
+    /// show the test as sitelen pona glyphs to type in latin letters,
+    /// the terminal needs a font with the UCSUR glyphs
+    pub sitelen_pona: bool,
+    /// one word a line with its gloss beside it instead of a wrapped paragraph
+    pub word_per_line: bool,
+    /// a space typed inside a word is a mistake and doesn't move on
+    pub strict_space: bool,
+    /// a space always ends the word like in monkeytype, the letters left in it are mistakes
+    /// and a second space is ignored instead of skipping a word
+    pub space_commits: bool,
+    /// two neighbouring letters typed the wrong way round, like "tmoo" for "tomo",
+    /// are one mistake
+    pub forgiving: bool,
+    /// move on to the next word as soon as a word is typed right, without a space
+    pub auto_space: bool,
+    /// letters that can be typed past the end of a word, the rest are refused until a space
+    /// or backspace so one long mistake doesn't push the text along, 0 has no limit
+    pub max_overflow: usize,
+    pub number_system: NumberSystem,
+    /// results a test needs to pass, tests are only judged when one is set
+    pub pass: PassCriteria,
+    /// mistakes a test can take before it ends as failed
+    pub budget: ErrorBudget,
+    /// daily and weekly practice shown on the menu
+    pub goals: Goals,
+}
+
+impl<T> GameSettings<T> {
+    /// words in a test, a timed one gets enough that nobody runs out
+    pub fn test_len(&self) -> usize {
+        let timed = usize::try_from(self.time_secs)
+            .unwrap_or(usize::MAX)
+            .saturating_mul(TIMED_WORDS_PER_SEC);
+
+        self.len.max(timed)
+    }
+
+    /// how long the main loop waits for input before redrawing anyway
+    pub const fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms)
+    }
+}
+
+impl GameSettings<usize> {
+    pub const DEFAULT: usize = 1000;
+
+    #[must_use]
+    pub fn get_word(&self, word: &str) -> usize {
+        match self.pin(word) {
+            Pin::Often => 1,
+            Pin::Rare => Self::DEFAULT.saturating_mul(1000),
+            Pin::Normal | Pin::Never => *self.words.get(word).unwrap_or(&Self::DEFAULT),
+        }
+    }
+
+    #[must_use]
+    pub fn pin(&self, word: &str) -> Pin {
+        self.pins.get(word).copied().unwrap_or_default()
+    }
+
+    /// false for words pinned as never
+    #[must_use]
+    pub fn allows(&self, word: &str) -> bool {
+        self.pin(word) != Pin::Never
+    }
+
+    /// how rare a word is in tests, a word is picked with a chance of one over its weight
+    /// so half the weight is twice as often, a weight of 0 counts as 1, the most often there is
+    ///
+    /// # Errors
+    /// `Error::MissingField` for a word without a usage category, and `Error::NoFrequencies`
+    /// for frequency weighting without the frequency table
+    pub fn weight(&self, toml: &toml::Table) -> Result<usize> {
+        let category_weight = match self.weighting {
+            Weighting::Category => *self
+                .categories
+                .get(word_field(toml, "usage_category")?)
+                .unwrap_or(&self.unknown_category),
+            Weighting::Frequency if *HAS_FREQUENCIES => Self::frequency_weight(toml),
+            Weighting::Frequency => return Err(Error::NoFrequencies),
+        };
+
+        let deprecated_weight = if toml
+            .get("deprecated")
+            .and_then(toml::Value::as_bool)
+            .ok_or(Error::MissingField {
+                field: "deprecated",
+            })? {
+            self.deprecated
+        } else {
+            self.nondeprecated
+        };
+
+        let word_weight = self.get_word(word_field(toml, "word")?);
+
+        // weights come from the config, so big ones can't wrap around to tiny ones
+        Ok(category_weight
+            .saturating_mul(deprecated_weight)
+            .saturating_mul(word_weight)
+            .max(1))
+    }
+
+    /// the weight of a word used this many times per million words of the corpus
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn frequency_weight(toml: &toml::Table) -> usize {
+        let per_million = toml
+            .get("frequency")
+            .and_then(toml::Value::as_float)
+            .unwrap_or_default();
+
+        (Self::DEFAULT as f64 * 1_000_000.0 / (per_million.max(0.0) + 1.0)) as usize
+    }
+}
+
+fn with_default_categories<'de, D, T>(deserializer: D) -> Result<HashMap<String, T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+    GameSettings<T>: Default,
+{
+    let mut categories = GameSettings::<T>::default().categories;
+    categories.extend(<HashMap<String, T> as serde::Deserialize>::deserialize(
+        deserializer,
+    )?);
+
+    Ok(categories)
+}
+
+impl Default for GameSettings<usize> {
+    fn default() -> Self {
+        Self {
+            categories: HashMap::from([
+                ("core".to_string(), Self::DEFAULT),
+                ("common".to_string(), Self::DEFAULT * 200),
+                ("uncommon".to_string(), Self::DEFAULT * 400),
+                ("obscure".to_string(), Self::DEFAULT * 600),
+                ("sandbox".to_string(), Self::DEFAULT * 800),
+            ]),
+            unknown_category: Self::DEFAULT * 800,
+            weighting: Weighting::default(),
+            deprecated: Self::DEFAULT * 800,
+            nondeprecated: Self::DEFAULT,
+            words: HashMap::new(),
+            pins: HashMap::new(),
+            len: 60,
+            repeat_words: false,
+            preview: false,
+            time_secs: 0,
+            tick_rate_ms: 50,
+            afk_timeout_secs: 10,
+            autosave_secs: 5,
+            hesitation_ms: 1000,
+            proper_names: 0,
+            noise: 0.0,
+            punctuation: false,
+            hint_letters: 0,
+            memory_secs: 0,
+            shadow: false,
+            sitelen_pona: false,
+            word_per_line: false,
+            strict_space: false,
+            space_commits: false,
+            forgiving: false,
+            auto_space: false,
+            max_overflow: 0,
+            number_system: NumberSystem::default(),
+            pass: PassCriteria::default(),
+            budget: ErrorBudget::default(),
+            goals: Goals::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(word: &str, frequency: Option<f64>) -> toml::Table {
+        let mut table: toml::Table = toml::from_str(&format!(
+            "word = {word:?}\nusage_category = \"core\"\ndeprecated = false"
+        ))
+        .unwrap();
+
+        if let Some(frequency) = frequency {
+            table.insert("frequency".to_string(), frequency.into());
+        }
+
+        table
+    }
+
+    #[test]
+    fn pins_replace_the_weight_of_a_word() {
+        let settings = GameSettings {
+            words: [("pona".to_string(), 5), ("ike".to_string(), 5)].into(),
+            pins: [
+                ("mi".to_string(), Pin::Often),
+                ("sina".to_string(), Pin::Rare),
+                ("ona".to_string(), Pin::Never),
+                ("pona".to_string(), Pin::Often),
+            ]
+            .into(),
+            ..GameSettings::default()
+        };
+
+        for (word, weight, allowed) in [
+            ("mi", 1, true),
+            ("sina", GameSettings::DEFAULT * 1000, true),
+            ("ona", GameSettings::DEFAULT, false),
+            ("pona", 1, true),
+            ("ike", 5, true),
+            ("moku", GameSettings::DEFAULT, true),
+        ] {
+            assert_eq!(settings.get_word(word), weight, "{word}");
+            assert_eq!(settings.allows(word), allowed, "{word}");
+        }
+
+        let core = GameSettings::DEFAULT * GameSettings::DEFAULT;
+        assert_eq!(settings.weight(&word("mi", None)).unwrap(), core);
+        assert_eq!(
+            settings.weight(&word("sina", None)).unwrap(),
+            core * GameSettings::DEFAULT * 1000
+        );
+    }
+
+    #[test]
+    fn frequent_words_weigh_less() {
+        let weights: Vec<usize> = [None, Some(0.0), Some(9.0), Some(999_999.0), Some(-5.0)]
+            .into_iter()
+            .map(|frequency| GameSettings::frequency_weight(&word("pona", frequency)))
+            .collect();
+
+        assert_eq!(
+            weights,
+            [
+                GameSettings::DEFAULT * 1_000_000,
+                GameSettings::DEFAULT * 1_000_000,
+                GameSettings::DEFAULT * 100_000,
+                GameSettings::DEFAULT,
+                GameSettings::DEFAULT * 1_000_000,
+            ]
+        );
+    }
+}
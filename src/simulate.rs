@@ -0,0 +1,139 @@
+use crate::output::{self, Format, Record};
+use ratatui::crossterm::event::{Event, KeyEvent};
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
+use tt::{
+    error::{Error, Result},
+    graphemes,
+    keymap::{Context, Key, Keymap},
+    Game, ManualClock,
+};
+
+/// a target with the keys typed for it, read from a json file
+#[derive(serde::Deserialize)]
+pub struct Recording {
+    pub target: String,
+    pub keys: Vec<RecordedKey>,
+}
+
+#[derive(serde::Deserialize)]
+pub struct RecordedKey {
+    /// written like in the config, "m", "backspace" or "ctrl+w"
+    pub key: Key,
+    /// milliseconds since the first key
+    pub ms: u64,
+}
+
+/// the results of a simulation as they are printed
+#[derive(serde::Serialize)]
+struct Simulation {
+    keys: usize,
+    finished: bool,
+    wpm: f64,
+    /// a fraction like in the history
+    accuracy: f64,
+    seconds: f64,
+    best_combo: usize,
+    kspc: f64,
+    target_graphemes: usize,
+    input_graphemes: usize,
+    processing_us: u64,
+}
+
+impl Record for Simulation {
+    const FIELDS: &'static [&'static str] = &[
+        "keys",
+        "finished",
+        "wpm",
+        "accuracy",
+        "seconds",
+        "best_combo",
+        "kspc",
+        "target_graphemes",
+        "input_graphemes",
+        "processing_us",
+    ];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.keys.to_string(),
+            self.finished.to_string(),
+            self.wpm.to_string(),
+            self.accuracy.to_string(),
+            self.seconds.to_string(),
+            self.best_combo.to_string(),
+            self.kspc.to_string(),
+            self.target_graphemes.to_string(),
+            self.input_graphemes.to_string(),
+            self.processing_us.to_string(),
+        ]
+    }
+}
+
+/// feed a recording through the game with the default keys and print the results
+pub fn run(path: &Path, format: Format) -> Result<()> {
+    let json = std::fs::read_to_string(path).map_err(|source| Error::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let recording: Recording = serde_json::from_str(&json).map_err(|source| Error::Json {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let keymap = Keymap::default();
+    let mut game: Game<_, ManualClock> = Game::from_target(recording.target);
+
+    let timer = Instant::now();
+
+    for recorded in &recording.keys {
+        let event = KeyEvent::new(recorded.key.code, recorded.key.modifiers);
+
+        game.clock.set(Duration::from_millis(recorded.ms));
+        game.crossterm_event(&Event::Key(event), keymap.action(Context::Game, &event));
+    }
+
+    let processing = timer.elapsed();
+
+    // stop the timer at the last key
+    game.pause();
+
+    let simulation = Simulation {
+        keys: recording.keys.len(),
+        finished: game.is_finished(),
+        wpm: game.wpm(),
+        accuracy: game.accuracy(),
+        seconds: game.elapsed().as_secs_f64(),
+        best_combo: game.best_combo,
+        kspc: game.kspc(),
+        target_graphemes: graphemes(&game.target),
+        input_graphemes: graphemes(&game.input),
+        processing_us: u64::try_from(processing.as_micros()).unwrap_or(u64::MAX),
+    };
+
+    match format {
+        Format::Text => print_text(&simulation),
+        Format::Tsv => output::print_tsv(&[simulation]),
+        Format::Json | Format::Toml => format.print_document(&simulation)?,
+    }
+
+    Ok(())
+}
+
+fn print_text(simulation: &Simulation) {
+    println!("keys {}", simulation.keys);
+    println!("finished {}", simulation.finished);
+    println!("wpm {:.2}", simulation.wpm);
+    println!("accuracy {:.2}%", simulation.accuracy * 100.0);
+    println!("time {:.3}s", simulation.seconds);
+    println!("best combo {}", simulation.best_combo);
+    println!("keystrokes per character {:.3}", simulation.kspc);
+    println!(
+        "target {} graphemes, input {} graphemes",
+        simulation.target_graphemes, simulation.input_graphemes
+    );
+    println!("processed in {}us", simulation.processing_us);
+}
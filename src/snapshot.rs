@@ -0,0 +1,78 @@
+use crate::{
+    error::{Error, Result},
+    history::Stroke,
+    lesson::Lesson,
+    migrate, storage, GameSettings,
+};
+use ratatui::crossterm::event::KeyCode;
+use std::path::Path;
+
+/// an in progress game, saved regularly so it can be resumed after a crash
+#[derive(serde::Deserialize, serde::Serialize)]
+pub struct Snapshot {
+    #[serde(default)]
+    pub version: i64,
+    /// the mode as it is kept in the history, so a resumed game ends like the one saved
+    pub mode: String,
+    /// words and seconds of a custom test
+    #[serde(default)]
+    pub custom_len: usize,
+    #[serde(default)]
+    pub custom_secs: u64,
+    /// the name pack of a names test
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub pack: String,
+    /// the lesson of a lesson test, which may have come from a file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lesson: Option<Lesson>,
+    pub target: String,
+    pub input: String,
+    /// keys with the milliseconds since the first key
+    pub key_log: Vec<(KeyCode, u64)>,
+    #[serde(default)]
+    pub strokes: Vec<Stroke>,
+    pub paused_ms: u64,
+    pub combo: usize,
+    pub best_combo: usize,
+    /// prompts of a drill, missing from snapshots of plain tests
+    #[serde(default)]
+    pub prompts: Vec<String>,
+    #[serde(default)]
+    pub rejected: usize,
+    pub settings: GameSettings<usize>,
+}
+
+impl Snapshot {
+    /// the saved game, `None` when there is none
+    ///
+    /// # Errors
+    /// the errors of `storage::load`, `Error::Parse` when it isn't a snapshot
+    /// and `Error::NewerVersion` when it is from a newer version
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        let Some(mut table) = storage::load::<toml::Table>(path)? else {
+            return Ok(None);
+        };
+
+        migrate::snapshot(path, &mut table)?;
+
+        toml::Value::Table(table)
+            .try_into()
+            .map(Some)
+            .map_err(|source| Error::Parse {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// # Errors
+    /// the errors of `storage::save`
+    pub fn save(&self, path: &Path) -> Result<()> {
+        storage::save(path, self)
+    }
+
+    /// # Errors
+    /// the errors of `storage::remove`
+    pub fn remove(path: &Path) -> Result<()> {
+        storage::remove(path)
+    }
+}
@@ -0,0 +1,175 @@
+use crate::hooks;
+use ratatui::crossterm::event::KeyCode;
+use std::path::{Path, PathBuf};
+use tt::{clock::Clock, error::Result, Game};
+
+/// file types looked for, in order, a player that can't play one is the player's problem
+const EXTENSIONS: [&str; 4] = ["wav", "ogg", "flac", "mp3"];
+
+/// key sounds, the `[sound]` table of the config
+#[derive(Clone, serde::Deserialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    /// a folder of the sounds directory next to config.toml, like "typewriter", "mechanical"
+    /// or "soft", with a correct, error and word file in it, empty for no sounds
+    pub theme: String,
+    /// run by the shell with the path of the file to play as `$1`
+    pub player: String,
+}
+
+impl Default for SoundConfig {
+    fn default() -> Self {
+        Self {
+            theme: String::new(),
+            player: DEFAULT_PLAYER.to_string(),
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+const DEFAULT_PLAYER: &str = r#"afplay "$1""#;
+#[cfg(not(target_os = "macos"))]
+const DEFAULT_PLAYER: &str = r#"paplay "$1" 2>/dev/null || aplay -q "$1""#;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sound {
+    Correct,
+    Error,
+    /// a word was finished, over the sound of the key that finished it
+    Word,
+}
+
+impl Sound {
+    const ALL: [Self; 3] = [Self::Correct, Self::Error, Self::Word];
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::Error => "error",
+            Self::Word => "word",
+        }
+    }
+
+    /// the sound for the key the game just handled, given the words done and strokes before it,
+    /// keys that typed nothing like backspace have none
+    pub fn for_key<C: Clock + Default>(
+        game: &Game<KeyCode, C>,
+        words: usize,
+        strokes: usize,
+    ) -> Option<Self> {
+        let (_, correct, _, _) = game.strokes.get(strokes)?;
+
+        Some(if !correct {
+            Self::Error
+        } else if game.is_finished() || game.input.matches(' ').count() > words {
+            Self::Word
+        } else {
+            Self::Correct
+        })
+    }
+}
+
+/// the files of a theme and how to play them
+pub struct Soundpack {
+    files: [Option<PathBuf>; 3],
+    player: String,
+}
+
+impl Soundpack {
+    /// the theme's files in the sounds directory, None when no theme is set,
+    /// a sound without a file is left quiet
+    pub fn load(dir: &Path, config: &SoundConfig) -> Option<Self> {
+        if config.theme.trim().is_empty() {
+            return None;
+        }
+
+        let theme = dir.join(config.theme.trim());
+        let files = Sound::ALL.map(|sound| {
+            EXTENSIONS
+                .iter()
+                .map(|extension| theme.join(sound.name()).with_extension(extension))
+                .find(|path| path.is_file())
+        });
+
+        Some(Self {
+            files,
+            player: config.player.clone(),
+        })
+    }
+
+    /// start playing a sound without waiting for it to end
+    pub fn play(&self, sound: Sound) -> Result<()> {
+        let Some(file) = &self.files[sound as usize] else {
+            return Ok(());
+        };
+
+        hooks::run(&self.player, None, |command| {
+            command.arg(file);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::crossterm::event::{Event, KeyEvent};
+    use tt::clock::ManualClock;
+
+    /// the sound of each key typing the text
+    fn sounds(target: &str, text: &str) -> Vec<Option<Sound>> {
+        let mut game: Game<KeyCode, ManualClock> = Game::from_target(target.to_string());
+
+        text.chars()
+            .map(|c| {
+                let words = game.input.matches(' ').count();
+                let strokes = game.strokes.len();
+                game.crossterm_event(&Event::Key(KeyEvent::from(KeyCode::Char(c))), None);
+                Sound::for_key(&game, words, strokes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn each_key_sounds_how_it_went() {
+        use Sound::{Correct, Error, Word};
+
+        assert_eq!(
+            sounds("mi moku", "mx moku"),
+            [
+                Some(Correct),
+                Some(Error),
+                Some(Word),
+                Some(Correct),
+                Some(Correct),
+                Some(Correct),
+                Some(Word)
+            ]
+        );
+    }
+
+    #[test]
+    fn a_theme_finds_its_files_by_name() {
+        let dir = std::env::temp_dir().join(format!("tt-sounds-{}", std::process::id()));
+        let theme = dir.join("soft");
+        std::fs::create_dir_all(&theme).unwrap();
+        std::fs::write(theme.join("correct.ogg"), []).unwrap();
+        std::fs::write(theme.join("word.wav"), []).unwrap();
+
+        let config = SoundConfig {
+            theme: "soft".to_string(),
+            ..SoundConfig::default()
+        };
+        let pack = Soundpack::load(&dir, &config).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            pack.files,
+            [
+                Some(theme.join("correct.ogg")),
+                None,
+                Some(theme.join("word.wav"))
+            ]
+        );
+        assert!(Soundpack::load(&dir, &SoundConfig::default()).is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use crate::error::{Error, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// old versions kept of files saved with backups
+pub const BACKUPS: usize = 3;
+
+/// read a toml file, a missing file gives `None`
+///
+/// # Errors
+/// `Error::Read` when the file can't be read and `Error::Parse` when it isn't valid
+pub fn load<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    match std::fs::read_to_string(path) {
+        Ok(data) => toml::from_str(&data)
+            .map(Some)
+            .map_err(|source| Error::Parse {
+                path: path.to_path_buf(),
+                source,
+            }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(Error::Read {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+/// write a toml file, creating its directory if needed
+///
+/// it is written next to the old one and renamed over it, so a crash part way through
+/// leaves the old file whole
+///
+/// # Errors
+/// `Error::Serialize` when the value can't be written as toml and `Error::Write` when
+/// the file can't be
+pub fn save<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    let data = toml::to_string(value).map_err(|source| Error::Serialize {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|source| Error::Write {
+            path: dir.to_path_buf(),
+            source,
+        })?;
+    }
+
+    let temp = sibling(path, "tmp");
+    let write_error = |source| Error::Write {
+        path: temp.clone(),
+        source,
+    };
+
+    let mut file = std::fs::File::create(&temp).map_err(write_error)?;
+    file.write_all(data.as_bytes()).map_err(write_error)?;
+    // on disk before the rename makes it the file
+    file.sync_all().map_err(write_error)?;
+    drop(file);
+
+    std::fs::rename(&temp, path).map_err(|source| Error::Write {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// save a file and keep the last `BACKUPS` versions of it, history.toml.1 the newest
+///
+/// # Errors
+/// the errors of `save`, and `Error::Write` when a backup can't be moved or copied
+pub fn save_with_backups<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if path.exists() {
+        for number in (1..BACKUPS).rev() {
+            rename_existing(&backup(path, number), &backup(path, number + 1))?;
+        }
+
+        // copied rather than moved so there is always a whole file at the path
+        std::fs::copy(path, backup(path, 1)).map_err(|source| Error::Write {
+            path: backup(path, 1),
+            source,
+        })?;
+    }
+
+    save(path, value)
+}
+
+/// the path of a backup, 1 is the newest
+#[must_use]
+pub fn backup(path: &Path, number: usize) -> PathBuf {
+    sibling(path, &number.to_string())
+}
+
+/// the path with another extension after its own, like history.toml.tmp
+fn sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+/// move a file, a missing file is not an error
+fn rename_existing(from: &Path, to: &Path) -> Result<()> {
+    match std::fs::rename(from, to) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(Error::Write {
+            path: to.to_path_buf(),
+            source: err,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// delete a file, a missing file is not an error
+///
+/// # Errors
+/// `Error::Write` when the file is there and can't be deleted
+pub fn remove(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(Error::Write {
+            path: path.to_path_buf(),
+            source: err,
+        }),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Deserialize, serde::Serialize)]
+    struct File {
+        saves: usize,
+    }
+
+    fn dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("tt-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn saves(path: &Path) -> Option<usize> {
+        load::<File>(path).unwrap().map(|file| file.saves)
+    }
+
+    #[test]
+    fn backups_keep_only_the_last_few_versions() {
+        let dir = dir("backups");
+        let path = dir.join("history.toml");
+
+        for saves in 1..=BACKUPS + 3 {
+            save_with_backups(&path, &File { saves }).unwrap();
+        }
+
+        assert_eq!(saves(&path), Some(BACKUPS + 3));
+        for number in 1..=BACKUPS {
+            assert_eq!(saves(&backup(&path, number)), Some(BACKUPS + 3 - number));
+        }
+        assert_eq!(saves(&backup(&path, BACKUPS + 1)), None);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), BACKUPS + 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn a_failed_save_leaves_the_old_file_whole() {
+        let dir = dir("atomic");
+        let path = dir.join("history.toml");
+
+        save(&path, &File { saves: 1 }).unwrap();
+        // a bare number isn't a toml document, so it fails before anything is written
+        assert!(matches!(save(&path, &2), Err(Error::Serialize { .. })));
+        assert_eq!(saves(&path), Some(1));
+
+        // the temporary file is renamed over the old one, not left next to it
+        save(&path, &File { saves: 2 }).unwrap();
+        assert_eq!(saves(&path), Some(2));
+        assert!(!sibling(&path, "tmp").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{date::SECS_PER_DAY, history::Session};
+use std::collections::HashSet;
+
+/// the file name, next to history.toml
+pub const FILE: &str = "summary.toml";
+
+/// a few numbers about a history, saved next to it as summary.toml so a status line
+/// can show them without reading the whole history
+#[derive(Clone, Copy, Default, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Summary {
+    /// the day the numbers were counted on, in days since 1970 in UTC like the goals
+    pub day: u64,
+    /// days in a row with a finished test, up to `day` or the day before it
+    pub streak: usize,
+    /// finished tests on `day`
+    pub tests_today: usize,
+    /// the fastest finished test on `day`
+    pub best_wpm_today: f64,
+}
+
+impl Summary {
+    pub fn new(sessions: &[Session], now: u64) -> Self {
+        let day = now / SECS_PER_DAY;
+        let finished = || sessions.iter().filter(|session| session.finished);
+        let days: HashSet<u64> = finished()
+            .map(|session| session.date / SECS_PER_DAY)
+            .collect();
+
+        // a streak isn't over until a whole day goes by without a test
+        let last = if days.contains(&day) {
+            day
+        } else {
+            day.saturating_sub(1)
+        };
+        let streak = (0..=last)
+            .rev()
+            .take_while(|day| days.contains(day))
+            .count();
+
+        let today: Vec<&Session> = finished()
+            .filter(|session| session.date / SECS_PER_DAY == day)
+            .collect();
+
+        Self {
+            day,
+            streak,
+            tests_today: today.len(),
+            best_wpm_today: today.iter().map(|session| session.wpm).fold(0.0, f64::max),
+        }
+    }
+
+    /// the summary as it stands on a later day, when nothing has been typed since it was saved
+    #[must_use]
+    pub const fn on(self, now: u64) -> Self {
+        let day = now / SECS_PER_DAY;
+
+        if day == self.day {
+            return self;
+        }
+
+        Self {
+            day,
+            streak: if day == self.day + 1 { self.streak } else { 0 },
+            tests_today: 0,
+            best_wpm_today: 0.0,
+        }
+    }
+
+    /// a line each for reading in the terminal
+    #[must_use]
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!("streak: {} days", self.streak),
+            format!("tests today: {}", self.tests_today),
+            format!("best today: {:.0} wpm", self.best_wpm_today),
+        ]
+    }
+
+    /// one short line for a tmux status bar or a shell prompt
+    #[must_use]
+    pub fn compact(&self) -> String {
+        format!(
+            "{}d streak | {:.0} wpm today",
+            self.streak, self.best_wpm_today
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a finished session at noon on the day
+    fn session(day: u64, wpm: f64) -> Session {
+        Session {
+            id: String::new(),
+            date: day * SECS_PER_DAY + SECS_PER_DAY / 2,
+            words: 0,
+            target: String::new(),
+            input: String::new(),
+            wpm,
+            accuracy: 1.0,
+            seconds: 0.0,
+            best_combo: 0,
+            finished: true,
+            strokes: Vec::new(),
+            keystrokes: 0,
+            passed: None,
+            mode: String::new(),
+            tags: Vec::new(),
+            note: String::new(),
+            goals_met: Vec::new(),
+        }
+    }
+
+    const TODAY: u64 = 100;
+    const NOW: u64 = TODAY * SECS_PER_DAY + 60;
+
+    #[test]
+    fn a_streak_counts_today() {
+        let sessions = [session(98, 30.0), session(99, 30.0), session(100, 40.0)];
+        let summary = Summary::new(&sessions, NOW);
+
+        assert_eq!(summary.day, TODAY);
+        assert_eq!(summary.streak, 3);
+    }
+
+    #[test]
+    fn a_streak_lasts_until_a_day_is_missed() {
+        let sessions = [session(98, 30.0), session(99, 30.0)];
+        let summary = Summary::new(&sessions, NOW);
+
+        assert_eq!(summary.streak, 2);
+        assert_eq!(summary.tests_today, 0);
+    }
+
+    #[test]
+    fn a_missed_day_breaks_the_streak() {
+        let sessions = [session(96, 30.0), session(97, 30.0), session(99, 30.0)];
+
+        assert_eq!(Summary::new(&sessions, NOW).streak, 1);
+
+        let sessions = [session(97, 30.0)];
+
+        assert_eq!(Summary::new(&sessions, NOW).streak, 0);
+    }
+
+    #[test]
+    fn unfinished_sessions_are_left_out() {
+        let mut unfinished = session(100, 90.0);
+        unfinished.finished = false;
+        let sessions = [
+            session(99, 30.0),
+            unfinished,
+            session(100, 45.0),
+            session(100, 40.0),
+        ];
+        let summary = Summary::new(&sessions, NOW);
+
+        assert_eq!(summary.streak, 2);
+        assert_eq!(summary.tests_today, 2);
+        assert!((summary.best_wpm_today - 45.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn on_a_later_day_keeps_the_streak_for_a_day() {
+        let summary = Summary::new(&[session(99, 30.0), session(100, 40.0)], NOW);
+
+        let tomorrow = summary.on(NOW + SECS_PER_DAY);
+        assert_eq!(tomorrow.streak, 2);
+        assert_eq!(tomorrow.tests_today, 0);
+
+        assert_eq!(summary.on(NOW + 2 * SECS_PER_DAY).streak, 0);
+    }
+}
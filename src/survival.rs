@@ -0,0 +1,42 @@
+use crate::{clock::Clock, game::Game};
+use ratatui::crossterm::event::KeyCode;
+use std::time::Duration;
+
+/// typing before the pace is first checked
+pub const GRACE: Duration = Duration::from_secs(10);
+
+/// the pace is measured over this much of the latest typing
+const WINDOW: Duration = Duration::from_secs(10);
+
+/// the pace needed when the grace ends
+pub const START_WPM: f64 = 10.0;
+
+/// how much the needed pace goes up each minute
+pub const RISE_PER_MINUTE: f64 = 5.0;
+
+/// words in the target, more than fit in any run
+pub const WORDS: usize = 1000;
+
+/// the wpm needed to keep going at this point of the run
+pub fn required<C: Clock + Default>(game: &Game<KeyCode, C>) -> f64 {
+    RISE_PER_MINUTE.mul_add(game.elapsed().as_secs_f64() / 60.0, START_WPM)
+}
+
+/// wpm of the right characters typed in the last few seconds, so stopping drops it
+#[allow(clippy::cast_precision_loss)]
+pub fn pace<C: Clock + Default>(game: &Game<KeyCode, C>) -> f64 {
+    let now = game.clock.now();
+
+    let correct = game
+        .strokes
+        .iter()
+        .filter(|(_, correct, time, _)| *correct && now.saturating_duration_since(*time) < WINDOW)
+        .count();
+
+    correct as f64 / 5.0 / (WINDOW.as_secs_f64() / 60.0)
+}
+
+/// the pace fell under what is needed, never while paused or in the grace
+pub fn is_over<C: Clock + Default>(game: &Game<KeyCode, C>) -> bool {
+    game.paused_at.is_none() && game.elapsed() >= GRACE && pace(game) < required(game)
+}
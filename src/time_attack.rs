@@ -0,0 +1,35 @@
+use crate::{clock::Clock, game::Game};
+use ratatui::crossterm::event::KeyCode;
+use std::time::Duration;
+
+/// on the clock when the first key is pressed
+pub const START: Duration = Duration::from_secs(10);
+
+/// added for every word typed without a mistake
+pub const BONUS: Duration = Duration::from_secs(2);
+
+/// words in the target, more than fit in any run
+pub const WORDS: usize = 400;
+
+/// words finished without a mistake, the score of a run
+pub fn score<C: Clock + Default>(game: &Game<KeyCode, C>) -> usize {
+    let finished = game.input.matches(' ').count();
+
+    game.word_results()
+        .into_iter()
+        .take(finished)
+        .filter(|(_, correct)| *correct)
+        .count()
+}
+
+/// time left before the run ends
+pub fn remaining<C: Clock + Default>(game: &Game<KeyCode, C>) -> Duration {
+    let bonus = BONUS * u32::try_from(score(game)).unwrap_or(u32::MAX);
+
+    (START + bonus).saturating_sub(game.elapsed())
+}
+
+/// the clock ran out, a run that hasn't started can't be over
+pub fn is_over<C: Clock + Default>(game: &Game<KeyCode, C>) -> bool {
+    !game.key_log.is_empty() && remaining(game).is_zero()
+}
@@ -0,0 +1,123 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    symbols::Marker,
+    text::Span,
+    widgets::{Axis, Block, BorderType::Rounded, Chart, Dataset, GraphType},
+    Frame,
+};
+use tt::{
+    history::Session,
+    locale::{Locale, Message},
+};
+
+/// tests in each point of the moving average
+const WINDOW: usize = 10;
+
+/// the mean of each value with up to the WINDOW - 1 values before it
+#[allow(clippy::cast_precision_loss)]
+fn moving_average(values: &[f64]) -> Vec<f64> {
+    (0..values.len())
+        .map(|i| {
+            let window = &values[(i + 1).saturating_sub(WINDOW)..=i];
+            window.iter().sum::<f64>() / window.len() as f64
+        })
+        .collect()
+}
+
+/// slope and intercept of the least squares line through the values by index,
+/// None with fewer than two values
+#[allow(clippy::cast_precision_loss)]
+fn linear_fit(values: &[f64]) -> Option<(f64, f64)> {
+    if values.len() < 2 {
+        return None;
+    }
+
+    let n = values.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = values.iter().sum::<f64>() / n;
+
+    let (covariance, variance) =
+        values
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(covariance, variance), (x, y)| {
+                let dx = x as f64 - mean_x;
+                (covariance + dx * (y - mean_y), variance + dx * dx)
+            });
+
+    let slope = covariance / variance;
+
+    Some((slope, slope.mul_add(-mean_x, mean_y)))
+}
+
+/// wpm of each test oldest first, with the moving average and trend line over it
+#[allow(clippy::cast_precision_loss)]
+pub fn render(frame: &mut Frame, area: Rect, sessions: &[&Session], hint: &str, locale: Locale) {
+    let wpm: Vec<f64> = sessions.iter().rev().map(|session| session.wpm).collect();
+    let points: Vec<(f64, f64)> = wpm
+        .iter()
+        .enumerate()
+        .map(|(x, y)| (x as f64, *y))
+        .collect();
+
+    let average: Vec<(f64, f64)> = moving_average(&wpm)
+        .into_iter()
+        .enumerate()
+        .map(|(x, y)| (x as f64, y))
+        .collect();
+
+    let fit = linear_fit(&wpm);
+    let last = wpm.len().saturating_sub(1) as f64;
+    let trend: Vec<(f64, f64)> = fit
+        .map(|(slope, intercept)| vec![(0.0, intercept), (last, slope.mul_add(last, intercept))])
+        .unwrap_or_default();
+
+    let top = (wpm.iter().copied().fold(0.0, f64::max) / 10.0).ceil() * 10.0;
+
+    let title = fit.map_or_else(
+        || locale.title(Message::WpmOverTests),
+        |(slope, _)| {
+            format!(
+                " {} ",
+                locale.fill(Message::TrendPerTest, &[&format!("{slope:+.2}")])
+            )
+        },
+    );
+
+    frame.render_widget(
+        Chart::new(vec![
+            Dataset::default()
+                .name(locale.get(Message::Wpm))
+                .marker(Marker::Dot)
+                .graph_type(GraphType::Scatter)
+                .style(Style::new().fg(Color::DarkGray))
+                .data(&points),
+            Dataset::default()
+                .name(locale.fill(Message::LastTests, &[&WINDOW]))
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(Color::Cyan))
+                .data(&average),
+            Dataset::default()
+                .name(locale.get(Message::Trend))
+                .marker(Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(Style::new().fg(Color::Yellow))
+                .data(&trend),
+        ])
+        .x_axis(Axis::default().bounds([0.0, last.max(1.0)]))
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, top.max(10.0)])
+                .labels([Span::raw("0"), Span::raw(format!("{top:.0}"))]),
+        )
+        .block(
+            Block::bordered()
+                .border_type(Rounded)
+                .title(title)
+                .title_bottom(hint.to_string()),
+        ),
+        area,
+    );
+}
@@ -0,0 +1,81 @@
+use tt::{
+    keymap::{Action, Context, Keymap},
+    locale::{Locale, Message},
+};
+
+/// the guided first test shown on first launch
+pub struct Tutorial {
+    /// the next popup to show
+    pub step: usize,
+    /// the popup for `step` is on screen
+    pub open: bool,
+}
+
+impl Tutorial {
+    /// length of the tutorial test
+    pub const WORDS: usize = 10;
+
+    const LAST_STEP: usize = 3;
+
+    pub const fn new() -> Self {
+        Self {
+            step: 0,
+            open: false,
+        }
+    }
+
+    /// whether the popup for the current step should open now
+    pub const fn due(&self, words_typed: usize, finished: bool) -> bool {
+        !self.open
+            && match self.step {
+                0 => true,
+                1 => words_typed >= 1,
+                2 => words_typed >= 3,
+                _ => finished,
+            }
+    }
+
+    pub const fn is_last(&self) -> bool {
+        self.step >= Self::LAST_STEP
+    }
+
+    /// title and text of the current step
+    pub fn popup(&self, keymap: &Keymap, locale: Locale) -> (String, String) {
+        let key = |context, action| {
+            keymap.key(context, action).map_or_else(
+                || locale.get(Message::Unbound).to_string(),
+                |key| key.to_string(),
+            )
+        };
+
+        match self.step {
+            0 => (
+                locale.title(Message::Welcome),
+                locale.fill(
+                    Message::WelcomeText,
+                    &[&key(Context::Game, Action::TogglePanels)],
+                ),
+            ),
+            1 => (
+                locale.title(Message::Colors),
+                locale.get(Message::ColorsText).to_string(),
+            ),
+            2 => (
+                locale.title(Message::Keys),
+                locale.fill(
+                    Message::KeysText,
+                    &[
+                        &key(Context::Game, Action::Pause),
+                        &key(Context::Game, Action::SkipWord),
+                        &key(Context::Game, Action::DeleteWord),
+                        &key(Context::Game, Action::Help),
+                    ],
+                ),
+            ),
+            _ => (
+                locale.title(Message::Done),
+                locale.get(Message::DoneText).to_string(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use crate::output::{self, Format, Record};
+use tt::{error::Result, words::word_field, WORDS};
+
+/// which words `tt words` prints, every filter left out matches everything
+#[derive(Default)]
+pub struct WordFilter {
+    /// the linku usage category, like core or common
+    pub category: Option<String>,
+    pub deprecated: Option<bool>,
+    /// text in the word or its definition, any case
+    pub search: Option<String>,
+}
+
+impl WordFilter {
+    fn matches(&self, word: &str, toml: &toml::Table) -> bool {
+        let category = word_field(toml, "usage_category").unwrap_or_default();
+        let deprecated = toml
+            .get("deprecated")
+            .and_then(toml::Value::as_bool)
+            .unwrap_or_default();
+
+        self.category
+            .as_deref()
+            .is_none_or(|wanted| wanted == category)
+            && self.deprecated.is_none_or(|wanted| wanted == deprecated)
+            && self.search.as_deref().is_none_or(|search| {
+                let search = search.to_lowercase();
+                word.to_lowercase().contains(&search)
+                    || definition(toml).to_lowercase().contains(&search)
+            })
+    }
+}
+
+/// a word as it is printed
+#[derive(serde::Serialize)]
+struct WordRecord {
+    word: String,
+    definition: String,
+    category: String,
+    deprecated: bool,
+}
+
+impl Record for WordRecord {
+    const FIELDS: &'static [&'static str] = &["word", "definition", "category", "deprecated"];
+
+    fn values(&self) -> Vec<String> {
+        vec![
+            self.word.clone(),
+            self.definition.clone(),
+            self.category.clone(),
+            self.deprecated.to_string(),
+        ]
+    }
+}
+
+#[derive(serde::Serialize)]
+struct Words {
+    words: Vec<WordRecord>,
+}
+
+/// print the matching words sorted, as text one per line with word, definition and category
+/// split by tabs
+pub fn run(filter: &WordFilter, format: Format) -> Result<()> {
+    let mut words: Vec<WordRecord> = WORDS
+        .iter()
+        .filter(|(word, toml)| filter.matches(word, toml))
+        .map(|(word, toml)| WordRecord {
+            word: word.clone(),
+            definition: definition(toml),
+            category: word_field(toml, "usage_category")
+                .unwrap_or_default()
+                .to_string(),
+            deprecated: toml
+                .get("deprecated")
+                .and_then(toml::Value::as_bool)
+                .unwrap_or_default(),
+        })
+        .collect();
+    words.sort_unstable_by(|a, b| a.word.cmp(&b.word));
+
+    match format {
+        Format::Text => {
+            for word in &words {
+                println!("{}\t{}\t{}", word.word, word.definition, word.category);
+            }
+        }
+        Format::Tsv => output::print_tsv(&words),
+        Format::Json | Format::Toml => format.print_document(&Words { words })?,
+    }
+
+    Ok(())
+}
+
+/// the definition on one line
+fn definition(toml: &toml::Table) -> String {
+    toml.get("definition")
+        .and_then(toml::Value::as_str)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,132 @@
+use crate::events::GameEvent;
+use tt::error::{Error, Result};
+
+#[cfg(feature = "websocket")]
+use std::{
+    net::{Ipv4Addr, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+#[cfg(feature = "websocket")]
+use tungstenite::{
+    handshake::server::{ErrorResponse, Request, Response},
+    http::StatusCode,
+    Message, WebSocket,
+};
+
+/// a slow client is dropped rather than holding up the game
+#[cfg(feature = "websocket")]
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// a connection that hasn't finished its handshake by then is closed
+#[cfg(feature = "websocket")]
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// game events sent as json to every websocket client on a local port, for browser overlays
+#[cfg(feature = "websocket")]
+pub struct EventServer {
+    clients: Arc<Mutex<Vec<WebSocket<TcpStream>>>>,
+}
+
+#[cfg(feature = "websocket")]
+impl EventServer {
+    /// listen on localhost only, clients are taken on a thread of their own
+    pub fn bind(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))
+            .map_err(|error| Error::Usage(format!("can't serve events on port {port}: {error}")))?;
+        let clients = Arc::new(Mutex::new(Vec::new()));
+        let accepted = Arc::clone(&clients);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let accepted = Arc::clone(&accepted);
+
+                // a handshake of its own so one that never finishes doesn't stop the others
+                std::thread::spawn(move || {
+                    if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err()
+                        || stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT)).is_err()
+                    {
+                        return;
+                    }
+
+                    // a connection that isn't a websocket or is from a website is just closed
+                    if let Ok(client) = tungstenite::accept_hdr(stream, check_origin) {
+                        if let Ok(mut clients) = accepted.lock() {
+                            clients.push(client);
+                        }
+                    }
+                });
+            }
+        });
+
+        Ok(Self { clients })
+    }
+
+    /// send an event to every client, the ones that fail are dropped
+    pub fn broadcast(&self, event: &GameEvent) {
+        let Ok(json) = serde_json::to_string(event) else {
+            return;
+        };
+
+        if let Ok(mut clients) = self.clients.lock() {
+            clients.retain_mut(|client| client.send(Message::text(json.clone())).is_ok());
+        }
+    }
+}
+
+/// refuse a browser page that isn't served from this machine, any website could
+/// otherwise connect to localhost and read the game
+#[cfg(feature = "websocket")]
+// the callback type is tungstenite's
+#[allow(clippy::result_large_err)]
+fn check_origin(
+    request: &Request,
+    response: Response,
+) -> std::result::Result<Response, ErrorResponse> {
+    let origin = request
+        .headers()
+        .get("origin")
+        .map(|origin| origin.to_str().unwrap_or_default());
+
+    if origin.is_none_or(local_origin) {
+        Ok(response)
+    } else {
+        let mut response = ErrorResponse::new(None);
+        *response.status_mut() = StatusCode::FORBIDDEN;
+        Err(response)
+    }
+}
+
+/// an origin like `http://localhost:8080`, programs that aren't browsers send none at all
+#[cfg(feature = "websocket")]
+fn local_origin(origin: &str) -> bool {
+    let Some(host) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.bytes().all(|byte| byte.is_ascii_digit()) => host,
+        _ => host,
+    };
+
+    matches!(host, "localhost" | "127.0.0.1" | "[::1]")
+}
+
+/// built without the websocket feature, `--serve-events` is refused
+#[cfg(not(feature = "websocket"))]
+pub struct EventServer;
+
+#[cfg(not(feature = "websocket"))]
+impl EventServer {
+    pub fn bind(_port: u16) -> Result<Self> {
+        Err(Error::Usage(
+            "--serve-events needs tt built with the websocket feature".to_string(),
+        ))
+    }
+
+    #[allow(clippy::unused_self)]
+    pub const fn broadcast(&self, _event: &GameEvent) {}
+}
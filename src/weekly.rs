@@ -0,0 +1,217 @@
+use crate::output::Format;
+use std::{
+    collections::{BTreeMap, HashSet},
+    path::Path,
+};
+use tt::{
+    bigrams,
+    date::{self, SECS_PER_DAY},
+    error::Result,
+    history::{History, Session},
+    punctuation,
+};
+
+/// times a word has to come up in the week, always right, to count as mastered
+const MASTERED_COUNT: usize = 3;
+
+/// words and letter pairs suggested to work on
+const FOCUS_COUNT: usize = 5;
+
+/// the week up to now next to the week before it
+#[derive(serde::Serialize)]
+struct WeekReport {
+    /// unix time in seconds when the week starts
+    from: u64,
+    to: u64,
+    tests: usize,
+    minutes: f64,
+    wpm: f64,
+    /// against the week before, None when nothing was typed then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wpm_change: Option<f64>,
+    /// a fraction like in the history
+    accuracy: f64,
+    /// typed this week for the first time
+    new_words: Vec<String>,
+    /// typed right every time this week, at least a few times
+    mastered: Vec<String>,
+    /// the words mistyped most this week
+    focus_words: Vec<String>,
+    /// the slowest and most mistyped letter pairs this week
+    focus_bigrams: Vec<String>,
+}
+
+impl WeekReport {
+    #[allow(clippy::cast_precision_loss)]
+    fn new(sessions: &[Session], now: u64) -> Self {
+        let from = now.saturating_sub(7 * SECS_PER_DAY);
+        let before = from.saturating_sub(7 * SECS_PER_DAY);
+
+        // sessions are saved in order, so the week is the end of the history
+        let start = sessions.partition_point(|session| session.date < from);
+        let (earlier, week) = sessions.split_at(start);
+
+        let finished: Vec<&Session> = week.iter().filter(|session| session.finished).collect();
+        let last_week: Vec<&Session> = earlier
+            .iter()
+            .filter(|session| session.finished && session.date >= before)
+            .collect();
+
+        let mean = |sessions: &[&Session], value: fn(&Session) -> f64| {
+            (!sessions.is_empty()).then(|| {
+                sessions.iter().map(|session| value(session)).sum::<f64>() / sessions.len() as f64
+            })
+        };
+
+        let wpm = mean(&finished, |session| session.wpm);
+
+        let seen: HashSet<&str> = earlier
+            .iter()
+            .flat_map(typed_words)
+            .map(|(t, _)| t)
+            .collect();
+
+        // times each word was typed this week and how many of those were wrong
+        let mut typed: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+
+        for (target, right) in week.iter().flat_map(typed_words) {
+            let entry = typed.entry(target).or_default();
+            entry.0 += 1;
+            entry.1 += usize::from(!right);
+        }
+
+        let mut focus: Vec<(&str, usize)> = typed
+            .iter()
+            .filter(|(_, (_, errors))| *errors > 0)
+            .map(|(word, (_, errors))| (*word, *errors))
+            .collect();
+        focus.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Self {
+            from,
+            to: now,
+            tests: finished.len(),
+            minutes: week.iter().map(|session| session.seconds).sum::<f64>() / 60.0,
+            wpm: wpm.unwrap_or_default(),
+            wpm_change: wpm
+                .zip(mean(&last_week, |session| session.wpm))
+                .map(|(wpm, last)| wpm - last),
+            accuracy: mean(&finished, |session| session.accuracy).unwrap_or_default(),
+            new_words: typed
+                .keys()
+                .filter(|word| !seen.contains(*word))
+                .map(|word| (*word).to_string())
+                .collect(),
+            mastered: typed
+                .iter()
+                .filter(|(_, (count, errors))| *count >= MASTERED_COUNT && *errors == 0)
+                .map(|(word, _)| (*word).to_string())
+                .collect(),
+            focus_words: focus
+                .into_iter()
+                .take(FOCUS_COUNT)
+                .map(|(word, _)| word.to_string())
+                .collect(),
+            focus_bigrams: bigrams::weak::<2>(week, FOCUS_COUNT)
+                .into_iter()
+                .map(|[a, b]| format!("{a}{b}"))
+                .collect(),
+        }
+    }
+
+    fn change(&self) -> String {
+        match self.wpm_change {
+            Some(change) if change >= 0.0 => format!("up {change:.1} from the week before"),
+            Some(change) => format!("down {:.1} from the week before", -change),
+            None => "nothing typed the week before".to_string(),
+        }
+    }
+
+    fn print_text(&self) {
+        println!(
+            "week of {} to {}",
+            &date::format(self.from)[..10],
+            &date::format(self.to)[..10]
+        );
+        println!(
+            "practiced {:.0} minutes over {} tests",
+            self.minutes, self.tests
+        );
+        println!("wpm {:.1}, {}", self.wpm, self.change());
+        println!("accuracy {:.1}%", self.accuracy * 100.0);
+        println!("{}", list("new words", &self.new_words));
+        println!("{}", list("mastered", &self.mastered));
+        println!("{}", list("focus on words", &self.focus_words));
+        println!("{}", list("focus on letter pairs", &self.focus_bigrams));
+    }
+
+    fn print_html(&self) {
+        let items = [
+            format!(
+                "practiced {:.0} minutes over {} tests",
+                self.minutes, self.tests
+            ),
+            format!("wpm {:.1}, {}", self.wpm, self.change()),
+            format!("accuracy {:.1}%", self.accuracy * 100.0),
+            list("new words", &self.new_words),
+            list("mastered", &self.mastered),
+            list("focus on words", &self.focus_words),
+            list("focus on letter pairs", &self.focus_bigrams),
+        ];
+
+        println!("<!DOCTYPE html>");
+        println!("<html><head><meta charset=\"utf-8\"><title>tt weekly report</title></head>");
+        println!("<body>");
+        println!(
+            "<h1>week of {} to {}</h1>",
+            &date::format(self.from)[..10],
+            &date::format(self.to)[..10]
+        );
+        println!("<ul>");
+
+        for item in items {
+            println!("<li>{}</li>", escape(&item));
+        }
+
+        println!("</ul>");
+        println!("</body></html>");
+    }
+}
+
+/// print a summary of the last seven days of one history file
+pub fn run(path: &Path, format: Format, html: bool) -> Result<()> {
+    let history = History::load(path)?;
+    let report = WeekReport::new(&history.sessions, date::now());
+
+    match format {
+        Format::Text if html => report.print_html(),
+        Format::Text => report.print_text(),
+        Format::Json | Format::Toml | Format::Tsv => format.print_document(&report)?,
+    }
+
+    Ok(())
+}
+
+/// each target word reached with whether it was typed right
+fn typed_words(session: &Session) -> impl Iterator<Item = (&str, bool)> {
+    session
+        .target
+        .split_whitespace()
+        .zip(session.input.split_whitespace())
+        .map(|(target, input)| (punctuation::bare(target), target == input))
+}
+
+/// a labelled list on one line, "none" when it is empty
+fn list(label: &str, words: &[String]) -> String {
+    if words.is_empty() {
+        format!("{label}: none")
+    } else {
+        format!("{label} ({}): {}", words.len(), words.join(", "))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
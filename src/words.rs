@@ -0,0 +1,148 @@
+use crate::error::{Error, Result};
+use rand::{rngs::StdRng, seq::IndexedRandom, SeedableRng};
+use std::{
+    collections::HashMap,
+    hash::BuildHasher,
+    sync::{LazyLock, OnceLock},
+};
+
+/// words from the user's words.toml, set before `WORDS` is first read
+static USER_WORDS: OnceLock<HashMap<String, UserWord>> = OnceLock::new();
+
+#[cfg(not(feature = "compressed"))]
+pub static WORDS: LazyLock<HashMap<String, toml::Table>> = LazyLock::new(|| {
+    with_user_words(
+        toml::from_str(include_str!("res/words.toml")).expect("failed to parse words.toml"),
+    )
+});
+
+#[cfg(feature = "compressed")]
+pub static WORDS: LazyLock<HashMap<String, toml::Table>> = LazyLock::new(|| {
+    use std::io::Read;
+
+    let bz2 = include_bytes!("res/words.toml.bz2").as_slice();
+    let mut toml = String::new();
+    let mut decompressor = bzip2::read::BzDecoder::new(bz2);
+
+    decompressor
+        .read_to_string(&mut toml)
+        .expect("failed to decompress words");
+
+    with_user_words(toml::from_str(&toml).expect("failed to parse words.toml"))
+});
+
+/// whether the build had a frequency table, without one every word would weigh the same
+pub static HAS_FREQUENCIES: LazyLock<bool> =
+    LazyLock::new(|| WORDS.values().any(|word| word.contains_key("frequency")));
+
+/// the words the user's words.toml added to the linku ones, in no order
+pub static ADDED_WORDS: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    WORDS
+        .iter()
+        .filter(|(_, toml)| toml.get("user").and_then(toml::Value::as_bool) == Some(true))
+        .map(|(word, _)| word.as_str())
+        .collect()
+});
+
+/// a word that isn't in the linku data, like a new word a community is trying out
+#[derive(serde::Deserialize)]
+#[serde(default)]
+pub struct UserWord {
+    pub definition: String,
+    /// the linku category it is weighted as
+    pub usage_category: String,
+    pub deprecated: bool,
+}
+
+impl Default for UserWord {
+    fn default() -> Self {
+        Self {
+            definition: String::new(),
+            usage_category: "sandbox".to_string(),
+            deprecated: false,
+        }
+    }
+}
+
+/// add words to the dictionary and tests, this does nothing once `WORDS` has been read
+pub fn add_user_words<S: BuildHasher>(words: HashMap<String, UserWord, S>) {
+    _ = USER_WORDS.set(words.into_iter().collect());
+}
+
+/// the linku words with the user's ones that aren't already among them
+fn with_user_words(mut words: HashMap<String, toml::Table>) -> HashMap<String, toml::Table> {
+    for (word, user_word) in USER_WORDS.get().into_iter().flatten() {
+        words.entry(word.clone()).or_insert_with(|| {
+            let mut toml = toml::Table::new();
+            toml.insert("word".into(), word.clone().into());
+            toml.insert("definition".into(), user_word.definition.clone().into());
+            toml.insert(
+                "usage_category".into(),
+                user_word.usage_category.clone().into(),
+            );
+            toml.insert("deprecated".into(), user_word.deprecated.into());
+            toml.insert("user".into(), true.into());
+            toml
+        });
+    }
+
+    words
+}
+
+/// get a string field of a word entry
+///
+/// # Errors
+/// `Error::MissingField` when the word doesn't have it
+pub fn word_field<'a>(toml: &'a toml::Table, field: &'static str) -> Result<&'a str> {
+    toml.get(field)
+        .and_then(toml::Value::as_str)
+        .ok_or(Error::MissingField { field })
+}
+
+/// the word of a day counted from the unix epoch, a core or common word that is
+/// the same for everyone on that day
+pub fn word_of_the_day(day: u64) -> Option<&'static str> {
+    let mut words: Vec<&'static str> = WORDS
+        .iter()
+        .filter(|(_, toml)| {
+            word_field(toml, "usage_category")
+                .is_ok_and(|category| category == "core" || category == "common")
+        })
+        .map(|(word, _)| word.as_str())
+        .collect();
+    words.sort_unstable();
+
+    words.choose(&mut StdRng::seed_from_u64(day)).copied()
+}
+
+/// the english translation of a word given most often in ku
+#[must_use]
+pub fn gloss(word: &str) -> Option<&'static str> {
+    glosses(word).first().copied()
+}
+
+/// the english translations ku gives a word, most given first
+pub fn glosses(word: &str) -> Vec<&'static str> {
+    let mut glosses: Vec<(&str, i64)> = WORDS
+        .get(word)
+        .and_then(|toml| toml.get("ku_data"))
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten()
+        .map(|(gloss, count)| (gloss.as_str(), count.as_integer().unwrap_or_default()))
+        .collect();
+
+    glosses.sort_by_key(|(gloss, count)| (-count, *gloss));
+    glosses.into_iter().map(|(gloss, _)| gloss).collect()
+}
+
+/// the sitelen pona glyph of a word from its UCSUR code point, like "U+F1900" for a
+pub fn glyph(word: &str) -> Option<char> {
+    let code = WORDS
+        .get(word)?
+        .get("ucsur")?
+        .as_str()?
+        .strip_prefix("U+")?;
+
+    char::from_u32(u32::from_str_radix(code, 16).ok()?)
+}